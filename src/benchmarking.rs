@@ -0,0 +1,1240 @@
+//! Benchmarks for the reward pallet.
+//!
+//! Privileged calls are dispatched by the on-chain `Admin`, which is accepted
+//! wherever `FundingOrigin` or `ParamsOrigin` are, so the results don't depend on
+//! how a runtime configures those origins. Claims are benchmarked for a returning
+//! claimant with a full `ClaimHistory`, paying into a payout destination that
+//! does not exist yet.
+
+use super::*;
+use codec::Encode;
+use frame_benchmarking::v2::*;
+use frame_support::{
+    dispatch::DispatchClass,
+    traits::{Currency, EnsureOrigin, Get, Hooks, LockableCurrency, WithdrawReasons},
+    weights::Weight,
+    BoundedVec,
+};
+use frame_system::RawOrigin;
+use sp_core::{crypto::KeyTypeId, sr25519, H256};
+use sp_io::hashing::blake2_256;
+use sp_runtime::{
+    traits::{Bounded, Saturating, Zero},
+    Permill,
+};
+use sp_std::{vec, vec::Vec};
+
+const SEED: u32 = 0;
+
+/// The keystore key type used to sign claim vouchers.
+const VOUCHER_KEY_TYPE: KeyTypeId = KeyTypeId(*b"rwrd");
+
+/// The balance every benchmark account is funded with.
+fn large_balance<T: Config>() -> BalanceOf<T> {
+    T::Currency::minimum_balance().saturating_mul(1_000_000u32.into())
+}
+
+/// An account holding `large_balance`.
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+    let who = account(name, index, SEED);
+    T::Currency::make_free_balance_be(&who, large_balance::<T>());
+    who
+}
+
+/// Make the whitelisted caller the funded `Admin`.
+fn admin<T: Config>() -> T::AccountId {
+    let admin: T::AccountId = whitelisted_caller();
+    T::Currency::make_free_balance_be(&admin, large_balance::<T>());
+    Admin::<T>::put(&admin);
+    admin
+}
+
+/// An amount every per-claim and per-payout limit of the runtime accepts, and
+/// large enough to create a fresh account.
+fn claim_amount<T: Config>() -> BalanceOf<T> {
+    let limits =
+        [T::ClaimCapPerPeriod::get(), T::MaxClaimedPerBlock::get(), T::DirectPayoutLimit::get()];
+    limits
+        .into_iter()
+        .filter(|limit| !limit.is_zero())
+        .fold(T::Currency::minimum_balance().saturating_mul(10u32.into()), |amount, limit| {
+            amount.min(limit)
+        })
+}
+
+/// A pool size that leaves room for top-ups under `MaxPoolSize`.
+fn pool_amount<T: Config>() -> BalanceOf<T> {
+    let amount = claim_amount::<T>().saturating_mul(1_000u32.into());
+    let max = T::MaxPoolSize::get();
+    if max.is_zero() {
+        amount
+    } else {
+        amount.min(max / 2u32.into())
+    }
+}
+
+/// Mint `amount` into the pot, keeping the existential deposit on top.
+fn fund_pot<T: Config>(amount: BalanceOf<T>) {
+    let pot = Pallet::<T>::account_id();
+    let _ = T::Currency::deposit_creating(
+        &pot,
+        amount.saturating_add(T::Currency::minimum_balance()),
+    );
+    TotalToppedUp::<T>::mutate(|total| *total = total.saturating_add(amount));
+}
+
+/// Add `amount` of backed funds to the reward pool.
+fn fund_pool<T: Config>(amount: BalanceOf<T>) {
+    fund_pot::<T>(amount);
+    RewardPool::<T>::mutate(|pool| *pool = pool.saturating_add(amount));
+}
+
+/// Add `amount` of backed funds set aside for a payout, as accrual and era
+/// settlement do.
+fn fund_allocated<T: Config>(amount: BalanceOf<T>) {
+    fund_pot::<T>(amount);
+    TotalAllocated::<T>::mutate(|total| *total = total.saturating_add(amount));
+}
+
+/// Allocate `amount` to `who` out of freshly funded pool.
+fn allocate<T: Config>(who: &T::AccountId, amount: BalanceOf<T>) {
+    fund_pool::<T>(amount.saturating_add(Pallet::<T>::min_pool_reserve()));
+    Pallet::<T>::do_allocate(who, amount).expect("the pool covers the allocation; qed");
+}
+
+/// A whitelisted, funded claimant with a full `ClaimHistory`, an earlier claim
+/// just past the cooldown and a payout destination that does not exist yet.
+fn returning_claimant<T: Config>(name: &'static str) -> T::AccountId {
+    let who = funded_account::<T>(name, 0);
+    Initialized::<T>::put(true);
+    Params::<T>::mutate(|params| params.min_claim_amount = Zero::zero());
+    Claimers::<T>::insert(&who, ());
+    PayoutDestination::<T>::insert(&who, account::<T::AccountId>("destination", 0, SEED));
+
+    // Claiming evicts the oldest history entry
+    let record = ClaimRecord { amount: claim_amount::<T>(), block: Zero::zero() };
+    let history = vec![record; T::MaxHistoryEntries::get() as usize];
+    ClaimHistory::<T>::insert(&who, BoundedVec::truncate_from(history));
+
+    LastClaimBlock::<T>::insert(&who, T::BlockNumber::zero());
+    let cooldown = Pallet::<T>::params().claim_cooldown;
+    frame_system::Pallet::<T>::set_block_number(cooldown.saturating_add(1u32.into()));
+    who
+}
+
+/// A sub-pool identifier of the maximum length.
+fn pool_id<T: Config>() -> PoolId<T> {
+    BoundedVec::truncate_from(vec![b'p'; T::MaxPoolIdLength::get() as usize])
+}
+
+/// Fill `PendingDrips` with `count` drips funded by `funder`, escrowing their funds.
+fn pending_drips<T: Config>(funder: &T::AccountId, count: u32) {
+    let amount = claim_amount::<T>();
+    let drips: Vec<_> = (0..count)
+        .map(|id| DripInfo { id, funder: funder.clone(), remaining: amount, per_block: amount })
+        .collect();
+    PendingDrips::<T>::put(BoundedVec::truncate_from(drips));
+    NextDripId::<T>::put(count);
+    T::Currency::make_free_balance_be(
+        &Pallet::<T>::drip_account(),
+        amount.saturating_mul(count.into()).saturating_add(T::Currency::minimum_balance()),
+    );
+}
+
+/// Fill `ScheduledParamChanges` with `count` changes that never apply.
+fn scheduled_param_changes<T: Config>(count: u32) {
+    let changes: Vec<_> = (0..count)
+        .map(|id| ScheduledParamChange {
+            id,
+            change: PartialParams::default(),
+            effective_at: T::BlockNumber::max_value(),
+        })
+        .collect();
+    ScheduledParamChanges::<T>::put(BoundedVec::truncate_from(changes));
+    NextParamChangeId::<T>::put(count);
+}
+
+/// Insert a payout proposal for a fresh beneficiary, returning its id.
+fn payout_proposal<T: Config>(proposer: T::AccountId, expires_at: T::BlockNumber) -> u32 {
+    let id = NextPayoutProposalId::<T>::get();
+    PayoutProposals::<T>::insert(
+        id,
+        PayoutProposal {
+            proposer,
+            beneficiary: account("beneficiary", 0, SEED),
+            amount: claim_amount::<T>(),
+            expires_at,
+        },
+    );
+    NextPayoutProposalId::<T>::put(id.wrapping_add(1));
+    id
+}
+
+/// Make the current block count as non-empty, so `RewardEmptyBlocks` doesn't skip
+/// its reward.
+fn note_non_empty_block<T: Config>() {
+    frame_system::Pallet::<T>::note_applied_extrinsic(&Ok(().into()), Default::default());
+    frame_system::Pallet::<T>::note_finished_extrinsics();
+    frame_system::Pallet::<T>::register_extra_weight_unchecked(
+        Weight::from_parts(1, 0),
+        DispatchClass::Normal,
+    );
+}
+
+/// An update setting every parameter to its current value.
+fn full_update<T: Config>() -> PartialParamsOf<T> {
+    let params = Pallet::<T>::params();
+    PartialParams {
+        min_pool_reserve: Some(params.min_pool_reserve),
+        eligibility_min_balance: Some(params.eligibility_min_balance),
+        era_budget: Some(params.era_budget),
+        reward_per_block: Some(params.reward_per_block),
+        min_consensus_fee: Some(params.min_consensus_fee),
+        fee_split: Some(params.fee_split),
+        claim_cooldown: Some(params.claim_cooldown),
+        min_claim_amount: Some(params.min_claim_amount),
+        dust_threshold: Some(params.dust_threshold),
+    }
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn top_up_pool() {
+        let admin = admin::<T>();
+        fund_pool::<T>(pool_amount::<T>());
+        let amount = claim_amount::<T>();
+        let before = Pallet::<T>::reward_pool();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), amount);
+
+        assert_eq!(Pallet::<T>::reward_pool(), before.saturating_add(amount));
+    }
+
+    #[benchmark]
+    fn force_top_up_pool() {
+        fund_pool::<T>(pool_amount::<T>());
+        let amount = claim_amount::<T>();
+        let before = Pallet::<T>::reward_pool();
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, amount);
+
+        assert_eq!(Pallet::<T>::reward_pool(), before.saturating_add(amount));
+    }
+
+    #[benchmark]
+    fn donate_to_pool() {
+        let caller = funded_account::<T>("donor", 0);
+        fund_pool::<T>(pool_amount::<T>());
+        let amount = claim_amount::<T>().max(T::MinDonation::get());
+        let before = Pallet::<T>::reward_pool();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), amount);
+
+        assert_eq!(Pallet::<T>::reward_pool(), before.saturating_add(amount));
+    }
+
+    #[benchmark]
+    fn claim_reward() {
+        let caller = returning_claimant::<T>("claimant");
+        let amount = claim_amount::<T>();
+        allocate::<T>(&caller, amount.saturating_mul(2u32.into()));
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), amount, Some([1u8; 32]));
+
+        assert_eq!(Pallet::<T>::allocations(&caller), amount);
+    }
+
+    #[benchmark]
+    fn claim_reward_with_memo() {
+        let caller = returning_claimant::<T>("claimant");
+        let amount = claim_amount::<T>();
+        allocate::<T>(&caller, amount.saturating_mul(2u32.into()));
+        let memo = BoundedVec::truncate_from(vec![b'm'; T::MaxMemoLength::get() as usize]);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), amount, memo);
+
+        assert_eq!(Pallet::<T>::allocations(&caller), amount);
+    }
+
+    #[benchmark]
+    fn claim_all() {
+        let caller = returning_claimant::<T>("claimant");
+        allocate::<T>(&caller, claim_amount::<T>());
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()));
+
+        assert!(Pallet::<T>::allocations(&caller).is_zero());
+    }
+
+    #[benchmark]
+    fn payout_to() {
+        let caller = funded_account::<T>("caller", 0);
+        let beneficiary = returning_claimant::<T>("beneficiary");
+        allocate::<T>(
+            &beneficiary,
+            claim_amount::<T>().saturating_add(T::PayoutCallerReward::get()),
+        );
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), beneficiary.clone());
+
+        assert!(Pallet::<T>::allocations(&beneficiary).is_zero());
+    }
+
+    #[benchmark]
+    fn allocate_reward() {
+        let admin = admin::<T>();
+        fund_pool::<T>(pool_amount::<T>());
+        let who: T::AccountId = account("who", 0, SEED);
+        let amount = claim_amount::<T>();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), who.clone(), amount);
+
+        assert_eq!(Pallet::<T>::allocations(&who), amount);
+    }
+
+    #[benchmark]
+    fn allocate_many(n: Linear<1, { T::MaxAllocationsPerCall::get() }>) {
+        let admin = admin::<T>();
+        let amount = claim_amount::<T>();
+        fund_pool::<T>(amount.saturating_mul(n.into()));
+        let allocations: Vec<_> =
+            (0..n).map(|i| (account::<T::AccountId>("who", i, SEED), amount)).collect();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), BoundedVec::truncate_from(allocations));
+
+        assert_eq!(Pallet::<T>::total_allocated(), amount.saturating_mul(n.into()));
+    }
+
+    #[benchmark]
+    fn revoke_allocation() {
+        let admin = admin::<T>();
+        let who: T::AccountId = account("who", 0, SEED);
+        allocate::<T>(&who, claim_amount::<T>());
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), who.clone(), None);
+
+        assert!(Pallet::<T>::allocations(&who).is_zero());
+    }
+
+    #[benchmark]
+    fn force_set_reward_pool() {
+        fund_pool::<T>(pool_amount::<T>());
+        let new_value = pool_amount::<T>() / 2u32.into();
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, new_value);
+
+        assert_eq!(Pallet::<T>::reward_pool(), new_value);
+    }
+
+    #[benchmark]
+    fn withdraw_from_pool() {
+        let admin = admin::<T>();
+        fund_pool::<T>(pool_amount::<T>());
+        let amount = claim_amount::<T>();
+        let beneficiary: T::AccountId = account("beneficiary", 0, SEED);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), amount, beneficiary.clone());
+
+        assert_eq!(T::Currency::free_balance(&beneficiary), amount);
+    }
+
+    #[benchmark]
+    fn burn_pool() {
+        let admin = admin::<T>();
+        fund_pool::<T>(pool_amount::<T>());
+        let amount = claim_amount::<T>();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), amount);
+
+        assert_eq!(Pallet::<T>::reward_pool(), pool_amount::<T>().saturating_sub(amount));
+    }
+
+    #[benchmark]
+    fn sync_pool() {
+        let caller = funded_account::<T>("caller", 0);
+        // A surplus in the pot that the pool doesn't account for yet
+        let _ = T::Currency::deposit_creating(
+            &Pallet::<T>::account_id(),
+            claim_amount::<T>().saturating_add(T::Currency::minimum_balance()),
+        );
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller));
+
+        assert!(!Pallet::<T>::reward_pool().is_zero());
+    }
+
+    #[benchmark]
+    fn create_pool() {
+        let admin = admin::<T>();
+        let id = pool_id::<T>();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), id.clone());
+
+        assert!(Pallet::<T>::pools(&id).is_some());
+    }
+
+    #[benchmark]
+    fn top_up_sub_pool() {
+        let admin = admin::<T>();
+        let id = pool_id::<T>();
+        Pools::<T>::insert(&id, PoolInfo::default());
+        PoolCount::<T>::put(1);
+        let amount = claim_amount::<T>();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), id.clone(), amount);
+
+        assert_eq!(Pallet::<T>::pools(&id).map(|info| info.balance), Some(amount));
+    }
+
+    #[benchmark]
+    fn claim_from_pool() {
+        let caller = returning_claimant::<T>("claimant");
+        let id = pool_id::<T>();
+        let amount = claim_amount::<T>();
+        Pools::<T>::insert(&id, PoolInfo::default());
+        PoolCount::<T>::put(1);
+        PoolAllocations::<T>::insert(&id, &caller, amount);
+        T::Currency::make_free_balance_be(
+            &Pallet::<T>::sub_pool_account(&id),
+            amount.saturating_add(T::Currency::minimum_balance()),
+        );
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), id.clone(), amount);
+
+        let destination = Pallet::<T>::payout_account(&caller);
+        assert_eq!(T::Currency::free_balance(&destination), amount);
+        assert!(Pallet::<T>::pool_allocations(&id, &caller).is_zero());
+    }
+
+    #[benchmark]
+    fn allocate_from_pool() {
+        let admin = admin::<T>();
+        let id = pool_id::<T>();
+        let amount = claim_amount::<T>();
+        Pools::<T>::insert(&id, PoolInfo { balance: amount, total_distributed: Zero::zero() });
+        PoolCount::<T>::put(1);
+        let who: T::AccountId = account("who", 0, SEED);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), id.clone(), who.clone(), amount);
+
+        assert_eq!(Pallet::<T>::pool_allocations(&id, &who), amount);
+    }
+
+    #[benchmark]
+    fn schedule_top_up() {
+        let admin = admin::<T>();
+        pending_drips::<T>(&admin, T::MaxPendingDrips::get().saturating_sub(1));
+        let amount = claim_amount::<T>();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), amount, amount);
+
+        assert_eq!(Pallet::<T>::pending_drips().len() as u32, T::MaxPendingDrips::get());
+    }
+
+    #[benchmark]
+    fn cancel_top_up() {
+        let admin = admin::<T>();
+        let count = T::MaxPendingDrips::get();
+        pending_drips::<T>(&admin, count);
+        let refund_to: T::AccountId = account("refund", 0, SEED);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), count - 1, refund_to);
+
+        assert_eq!(Pallet::<T>::pending_drips().len() as u32, count - 1);
+    }
+
+    #[benchmark]
+    fn set_min_pool_reserve() {
+        let admin = admin::<T>();
+        let reserve = claim_amount::<T>();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), reserve);
+
+        assert_eq!(Pallet::<T>::min_pool_reserve(), reserve);
+    }
+
+    #[benchmark]
+    fn set_payout_destination() {
+        let caller = funded_account::<T>("caller", 0);
+        let dest: T::AccountId = account("destination", 0, SEED);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), Some(dest.clone()));
+
+        assert_eq!(Pallet::<T>::payout_account(&caller), dest);
+    }
+
+    #[benchmark]
+    fn prune_claim_history() {
+        let admin = admin::<T>();
+        let who = returning_claimant::<T>("claimant");
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), who.clone());
+
+        assert!(Pallet::<T>::claim_history(&who).is_empty());
+    }
+
+    #[benchmark]
+    fn set_claim_root() {
+        let admin = admin::<T>();
+        fund_pool::<T>(pool_amount::<T>());
+        // Replacing a root returns its unclaimed remainder to the pool
+        fund_allocated::<T>(claim_amount::<T>());
+        ClaimRoot::<T>::put((H256::repeat_byte(2), claim_amount::<T>()));
+        let root = H256::repeat_byte(1);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), root, claim_amount::<T>());
+
+        assert_eq!(Pallet::<T>::claim_root().map(|(root, _)| root), Some(root));
+    }
+
+    #[benchmark]
+    fn claim_with_proof(p: Linear<0, { T::MaxProofLen::get() }>) {
+        let caller = returning_claimant::<T>("claimant");
+        let amount = claim_amount::<T>();
+        fund_allocated::<T>(amount);
+
+        let leaf = H256(blake2_256(&(&caller, amount).encode()));
+        let proof: Vec<_> = (0..p).map(|i| H256::repeat_byte(i as u8)).collect();
+        let root = Pallet::<T>::proof_root(leaf, &proof);
+        ClaimRoot::<T>::put((root, amount));
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), amount, BoundedVec::truncate_from(proof));
+
+        assert!(ClaimedLeaves::<T>::contains_key(root, leaf));
+    }
+
+    #[benchmark]
+    fn set_claim_signer() {
+        let admin = admin::<T>();
+        let signer = sr25519::Public::from_raw([1u8; 32]);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), Some(signer));
+
+        assert_eq!(Pallet::<T>::claim_signer(), Some(signer));
+    }
+
+    #[benchmark]
+    fn claim_with_voucher() {
+        let caller = returning_claimant::<T>("claimant");
+        let amount = claim_amount::<T>();
+        fund_pool::<T>(pool_amount::<T>());
+
+        let signer = sp_io::crypto::sr25519_generate(VOUCHER_KEY_TYPE, None);
+        ClaimSigner::<T>::put(signer);
+        let nonce = 0u64;
+        let payload = Pallet::<T>::voucher_payload(&caller, amount, nonce);
+        let signature = sp_io::crypto::sr25519_sign(VOUCHER_KEY_TYPE, &signer, &payload)
+            .expect("the key was just generated; qed");
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), amount, nonce, signature);
+
+        assert!(UsedNonces::<T>::contains_key(nonce));
+    }
+
+    #[benchmark]
+    fn add_claimer() {
+        let admin = admin::<T>();
+        let who: T::AccountId = account("claimer", 0, SEED);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), who.clone());
+
+        assert!(Claimers::<T>::contains_key(&who));
+    }
+
+    #[benchmark]
+    fn remove_claimer() {
+        let admin = admin::<T>();
+        let who: T::AccountId = account("claimer", 0, SEED);
+        Claimers::<T>::insert(&who, ());
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), who.clone());
+
+        assert!(!Claimers::<T>::contains_key(&who));
+    }
+
+    #[benchmark]
+    fn add_claimers(n: Linear<1, { T::MaxClaimersPerCall::get() }>) {
+        let admin = admin::<T>();
+        let who: Vec<T::AccountId> = (0..n).map(|i| account("claimer", i, SEED)).collect();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), BoundedVec::truncate_from(who));
+
+        assert_eq!(Claimers::<T>::count(), n);
+    }
+
+    #[benchmark]
+    fn remove_claimers(n: Linear<1, { T::MaxClaimersPerCall::get() }>) {
+        let admin = admin::<T>();
+        let who: Vec<T::AccountId> = (0..n).map(|i| account("claimer", i, SEED)).collect();
+        for account in who.iter() {
+            Claimers::<T>::insert(account, ());
+        }
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), BoundedVec::truncate_from(who));
+
+        assert_eq!(Claimers::<T>::count(), 0);
+    }
+
+    #[benchmark]
+    fn ban_claimer() {
+        let admin = admin::<T>();
+        let who: T::AccountId = account("claimer", 0, SEED);
+        allocate::<T>(&who, claim_amount::<T>());
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), who.clone(), true);
+
+        assert!(Pallet::<T>::is_banned(&who));
+        assert!(Pallet::<T>::allocations(&who).is_zero());
+    }
+
+    #[benchmark]
+    fn unban_claimer() {
+        let admin = admin::<T>();
+        let who: T::AccountId = account("claimer", 0, SEED);
+        BannedClaimers::<T>::insert(&who, ());
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), who.clone());
+
+        assert!(!Pallet::<T>::is_banned(&who));
+    }
+
+    #[benchmark]
+    fn set_eligibility_min_balance() {
+        let admin = admin::<T>();
+        let min_balance = T::Currency::minimum_balance();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), min_balance);
+
+        assert_eq!(Pallet::<T>::eligibility_min_balance(), min_balance);
+    }
+
+    #[benchmark]
+    fn force_payout_many(n: Linear<1, { T::MaxPayoutsPerCall::get() }>) {
+        let admin = admin::<T>();
+        let amount = claim_amount::<T>();
+        fund_pool::<T>(amount.saturating_mul(n.into()));
+        // Every payee is a fresh account
+        let payouts: Vec<_> =
+            (0..n).map(|i| (account::<T::AccountId>("payee", i, SEED), amount)).collect();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), BoundedVec::truncate_from(payouts));
+
+        assert_eq!(Pallet::<T>::total_distributed(), amount.saturating_mul(n.into()));
+    }
+
+    #[benchmark]
+    fn sweep_expired(n: Linear<1, { T::MaxSweepPerCall::get() }>) {
+        let caller = funded_account::<T>("caller", 0);
+        let amount = claim_amount::<T>();
+        fund_allocated::<T>(amount.saturating_mul(n.into()));
+        let accounts: Vec<T::AccountId> = (0..n).map(|i| account("who", i, SEED)).collect();
+        for who in accounts.iter() {
+            Allocations::<T>::insert(who, AllocationInfo { amount, expires_at: Zero::zero() });
+        }
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), BoundedVec::truncate_from(accounts));
+
+        assert!(Pallet::<T>::total_allocated().is_zero());
+    }
+
+    #[benchmark]
+    fn prune_claim_keys(n: Linear<1, { T::MaxSweepPerCall::get() }>) {
+        let caller = funded_account::<T>("caller", 0);
+        let who: T::AccountId = account("claimant", 0, SEED);
+        let keys: Vec<ClaimKey> = (0..n).map(|i| blake2_256(&i.encode())).collect();
+        for key in keys.iter() {
+            ProcessedClaimKeys::<T>::insert(&who, key, T::BlockNumber::zero());
+        }
+        // Every key is past its retention
+        frame_system::Pallet::<T>::set_block_number(
+            T::ClaimKeyRetention::get().saturating_add(1u32.into()),
+        );
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), who.clone(), BoundedVec::truncate_from(keys));
+
+        assert_eq!(ProcessedClaimKeys::<T>::iter_prefix(&who).count(), 0);
+    }
+
+    #[benchmark]
+    fn set_claim_delegate() {
+        let caller = funded_account::<T>("principal", 0);
+        let delegate: T::AccountId = account("delegate", 0, SEED);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), Some(delegate.clone()));
+
+        assert_eq!(Pallet::<T>::claim_delegate(&caller), Some(delegate));
+    }
+
+    #[benchmark]
+    fn claim_as_delegate() {
+        let delegate = funded_account::<T>("delegate", 0);
+        let principal = returning_claimant::<T>("principal");
+        let amount = claim_amount::<T>();
+        allocate::<T>(&principal, amount.saturating_mul(2u32.into()));
+        ClaimDelegate::<T>::insert(&principal, &delegate);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(delegate), principal.clone(), amount);
+
+        assert_eq!(Pallet::<T>::allocations(&principal), amount);
+    }
+
+    #[benchmark]
+    fn request_claim() {
+        let caller = funded_account::<T>("claimant", 0);
+        let amount = claim_amount::<T>();
+        let count = T::MaxPendingClaims::get().saturating_sub(1);
+        let queue: Vec<_> =
+            (0..count).map(|id| PendingClaim { id, who: caller.clone(), amount }).collect();
+        PendingClaims::<T>::put(BoundedVec::truncate_from(queue));
+        NextClaimRequestId::<T>::put(count);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), amount);
+
+        assert_eq!(Pallet::<T>::pending_claims().len() as u32, count + 1);
+    }
+
+    #[benchmark]
+    fn cancel_claim_request() {
+        let caller = funded_account::<T>("claimant", 0);
+        let amount = claim_amount::<T>();
+        let count = T::MaxPendingClaims::get();
+        let queue: Vec<_> =
+            (0..count).map(|id| PendingClaim { id, who: caller.clone(), amount }).collect();
+        PendingClaims::<T>::put(BoundedVec::truncate_from(queue));
+        NextClaimRequestId::<T>::put(count);
+
+        // The last request is the furthest to search for
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), count - 1);
+
+        assert_eq!(Pallet::<T>::pending_claims().len() as u32, count - 1);
+    }
+
+    #[benchmark]
+    fn claim_reward_vested() {
+        let caller = returning_claimant::<T>("claimant");
+        let amount = claim_amount::<T>();
+        allocate::<T>(&caller, amount.saturating_mul(2u32.into()));
+        // Merge into an existing schedule on the destination
+        let dest = Pallet::<T>::payout_account(&caller);
+        let now = frame_system::Pallet::<T>::block_number();
+        Vesting::<T>::insert(
+            &dest,
+            VestingInfo { locked: amount, per_block: 1u32.into(), starting_block: now },
+        );
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), amount, 1u32.into());
+
+        assert!(Pallet::<T>::vesting(&dest).is_some());
+    }
+
+    #[benchmark]
+    fn vest_rewards() {
+        let caller = funded_account::<T>("claimant", 0);
+        let locked = claim_amount::<T>();
+        T::Currency::set_lock(VESTING_ID, &caller, locked, WithdrawReasons::all());
+        Vesting::<T>::insert(
+            &caller,
+            VestingInfo { locked, per_block: 1u32.into(), starting_block: Zero::zero() },
+        );
+        // Part of the lock has unlocked, so it's updated rather than removed
+        frame_system::Pallet::<T>::set_block_number(1u32.into());
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()));
+
+        assert!(Pallet::<T>::vesting(&caller).is_some());
+    }
+
+    #[benchmark]
+    fn set_era_budget() {
+        let admin = admin::<T>();
+        let budget = claim_amount::<T>();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), budget);
+
+        assert_eq!(Pallet::<T>::era_budget(), budget);
+    }
+
+    #[benchmark]
+    fn claim_era_share() {
+        let caller = returning_claimant::<T>("claimant");
+        let amount = claim_amount::<T>();
+        fund_allocated::<T>(amount);
+        // The only earner, so the era is settled by this claim
+        EraPoints::<T>::insert(0, &caller, 1);
+        EraRewards::<T>::insert(
+            0,
+            EraReward { budget: amount, total_points: 1, remaining: amount, claimed_points: 0 },
+        );
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), 0);
+
+        assert!(Pallet::<T>::era_reward(0).is_none());
+    }
+
+    #[benchmark]
+    fn sweep_expired_era(n: Linear<1, { T::MaxSweepPerCall::get() }>) {
+        let caller = funded_account::<T>("caller", 0);
+        let amount = claim_amount::<T>();
+        fund_allocated::<T>(amount);
+        for i in 0..n {
+            EraPoints::<T>::insert(0, account::<T::AccountId>("earner", i, SEED), 1);
+        }
+        let total_points = n.into();
+        EraRewards::<T>::insert(
+            0,
+            EraReward { budget: amount, total_points, remaining: amount, claimed_points: 0 },
+        );
+        // Era 0 is past its claim lifetime
+        CurrentEra::<T>::put(T::EraClaimLifetime::get().saturating_add(1));
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), 0);
+
+        assert!(Pallet::<T>::era_reward(0).is_none());
+        assert!(Pallet::<T>::total_allocated().is_zero());
+    }
+
+    #[benchmark]
+    fn prune_receipts(n: Linear<1, { T::MaxSweepPerCall::get() }>) {
+        let admin = admin::<T>();
+        let who: T::AccountId = account("claimant", 0, SEED);
+        for id in 0..n as ClaimId {
+            Receipts::<T>::insert(
+                id,
+                Receipt { who: who.clone(), amount: claim_amount::<T>(), block: Zero::zero() },
+            );
+        }
+        NextClaimId::<T>::put(n as ClaimId);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), n as ClaimId - 1);
+
+        assert_eq!(Pallet::<T>::first_receipt_id(), n as ClaimId);
+    }
+
+    #[benchmark]
+    fn set_third_party_claims() {
+        let caller = funded_account::<T>("caller", 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), true);
+
+        assert!(Pallet::<T>::allows_third_party_claims(&caller));
+    }
+
+    #[benchmark]
+    fn claim_for() {
+        let caller = funded_account::<T>("caller", 0);
+        let beneficiary = returning_claimant::<T>("beneficiary");
+        allocate::<T>(&beneficiary, claim_amount::<T>());
+        ThirdPartyClaims::<T>::insert(&beneficiary, true);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), beneficiary.clone());
+
+        assert!(Pallet::<T>::allocations(&beneficiary).is_zero());
+    }
+
+    #[benchmark]
+    fn claim_block_rewards() {
+        let caller = funded_account::<T>("author", 0);
+        let amount = claim_amount::<T>();
+        fund_allocated::<T>(amount);
+        AuthorAccrued::<T>::insert(&caller, amount);
+        AuthorPayoutAccount::<T>::insert(&caller, account::<T::AccountId>("stash", 0, SEED));
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()));
+
+        assert!(Pallet::<T>::author_accrued(&caller).is_zero());
+    }
+
+    #[benchmark]
+    fn exclude_author() {
+        let admin = admin::<T>();
+        let who: T::AccountId = account("author", 0, SEED);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), who.clone());
+
+        assert!(ExcludedAuthors::<T>::contains_key(&who));
+    }
+
+    #[benchmark]
+    fn include_author() {
+        let admin = admin::<T>();
+        let who: T::AccountId = account("author", 0, SEED);
+        ExcludedAuthors::<T>::insert(&who, ());
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), who.clone());
+
+        assert!(!ExcludedAuthors::<T>::contains_key(&who));
+    }
+
+    #[benchmark]
+    fn set_reward_per_block() {
+        let admin = admin::<T>();
+        let reward = Some(T::RewardPerBlock::get());
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), reward);
+
+        assert_eq!(Pallet::<T>::reward_per_block_override(), reward);
+    }
+
+    #[benchmark]
+    fn pause_block_rewards() {
+        let admin = admin::<T>();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin));
+
+        assert!(Pallet::<T>::block_rewards_paused());
+    }
+
+    #[benchmark]
+    fn resume_block_rewards() {
+        let admin = admin::<T>();
+        BlockRewardsPaused::<T>::put(true);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin));
+
+        assert!(!Pallet::<T>::block_rewards_paused());
+    }
+
+    #[benchmark]
+    fn set_author_payout_account() {
+        let caller = funded_account::<T>("author", 0);
+        let dest: T::AccountId = account("stash", 0, SEED);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), Some(dest.clone()));
+
+        assert_eq!(Pallet::<T>::block_reward_account(&caller), dest);
+    }
+
+    #[benchmark]
+    fn set_admin() {
+        let admin = admin::<T>();
+        let new: T::AccountId = account("admin", 1, SEED);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), new.clone());
+
+        assert_eq!(Pallet::<T>::pending_admin(), Some(new));
+    }
+
+    #[benchmark]
+    fn accept_admin() {
+        admin::<T>();
+        let caller = funded_account::<T>("admin", 1);
+        PendingAdmin::<T>::put(&caller);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()));
+
+        assert_eq!(Pallet::<T>::admin(), Some(caller));
+    }
+
+    #[benchmark]
+    fn update_params() {
+        let admin = admin::<T>();
+        let update = full_update::<T>();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), update);
+    }
+
+    #[benchmark]
+    fn pause() -> Result<(), BenchmarkError> {
+        let origin =
+            T::PauseOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin);
+
+        assert!(Pallet::<T>::paused());
+        Ok(())
+    }
+
+    #[benchmark]
+    fn unpause() -> Result<(), BenchmarkError> {
+        let origin =
+            T::PauseOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        Paused::<T>::put(true);
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin);
+
+        assert!(!Pallet::<T>::paused());
+        Ok(())
+    }
+
+    #[benchmark]
+    fn schedule_param_change() {
+        let admin = admin::<T>();
+        let count = T::MaxScheduledParamChanges::get().saturating_sub(1);
+        scheduled_param_changes::<T>(count);
+        let effective_at = frame_system::Pallet::<T>::block_number().saturating_add(1u32.into());
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), full_update::<T>(), effective_at);
+
+        assert_eq!(Pallet::<T>::scheduled_param_changes().len() as u32, count + 1);
+    }
+
+    #[benchmark]
+    fn cancel_param_change() {
+        let admin = admin::<T>();
+        let count = T::MaxScheduledParamChanges::get();
+        scheduled_param_changes::<T>(count);
+
+        // The last change is the furthest to search for
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), count - 1);
+
+        assert_eq!(Pallet::<T>::scheduled_param_changes().len() as u32, count - 1);
+    }
+
+    #[benchmark]
+    fn set_top_up_allowance() {
+        let admin = admin::<T>();
+        let who: T::AccountId = account("operator", 0, SEED);
+        let allowance = claim_amount::<T>();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), who.clone(), allowance);
+
+        assert_eq!(Pallet::<T>::top_up_allowance(&who), Some(allowance));
+    }
+
+    #[benchmark]
+    fn set_min_consensus_fee() {
+        let admin = admin::<T>();
+        let fee = T::Currency::minimum_balance();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), fee);
+
+        assert_eq!(Pallet::<T>::min_consensus_fee(), fee);
+    }
+
+    #[benchmark]
+    fn set_fee_split() {
+        let admin = admin::<T>();
+        let split = FeeSplit {
+            dev: Permill::from_percent(50),
+            validator: Permill::from_percent(30),
+            burn: Permill::from_percent(10),
+        };
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), split);
+
+        assert_eq!(Pallet::<T>::params().fee_split, split);
+    }
+
+    #[benchmark]
+    fn propose_payout() {
+        let admin = admin::<T>();
+        let beneficiary: T::AccountId = account("beneficiary", 0, SEED);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), beneficiary, claim_amount::<T>());
+
+        assert_eq!(PayoutProposals::<T>::count(), 1);
+    }
+
+    #[benchmark]
+    fn approve_payout() {
+        let admin = admin::<T>();
+        fund_pool::<T>(pool_amount::<T>());
+        let id = payout_proposal::<T>(admin.clone(), T::BlockNumber::max_value());
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), id);
+
+        assert!(Pallet::<T>::payout_proposals(id).is_none());
+    }
+
+    #[benchmark]
+    fn reject_payout() {
+        let admin = admin::<T>();
+        let id = payout_proposal::<T>(admin.clone(), T::BlockNumber::max_value());
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), id);
+
+        assert!(Pallet::<T>::payout_proposals(id).is_none());
+    }
+
+    #[benchmark]
+    fn remove_expired_payout() {
+        let caller = funded_account::<T>("caller", 0);
+        let id = payout_proposal::<T>(caller.clone(), Zero::zero());
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), id);
+
+        assert!(Pallet::<T>::payout_proposals(id).is_none());
+    }
+
+    #[benchmark]
+    fn add_operator() {
+        let admin = admin::<T>();
+        let who: T::AccountId = account("operator", 0, SEED);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), who.clone());
+
+        assert!(Pallet::<T>::is_operator(&who));
+    }
+
+    #[benchmark]
+    fn remove_operator() {
+        let admin = admin::<T>();
+        let who: T::AccountId = account("operator", 0, SEED);
+        Operators::<T>::insert(&who, ());
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), who.clone());
+
+        assert!(!Pallet::<T>::is_operator(&who));
+    }
+
+    #[benchmark]
+    fn initialize() {
+        let source = funded_account::<T>("source", 0);
+        let params = Pallet::<T>::params();
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, source, claim_amount::<T>(), params);
+
+        assert!(Pallet::<T>::initialized());
+    }
+
+    #[benchmark]
+    fn freeze_params() {
+        #[extrinsic_call]
+        _(RawOrigin::Root);
+
+        assert!(Pallet::<T>::params_frozen());
+    }
+
+    #[benchmark]
+    fn set_claim_cooldown() {
+        let admin = admin::<T>();
+        let cooldown: T::BlockNumber = 10u32.into();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), cooldown);
+
+        assert_eq!(Pallet::<T>::params().claim_cooldown, cooldown);
+    }
+
+    #[benchmark]
+    fn set_min_claim_amount() {
+        let admin = admin::<T>();
+        let amount = T::Currency::minimum_balance();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), amount);
+
+        assert_eq!(Pallet::<T>::params().min_claim_amount, amount);
+    }
+
+    #[benchmark]
+    fn set_dust_threshold() {
+        let admin = admin::<T>();
+        let threshold = T::Currency::minimum_balance();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admin), threshold);
+
+        assert_eq!(Pallet::<T>::params().dust_threshold, threshold);
+    }
+
+    /// The block reward pre-charged in `on_initialize` and paid in `on_finalize`,
+    /// running both hooks for a block without era boundary, drips, queued claims
+    /// or scheduled changes. `r = 1` pays a new author into a fresh stash; `r = 0`
+    /// has block rewards paused, which only clears the noted author.
+    #[benchmark]
+    fn on_initialize_reward(r: Linear<0, 1>) {
+        let author: T::AccountId = account("author", 0, SEED);
+        NotedAuthor::<T>::put(&author);
+        frame_system::Pallet::<T>::set_block_number(1u32.into());
+
+        if r == 0 {
+            BlockRewardsPaused::<T>::put(true);
+        } else {
+            let reward = claim_amount::<T>();
+            Params::<T>::mutate(|params| params.reward_per_block = Some(reward));
+            fund_pool::<T>(pool_amount::<T>());
+            AuthorPayoutAccount::<T>::insert(&author, account::<T::AccountId>("stash", 0, SEED));
+            T::Currency::make_free_balance_be(&T::TreasuryAccount::get(), large_balance::<T>());
+            note_non_empty_block::<T>();
+        }
+        let n = frame_system::Pallet::<T>::block_number();
+
+        #[block]
+        {
+            Pallet::<T>::on_initialize(n);
+            Pallet::<T>::on_finalize(n);
+        }
+
+        assert!(NotedAuthor::<T>::get().is_none());
+        assert_eq!(Pallet::<T>::emission_stats().blocks_rewarded, r as u64);
+    }
+
+    impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
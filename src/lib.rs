@@ -10,17 +10,40 @@
 ///!
 
 pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
 
 #[frame_support::pallet]
 pub mod pallet {
     use frame_support::{
         dispatch::{DispatchError, DispatchResult},
         pallet_prelude::*,
-        traits::{Currency, Get, ReservableCurrency},
+        traits::{
+            Currency, ExistenceRequirement, FindAuthor, Get, Imbalance, IsSubType, LockIdentifier,
+            LockableCurrency, OnUnbalanced, ReservableCurrency, WithdrawReasons,
+        },
+        PalletId,
     };
     use frame_system::pallet_prelude::*;
-    use sp_runtime::traits::Zero;
-    use sp_std::marker::PhantomData;
+
+    use crate::weights::WeightInfo;
+    use sp_core::{sr25519, H256};
+    use sp_io::hashing::blake2_256;
+    use sp_runtime::{
+        traits::{
+            AccountIdConversion, Bounded, CheckedAdd, CheckedShr, CheckedSub, DispatchInfoOf,
+            SaturatedConversion, Saturating, SignedExtension, Zero,
+        },
+        Perbill, Permill,
+    };
+    use sp_std::{marker::PhantomData, vec::Vec};
 
     // ---------------------------------------------
     //  Type aliases & helper definitions
@@ -31,6 +54,338 @@ pub mod pallet {
         <T as frame_system::Config>::AccountId,
     >>::Balance;
 
+    /// Negative imbalance of this pallet's currency, e.g. collected transaction fees.
+    pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+        <T as frame_system::Config>::AccountId,
+    >>::NegativeImbalance;
+
+    /// The runtime-tunable parameters of this pallet.
+    pub type ParamsOf<T> = RewardParams<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+
+    /// A partial update to `ParamsOf`.
+    pub type PartialParamsOf<T> =
+        PartialParams<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+
+    /// The balance lock identifier used for vested claims.
+    pub const VESTING_ID: LockIdentifier = *b"rwdvest ";
+
+    /// Domain tag prefixed to every signed voucher payload, so a `ClaimSigner`
+    /// signature can't be replayed as anything other than a reward voucher.
+    pub const VOUCHER_CONTEXT: &[u8] = b"rwrd/voucher";
+
+    /// An idempotency key supplied by off-chain services that retry claims.
+    pub type ClaimKey = [u8; 32];
+
+    /// Sequential identifier of a claim receipt.
+    pub type ClaimId = u64;
+
+    /// A short label attached to a claim (e.g. `b"milestone 2"`).
+    pub type Memo<T> = BoundedVec<u8, <T as Config>::MaxMemoLength>;
+
+    /// Identifier of a named reward sub-pool (e.g. `b"grants"`).
+    pub type PoolId<T> = BoundedVec<u8, <T as Config>::MaxPoolIdLength>;
+
+    /// The pool id under which the single `RewardPool` is addressed by the sub-pool
+    /// calls. It is served from `RewardPool` and `Allocations` directly, so the
+    /// default program migrates lazily, without a storage migration.
+    pub const DEFAULT_POOL_ID: &[u8] = b"default";
+
+    /// Bookkeeping for a named reward sub-pool.
+    #[derive(Clone, Copy, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct PoolInfo<Balance> {
+        /// The amount currently available in this sub-pool.
+        pub balance: Balance,
+        /// The lifetime amount claimed from this sub-pool.
+        pub total_distributed: Balance,
+    }
+
+    /// A scheduled top-up, released into the pool `per_block` at a time.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct DripInfo<AccountId, Balance> {
+        /// The identifier of this drip.
+        pub id: u32,
+        /// The account that escrowed the funds.
+        pub funder: AccountId,
+        /// The amount still held in escrow.
+        pub remaining: Balance,
+        /// The amount released into the pool per block.
+        pub per_block: Balance,
+    }
+
+    /// A claim waiting in the `PendingClaims` queue.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct PendingClaim<AccountId, Balance> {
+        /// The identifier of this request.
+        pub id: u32,
+        /// The account that requested the claim.
+        pub who: AccountId,
+        /// The amount requested.
+        pub amount: Balance,
+    }
+
+    /// A linearly decaying lock on vested claim payouts.
+    #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct VestingInfo<Balance, BlockNumber> {
+        /// The amount locked as of `starting_block`.
+        pub locked: Balance,
+        /// The amount unlocked per block.
+        pub per_block: Balance,
+        /// The block from which `per_block` starts unlocking.
+        pub starting_block: BlockNumber,
+    }
+
+    /// The budget of a settled era, shared out proportionally to points.
+    #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct EraReward<Balance> {
+        /// The budget set aside for the era.
+        pub budget: Balance,
+        /// The total points noted during the era.
+        pub total_points: u64,
+        /// The part of the budget not yet claimed.
+        pub remaining: Balance,
+        /// The points whose share has been claimed.
+        pub claimed_points: u64,
+    }
+
+    /// An outstanding reward allocation.
+    #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct AllocationInfo<Balance, BlockNumber> {
+        /// The amount still claimable.
+        pub amount: Balance,
+        /// The block from which the allocation can no longer be claimed.
+        pub expires_at: BlockNumber,
+    }
+
+    /// A single entry in an account's claim history.
+    #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct ClaimRecord<Balance, BlockNumber> {
+        /// The amount claimed.
+        pub amount: Balance,
+        /// The block the claim was made in.
+        pub block: BlockNumber,
+    }
+
+    /// An on-chain record of a successful claim, kept for audits.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Receipt<AccountId, Balance, BlockNumber> {
+        /// The account credited with the claim.
+        pub who: AccountId,
+        /// The amount paid out.
+        pub amount: Balance,
+        /// The block the claim was paid in.
+        pub block: BlockNumber,
+    }
+
+    /// How the per-block reward is determined.
+    #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum EmissionMode {
+        /// Pay `RewardPerBlock`, subject to halving.
+        Fixed,
+        /// Pay `PoolEmissionRate` of the current pool, decaying geometrically.
+        Proportional,
+    }
+
+    /// Why no block reward was paid in a block.
+    #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum SkipReason {
+        /// `RewardPerBlock` is zero, so no emission is configured.
+        ZeroReward,
+        /// The pool cannot cover the reward.
+        InsufficientPool,
+        /// The block author could not be found.
+        NoAuthor,
+        /// The proportional reward of a tiny pool rounds down to zero.
+        RoundedToZero,
+        /// The block held only inherents and `RewardEmptyBlocks` is off.
+        EmptyBlock,
+    }
+
+    /// How captured fees are divided. Whatever the parts leave over goes to the
+    /// `TreasuryAccount`.
+    #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct FeeSplit {
+        /// The share credited to the reward pool.
+        pub dev: Permill,
+        /// The share paid to the block author.
+        pub validator: Permill,
+        /// The share burned.
+        pub burn: Permill,
+    }
+
+    /// Everything goes to the reward pool.
+    impl Default for FeeSplit {
+        fn default() -> Self {
+            Self { dev: Permill::one(), validator: Permill::zero(), burn: Permill::zero() }
+        }
+    }
+
+    impl FeeSplit {
+        /// Whether the parts add up to at most 100%.
+        pub fn is_valid(&self) -> bool {
+            let total = (self.dev.deconstruct() as u64)
+                .saturating_add(self.validator.deconstruct() as u64)
+                .saturating_add(self.burn.deconstruct() as u64);
+            total <= Permill::one().deconstruct() as u64
+        }
+    }
+
+    /// The runtime-tunable parameters, stored together so they change atomically.
+    #[derive(Clone, Copy, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct RewardParams<Balance, BlockNumber> {
+        /// The floor that manual claims cannot push the pool below.
+        pub min_pool_reserve: Balance,
+        /// The minimum total balance a claimant must hold.
+        pub eligibility_min_balance: Balance,
+        /// The amount shared out among point earners at each era boundary.
+        pub era_budget: Balance,
+        /// Overrides the `RewardPerBlock` constant when set.
+        pub reward_per_block: Option<Balance>,
+        /// The fee a transaction must have paid for its usage to earn rewards.
+        pub min_consensus_fee: Balance,
+        /// How captured fees are divided.
+        pub fee_split: FeeSplit,
+        /// The minimum number of blocks between two claims by the same account.
+        pub claim_cooldown: BlockNumber,
+        /// The smallest amount a single claim may pay out.
+        pub min_claim_amount: Balance,
+        /// Residues below this are folded back into the reward pool.
+        pub dust_threshold: Balance,
+    }
+
+    /// A partial update to `RewardParams`. `None` fields are left unchanged.
+    #[derive(Clone, Copy, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct PartialParams<Balance, BlockNumber> {
+        pub min_pool_reserve: Option<Balance>,
+        pub eligibility_min_balance: Option<Balance>,
+        pub era_budget: Option<Balance>,
+        pub reward_per_block: Option<Option<Balance>>,
+        pub min_consensus_fee: Option<Balance>,
+        pub fee_split: Option<FeeSplit>,
+        pub claim_cooldown: Option<BlockNumber>,
+        pub min_claim_amount: Option<Balance>,
+        pub dust_threshold: Option<Balance>,
+    }
+
+    impl<Balance: Copy, BlockNumber: Copy> PartialParams<Balance, BlockNumber> {
+        /// Overwrite the fields of `params` that this update sets.
+        pub fn apply_to(&self, params: &mut RewardParams<Balance, BlockNumber>) {
+            if let Some(v) = self.min_pool_reserve {
+                params.min_pool_reserve = v;
+            }
+            if let Some(v) = self.eligibility_min_balance {
+                params.eligibility_min_balance = v;
+            }
+            if let Some(v) = self.era_budget {
+                params.era_budget = v;
+            }
+            if let Some(v) = self.reward_per_block {
+                params.reward_per_block = v;
+            }
+            if let Some(v) = self.min_consensus_fee {
+                params.min_consensus_fee = v;
+            }
+            if let Some(v) = self.fee_split {
+                params.fee_split = v;
+            }
+            if let Some(v) = self.claim_cooldown {
+                params.claim_cooldown = v;
+            }
+            if let Some(v) = self.min_claim_amount {
+                params.min_claim_amount = v;
+            }
+            if let Some(v) = self.dust_threshold {
+                params.dust_threshold = v;
+            }
+        }
+    }
+
+    /// A manual payout above `DirectPayoutLimit`, waiting for `ParamsOrigin` approval.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct PayoutProposal<AccountId, Balance, BlockNumber> {
+        /// The account that proposed the payout.
+        pub proposer: AccountId,
+        /// The account to be paid.
+        pub beneficiary: AccountId,
+        /// The amount to pay from the pool.
+        pub amount: Balance,
+        /// The block from which the proposal can no longer be approved.
+        pub expires_at: BlockNumber,
+    }
+
+    /// A parameter update queued to take effect at `effective_at`.
+    #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct ScheduledParamChange<BlockNumber, Balance> {
+        /// The identifier of this change.
+        pub id: u32,
+        /// The fields to change.
+        pub change: PartialParams<Balance, BlockNumber>,
+        /// The first block at which the change applies.
+        pub effective_at: BlockNumber,
+    }
+
+    /// Block reward activity accumulated over the current era.
+    #[derive(Clone, Copy, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct EraActivityInfo<Balance> {
+        /// The block rewards paid out, to authors and treasury.
+        pub total_paid: Balance,
+        /// The number of blocks whose reward was paid (or tallied).
+        pub blocks_rewarded: u32,
+        /// The number of blocks with a non-zero reward that was not paid.
+        pub blocks_skipped: u32,
+        /// The number of distinct authors seen.
+        pub unique_authors: u32,
+    }
+
+    /// Block reward emission counters, returned by `Pallet::emission_stats`.
+    #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct EmissionStats<BlockNumber> {
+        /// The number of blocks whose reward was paid (or tallied for the era).
+        pub blocks_rewarded: u64,
+        /// The number of blocks with a non-zero reward that was not paid.
+        pub blocks_skipped: u64,
+        /// The last block whose reward was paid, if any.
+        pub last_rewarded_block: Option<BlockNumber>,
+    }
+
+    /// Aggregate pool statistics for dashboards, returned by `Pallet::stats`.
+    #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct RewardStats<Balance, BlockNumber> {
+        /// The current size of the reward pool.
+        pub current_pool: Balance,
+        /// The lifetime amount distributed.
+        pub total_distributed: Balance,
+        /// The lifetime net amount added to the pool.
+        pub total_topped_up: Balance,
+        /// The number of distinct accounts that ever claimed a reward.
+        pub unique_claimants: u32,
+        /// The block of the last claim or block reward, if any.
+        pub last_distribution_block: Option<BlockNumber>,
+    }
+
+    /// How the live pot balance compares to the recorded `RewardPool`.
+    #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum Discrepancy<Balance> {
+        /// The pot holds exactly the recorded pool.
+        None,
+        /// The pot holds more than the recorded pool (e.g. a direct transfer).
+        Surplus(Balance),
+        /// The pot holds less than the recorded pool; the pool is under-backed.
+        Deficit(Balance),
+    }
+
+    /// A reconciliation view of the reward pool, returned by `Pallet::pool_status`.
+    #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct PoolStatus<Balance> {
+        /// The bookkeeping value in `RewardPool`.
+        pub recorded_pool: Balance,
+        /// The free balance actually held by the pot account.
+        pub pot_free_balance: Balance,
+        /// The lifetime amount distributed, from `TotalDistributed`.
+        pub total_distributed: Balance,
+        /// How far the pot balance is from the recorded pool.
+        pub discrepancy: Discrepancy<Balance>,
+    }
+
     // ---------------------------------------------
     //  Pallet Configuration
     // ---------------------------------------------
@@ -43,15 +398,299 @@ pub mod pallet {
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
         /// The currency mechanism (e.g., Balances) used for rewards.
-        type Currency: Currency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
+        type Currency: Currency<Self::AccountId>
+            + ReservableCurrency<Self::AccountId>
+            + LockableCurrency<Self::AccountId, Moment = Self::BlockNumber>;
 
         /// The reward amount automatically distributed per block to the block author.
         /// (Set to `0` if you don't want to use block-based emission.)
         #[pallet::constant]
         type RewardPerBlock: Get<BalanceOf<Self>>;
 
-        /// The origin that is allowed to top-up the reward pool (e.g., governance, root, etc.).
-        type RewardManagerOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Finds the author of the current block from its pre-runtime digests (e.g.
+        /// `pallet_aura::FindAccountFromAuthorIndex` or `pallet_babe`'s equivalent).
+        ///
+        /// Only consulted in `on_finalize` if no author was noted during the block
+        /// through `pallet_authorship`'s `EventHandler`; such runtimes can set `()`.
+        type FindAuthor: FindAuthor<Self::AccountId>;
+
+        /// The share of each block reward paid to the author. The rest goes to
+        /// `TreasuryAccount`. `Permill::one()` pays the author everything.
+        #[pallet::constant]
+        type BlockRewardSplit: Get<Permill>;
+
+        /// The account receiving the treasury share of each block reward.
+        #[pallet::constant]
+        type TreasuryAccount: Get<Self::AccountId>;
+
+        /// The pallet id, used for deriving the pot account that actually holds
+        /// the funds backing `RewardPool`.
+        #[pallet::constant]
+        type PalletId: Get<PalletId>;
+
+        /// The minimum amount accepted by `donate_to_pool`, to prevent dust spam.
+        #[pallet::constant]
+        type MinDonation: Get<BalanceOf<Self>>;
+
+        /// The origin that is allowed to fund and drain the reward pool (e.g. a treasury
+        /// multisig). The on-chain `Admin` account is accepted wherever this origin is.
+        /// It yields the acting account, or `None` for root and collective origins,
+        /// so top-ups can be attributed.
+        type FundingOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Option<Self::AccountId>>;
+
+        /// The origin that is allowed to change parameters, allocations and access
+        /// lists (e.g. a council). The on-chain `Admin` account is also accepted. A
+        /// runtime wanting a single manager can use the same type for both origins.
+        type ParamsOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// The origin that can pause and unpause the whole pallet, e.g. a fast-track
+        /// technical committee.
+        type PauseOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// The maximum amount that may sit in the reward pool. Zero means unlimited.
+        #[pallet::constant]
+        type MaxPoolSize: Get<BalanceOf<Self>>;
+
+        /// Where automatic inflows (e.g. captured fees) go when they would push the
+        /// pool above `MaxPoolSize`. Use `()` to burn the excess.
+        type OverflowHandler: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+        /// The maximum length of a named sub-pool identifier.
+        #[pallet::constant]
+        type MaxPoolIdLength: Get<u32>;
+
+        /// The maximum number of named sub-pools that can be created.
+        #[pallet::constant]
+        type MaxPools: Get<u32>;
+
+        /// The number of blocks in an era. Zero disables all era-based features.
+        #[pallet::constant]
+        type EraLength: Get<Self::BlockNumber>;
+
+        /// How many eras the shares of a settled era stay claimable, after which
+        /// `sweep_expired_era` returns the rest to the pool. Zero means era shares
+        /// never expire.
+        #[pallet::constant]
+        type EraClaimLifetime: Get<u32>;
+
+        /// The amount minted into the pool at the start of each era. Zero disables
+        /// inflationary funding.
+        #[pallet::constant]
+        type PoolInflationPerEra: Get<BalanceOf<Self>>;
+
+        /// The maximum number of drip top-ups that can be pending at once.
+        #[pallet::constant]
+        type MaxPendingDrips: Get<u32>;
+
+        /// The maximum number of parameter changes that can be scheduled at once.
+        #[pallet::constant]
+        type MaxScheduledParamChanges: Get<u32>;
+
+        /// `RewardPoolLow` is emitted when the pool drops below this value. Zero
+        /// disables the warning.
+        #[pallet::constant]
+        type LowPoolThreshold: Get<BalanceOf<Self>>;
+
+        /// Whether accounts without an allocation may claim arbitrary amounts from
+        /// the pool directly. This is effectively a faucet and should be `false` on
+        /// production chains.
+        #[pallet::constant]
+        type AllowUnallocatedClaims: Get<bool>;
+
+        /// The maximum number of entries accepted by `allocate_many`.
+        #[pallet::constant]
+        type MaxAllocationsPerCall: Get<u32>;
+
+        /// A tip paid to whoever triggers `payout_to` for someone else, taken out of
+        /// the beneficiary's allocation. Zero disables the tip.
+        #[pallet::constant]
+        type PayoutCallerReward: Get<BalanceOf<Self>>;
+
+        /// The initial minimum number of blocks between two claims by the same
+        /// account, until changed with `set_claim_cooldown`. Zero disables the cooldown.
+        #[pallet::constant]
+        type ClaimCooldown: Get<Self::BlockNumber>;
+
+        /// The most a single account may claim within one `ClaimPeriodLength`
+        /// window. Zero means unlimited.
+        #[pallet::constant]
+        type ClaimCapPerPeriod: Get<BalanceOf<Self>>;
+
+        /// The length, in blocks, of a claim cap period.
+        #[pallet::constant]
+        type ClaimPeriodLength: Get<Self::BlockNumber>;
+
+        /// The initial smallest amount a single claim may pay out, unless it empties
+        /// the claimant's remaining allocation, until changed with
+        /// `set_min_claim_amount`.
+        #[pallet::constant]
+        type MinClaimAmount: Get<BalanceOf<Self>>;
+
+        /// The maximum number of claims processed in a single block.
+        #[pallet::constant]
+        type MaxClaimsPerBlock: Get<u32>;
+
+        /// The number of claims kept in each account's `ClaimHistory`.
+        #[pallet::constant]
+        type MaxHistoryEntries: Get<u32>;
+
+        /// The maximum number of sibling hashes in a `claim_with_proof` Merkle proof.
+        #[pallet::constant]
+        type MaxProofLen: Get<u32>;
+
+        /// The maximum length of a claim memo.
+        #[pallet::constant]
+        type MaxMemoLength: Get<u32>;
+
+        /// A deposit reserved from the claimant for the duration of `claim_reward`.
+        /// It is slashed into the pool if the claimant fails the eligibility checks,
+        /// and returned otherwise. Zero disables the deposit.
+        #[pallet::constant]
+        type ClaimDeposit: Get<BalanceOf<Self>>;
+
+        /// The maximum number of accounts on the `Claimers` whitelist.
+        #[pallet::constant]
+        type MaxClaimers: Get<u32>;
+
+        /// Whether every account may claim while the `Claimers` whitelist is empty.
+        /// When false, the usual choice, only whitelisted accounts can ever claim,
+        /// so removing the last claimer closes claims instead of opening them.
+        #[pallet::constant]
+        type OpenClaimsWhenEmpty: Get<bool>;
+
+        /// The maximum number of accounts accepted by `add_claimers` and
+        /// `remove_claimers`.
+        #[pallet::constant]
+        type MaxClaimersPerCall: Get<u32>;
+
+        /// The maximum number of entries accepted by `force_payout_many`.
+        #[pallet::constant]
+        type MaxPayoutsPerCall: Get<u32>;
+
+        /// The largest single payout `force_payout_many` may make. Larger payouts
+        /// must go through `propose_payout`. Zero means unlimited.
+        #[pallet::constant]
+        type DirectPayoutLimit: Get<BalanceOf<Self>>;
+
+        /// The maximum number of payout proposals pending at once.
+        #[pallet::constant]
+        type MaxPayoutProposals: Get<u32>;
+
+        /// How long a payout proposal can be approved for.
+        #[pallet::constant]
+        type PayoutProposalExpiry: Get<Self::BlockNumber>;
+
+        /// How many blocks an allocation stays claimable. Each new allocation to an
+        /// account resets its expiry. Zero means allocations never expire.
+        #[pallet::constant]
+        type AllocationLifetime: Get<Self::BlockNumber>;
+
+        /// The maximum number of accounts accepted by `sweep_expired`.
+        #[pallet::constant]
+        type MaxSweepPerCall: Get<u32>;
+
+        /// Allocations below this amount are paid out automatically in `on_idle`, if
+        /// the account could claim them itself. Zero disables automatic payouts.
+        #[pallet::constant]
+        type AutoPayoutThreshold: Get<BalanceOf<Self>>;
+
+        /// How many blocks a processed claim key is remembered. After that the key
+        /// may be pruned and reused.
+        #[pallet::constant]
+        type ClaimKeyRetention: Get<Self::BlockNumber>;
+
+        /// The maximum number of queued claim requests.
+        #[pallet::constant]
+        type MaxPendingClaims: Get<u32>;
+
+        /// The number of queued claim requests processed per block.
+        #[pallet::constant]
+        type ClaimsPerBlock: Get<u32>;
+
+        /// The maximum total value manual claims may pay out in a single block. Block
+        /// author rewards are exempt. Zero disables the limit.
+        #[pallet::constant]
+        type MaxClaimedPerBlock: Get<BalanceOf<Self>>;
+
+        /// Whether to pay out the rest of the pool as a partial block reward once it
+        /// drops below `RewardPerBlock`, instead of skipping the reward.
+        #[pallet::constant]
+        type PayPartialBlockReward: Get<bool>;
+
+        /// Whether block authors accrue their rewards in `AuthorAccrued` and collect
+        /// them with `claim_block_rewards`, instead of being paid every block.
+        #[pallet::constant]
+        type AccrueBlockRewards: Get<bool>;
+
+        /// The block reward halves every `HalvingInterval` blocks. Zero disables
+        /// halving.
+        #[pallet::constant]
+        type HalvingInterval: Get<Self::BlockNumber>;
+
+        /// Whether the block reward is fixed or a fraction of the pool.
+        #[pallet::constant]
+        type EmissionMode: Get<EmissionMode>;
+
+        /// The fraction of the current pool paid per block in
+        /// `EmissionMode::Proportional`.
+        #[pallet::constant]
+        type PoolEmissionRate: Get<Perbill>;
+
+        /// Whether block authors are paid once per era, proportionally to the blocks
+        /// they authored, instead of every block.
+        #[pallet::constant]
+        type PayAuthorsPerEra: Get<bool>;
+
+        /// The maximum number of distinct authors tallied per era. Blocks by further
+        /// authors in the same era are not rewarded.
+        #[pallet::constant]
+        type MaxEraAuthors: Get<u32>;
+
+        /// The maximum number of authors excluded from block rewards.
+        #[pallet::constant]
+        type MaxExcludedAuthors: Get<u32>;
+
+        /// The maximum number of operators.
+        #[pallet::constant]
+        type MaxOperators: Get<u32>;
+
+        /// The maximum number of accounts holding a top-up allowance.
+        #[pallet::constant]
+        type MaxTopUpOperators: Get<u32>;
+
+        /// The most an author can earn in block rewards per era. Further rewards in
+        /// the era stay in the pool. Zero disables the cap.
+        #[pallet::constant]
+        type MaxAuthorRewardPerEra: Get<BalanceOf<Self>>;
+
+        /// Caps the fixed block reward at `pool / SmoothingWindow`, spreading
+        /// top-ups over that many blocks. Zero disables smoothing.
+        #[pallet::constant]
+        type SmoothingWindow: Get<Self::BlockNumber>;
+
+        /// Where era-end dust is sent. `None` returns it to the reward pool.
+        type DustDestination: Get<Option<Self::AccountId>>;
+
+        /// Whether blocks holding only inherents earn a block reward.
+        #[pallet::constant]
+        type RewardEmptyBlocks: Get<bool>;
+
+        /// Whether claims are refused until the pallet is set up, either at genesis
+        /// or with `initialize` on chains that add the pallet later.
+        #[pallet::constant]
+        type RequireInitialization: Get<bool>;
+
+        /// Whether to emit `BlockRewardDistributed` for every block. Chains that only
+        /// want the per-era `EraSummary` can turn this off.
+        #[pallet::constant]
+        type EmitBlockRewardEvents: Get<bool>;
+
+        /// Reports a performance bonus per author (e.g. from an uptime tracking
+        /// pallet), paid on top of the block reward. `()` pays no bonus.
+        type AuthorBonus: AuthorBonus<Self::AccountId>;
+
+        /// Weight information for the extrinsics of this pallet.
+        type WeightInfo: WeightInfo;
 
         /// This is typically your `Balance` type from the runtime (e.g., `u128`).
         type Balance: Parameter + From<u64> + Into<u128> + MaxEncodedLen + Default + Copy;
@@ -61,7 +700,8 @@ pub mod pallet {
     //  Genesis Configuration
     // ---------------------------------------------
 
-    /// Pallet genesis configuration. Allows specifying an initial reward pool at chain genesis.
+    /// Pallet genesis configuration. Allows specifying an initial reward pool at chain genesis,
+    /// which is minted into the pot account.
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         /// Amount of tokens to initialize in the reward pool.
@@ -82,12 +722,36 @@ pub mod pallet {
     }
 
     /// This block builds the genesis storage using the configuration values
-    /// provided in `GenesisConfig`.
+    /// provided in `GenesisConfig`. Inconsistent values panic here, with a
+    /// message naming the problem, rather than corrupting state later.
     #[pallet::genesis_build]
     impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
         fn build(&self) {
+            Pallet::<T>::assert_config_consistent();
+
+            let max_pool = T::MaxPoolSize::get();
+            assert!(
+                max_pool.is_zero() || self.initial_reward_pool <= max_pool,
+                "initial_reward_pool exceeds MaxPoolSize"
+            );
+
+            // Endow the pot so the chain does not start insolvent. Minting is
+            // legitimate here, at genesis.
+            if !self.initial_reward_pool.is_zero() {
+                let _ = T::Currency::deposit_creating(
+                    &Pallet::<T>::account_id(),
+                    self.initial_reward_pool,
+                );
+            }
+            assert!(
+                Pallet::<T>::pot() >= self.initial_reward_pool,
+                "reward pot could not be endowed with initial_reward_pool"
+            );
+
             RewardPool::<T>::put(self.initial_reward_pool);
             TotalDistributed::<T>::put(Zero::zero());
+            TotalToppedUp::<T>::put(self.initial_reward_pool);
+            Initialized::<T>::put(true);
         }
     }
 
@@ -104,7 +768,8 @@ pub mod pallet {
     // ---------------------------------------------
 
     /// The current size of the reward pool. This pool is the source of all
-    /// rewards in this pallet (per-block or manual claim).
+    /// rewards in this pallet (per-block or manual claim). The funds themselves
+    /// live in the pot account (see `Pallet::account_id`).
     #[pallet::storage]
     #[pallet::getter(fn reward_pool)]
     pub type RewardPool<T> = StorageValue<_, BalanceOf<T>, ValueQuery>;
@@ -115,152 +780,5092 @@ pub mod pallet {
     #[pallet::getter(fn total_distributed)]
     pub type TotalDistributed<T> = StorageValue<_, BalanceOf<T>, ValueQuery>;
 
-    // ---------------------------------------------
-    //  Events
-    // ---------------------------------------------
-
-    #[pallet::event]
-    #[pallet::generate_deposit(pub(super) fn deposit_event)]
-    pub enum Event<T: Config> {
-        /// Reward pool was increased. (amount_added, new_pool_total)
-        RewardPoolIncreased(BalanceOf<T>, BalanceOf<T>),
-        /// A reward was claimed by an account. (who, amount)
-        RewardClaimed(T::AccountId, BalanceOf<T>),
-        /// A block reward was distributed. (block_author, amount)
-        BlockRewardDistributed(T::AccountId, BalanceOf<T>),
-    }
-
-    // ---------------------------------------------
-    //  Errors
-    // ---------------------------------------------
-
-    #[pallet::error]
-    pub enum Error<T> {
-        /// Attempting to distribute or claim more than is available in the pool.
-        InsufficientRewardPool,
-        /// Attempting to claim zero (invalid) or negative (impossible) amount.
-        InvalidClaimAmount,
-        /// The origin did not match the required origin for this call.
-        BadOriginForTopUp,
-    }
+    /// Tracks the net amount ever added to the reward pool (top-ups, donations,
+    /// captured fees) minus non-reward outflows such as withdrawals. At all times
+    /// `TotalToppedUp == RewardPool + TotalAllocated + TotalDistributed` should hold.
+    #[pallet::storage]
+    #[pallet::getter(fn total_topped_up)]
+    pub type TotalToppedUp<T> = StorageValue<_, BalanceOf<T>, ValueQuery>;
 
-    // ---------------------------------------------
-    //  Hooks: Automatic Block Reward Logic
-    // ---------------------------------------------
+    /// Named reward sub-pools, isolated from each other and from the default
+    /// `RewardPool`. Each sub-pool is backed by its own sub-account of the pot
+    /// (see `Pallet::sub_pool_account`).
+    #[pallet::storage]
+    #[pallet::getter(fn pools)]
+    pub type Pools<T: Config> = StorageMap<_, Blake2_128Concat, PoolId<T>, PoolInfo<BalanceOf<T>>>;
 
-    /// We use the `on_initialize` hook to distribute a per-block reward
-    /// to the block author, if configured (RewardPerBlock > 0).
-    #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-        fn on_initialize(_n: T::BlockNumber) -> Weight {
-            let reward_per_block = T::RewardPerBlock::get();
-            // If the reward is set to zero, do nothing.
-            if reward_per_block.is_zero() {
-                return 0;
-            }
+    /// The amount each account has been allocated from a named sub-pool, already
+    /// deducted from the sub-pool's balance.
+    #[pallet::storage]
+    #[pallet::getter(fn pool_allocations)]
+    pub type PoolAllocations<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        PoolId<T>,
+        Blake2_128Concat,
+        T::AccountId,
+        BalanceOf<T>,
+        ValueQuery,
+    >;
 
-            let pool = Self::reward_pool();
+    /// The number of entries in `Pools`, bounded by `MaxPools`.
+    #[pallet::storage]
+    #[pallet::getter(fn pool_count)]
+    pub type PoolCount<T> = StorageValue<_, u32, ValueQuery>;
 
-            // If there's not enough in the pool, we skip distributing a block reward.
-            // (Alternatively, you could distribute what's left or handle in other ways.)
-            if pool < reward_per_block {
-                return 0;
-            }
+    /// The current era index, advanced every `EraLength` blocks.
+    #[pallet::storage]
+    #[pallet::getter(fn current_era)]
+    pub type CurrentEra<T> = StorageValue<_, u32, ValueQuery>;
 
-            // Get the block author. This depends on your consensus mechanism.
-            // In many Substrate setups (e.g., AURA/BABE), `pallet_authorship`
-            // or `frame_system` can store the block author.
-            //
-            // We demonstrate a simplified approach: see if the system pallet
-            // provides a block_author function. If it's Some(author), we proceed.
-            if let Some(block_author) = frame_system::Pallet::<T>::block_author() {
-                // Deduct from the reward pool
-                let new_pool = pool - reward_per_block;
-                RewardPool::<T>::put(new_pool);
+    /// The block at which the next era starts. Zero until the first rollover, which
+    /// then happens on the first multiple of `EraLength`. A boundary that falls in a
+    /// pause rolls over in the first block after it.
+    #[pallet::storage]
+    #[pallet::getter(fn next_era_start)]
+    pub type NextEraStart<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
 
-                // Update total distributed
-                let total_dist = Self::total_distributed();
-                let updated_dist = total_dist + reward_per_block;
-                TotalDistributed::<T>::put(updated_dist);
+    /// Scheduled drip top-ups. The head is released each block and rotated to the
+    /// back until exhausted.
+    #[pallet::storage]
+    #[pallet::getter(fn pending_drips)]
+    pub type PendingDrips<T: Config> = StorageValue<
+        _,
+        BoundedVec<DripInfo<T::AccountId, BalanceOf<T>>, T::MaxPendingDrips>,
+        ValueQuery,
+    >;
 
-                // Transfer reward to block author
-                T::Currency::deposit_creating(&block_author, reward_per_block);
+    /// The identifier assigned to the next scheduled drip.
+    #[pallet::storage]
+    pub type NextDripId<T> = StorageValue<_, u32, ValueQuery>;
 
-                // Emit event
-                Self::deposit_event(Event::BlockRewardDistributed(block_author, reward_per_block));
-            }
+    /// The runtime-tunable parameters. See `RewardParams`.
+    #[pallet::storage]
+    #[pallet::getter(fn params)]
+    pub type Params<T: Config> = StorageValue<_, ParamsOf<T>, ValueQuery, DefaultParams<T>>;
 
-            // Return some weight cost estimate. The actual weight formula should
-            // account for read/write operations. This is a simplified example.
-            10_000
+    /// Parameters start from the compile-time defaults.
+    #[pallet::type_value]
+    pub fn DefaultParams<T: Config>() -> ParamsOf<T> {
+        RewardParams {
+            claim_cooldown: T::ClaimCooldown::get(),
+            min_claim_amount: T::MinClaimAmount::get(),
+            ..Default::default()
         }
     }
 
-    // ---------------------------------------------
-    //  Extrinsics
-    // ---------------------------------------------
+    /// Accounts that have claimed at least once, used to count unique claimants.
+    #[pallet::storage]
+    pub type Claimants<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
 
-    /// The callable functions (extrinsics) of this pallet.
-    #[pallet::call]
-    impl<T: Config> Pallet<T> {
-        /// Top up the reward pool by `amount`. Must come from `RewardManagerOrigin`.
-        ///
-        /// # Arguments
-        /// * `origin` - Must satisfy the `RewardManagerOrigin` (e.g., Root, Council, etc.).
-        /// * `amount` - The amount to add to the reward pool.
-        #[pallet::weight(10_000)]
-        pub fn top_up_pool(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
-            // Check that the origin is authorized
-            T::RewardManagerOrigin::try_origin(origin)
-                .map_err(|_| Error::<T>::BadOriginForTopUp)?;
+    /// The number of distinct accounts that ever claimed a reward.
+    #[pallet::storage]
+    #[pallet::getter(fn claimant_count)]
+    pub type ClaimantCount<T> = StorageValue<_, u32, ValueQuery>;
 
-            let pool_before = Self::reward_pool();
-            let new_pool = pool_before
-                .checked_add(&amount)
-                .ok_or(ArithmeticError::Overflow)?;
+    /// The block of the last claim or block reward.
+    #[pallet::storage]
+    #[pallet::getter(fn last_distribution_block)]
+    pub type LastDistributionBlock<T: Config> = StorageValue<_, T::BlockNumber, OptionQuery>;
 
-            // Update the storage
-            RewardPool::<T>::put(new_pool);
+    /// Latched while the pool is below `LowPoolThreshold`, so `RewardPoolLow` is only
+    /// emitted once per crossing.
+    #[pallet::storage]
+    #[pallet::getter(fn pool_low)]
+    pub type PoolLow<T> = StorageValue<_, bool, ValueQuery>;
 
-            // Emit event
-            Self::deposit_event(Event::RewardPoolIncreased(amount, new_pool));
+    /// Rewards allocated to each account and not yet claimed. These are already
+    /// reserved out of `RewardPool`.
+    #[pallet::storage]
+    #[pallet::getter(fn allocation_info)]
+    pub type Allocations<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        AllocationInfo<BalanceOf<T>, T::BlockNumber>,
+        OptionQuery,
+    >;
 
-            Ok(())
+    /// The sum of all outstanding `Allocations`.
+    #[pallet::storage]
+    #[pallet::getter(fn total_allocated)]
+    pub type TotalAllocated<T> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+    /// Where each account's rewards are paid, if not to the account itself.
+    #[pallet::storage]
+    #[pallet::getter(fn payout_destination)]
+    pub type PayoutDestination<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId, OptionQuery>;
+
+    /// The block of each account's last successful claim.
+    #[pallet::storage]
+    #[pallet::getter(fn last_claim_block)]
+    pub type LastClaimBlock<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, T::BlockNumber, OptionQuery>;
+
+    /// The `(period_index, claimed_so_far)` of each account for the claim cap.
+    #[pallet::storage]
+    #[pallet::getter(fn period_claims)]
+    pub type PeriodClaims<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        (T::BlockNumber, BalanceOf<T>),
+        OptionQuery,
+    >;
+
+    /// The number of claims processed in the current block. Reset in `on_initialize`.
+    #[pallet::storage]
+    #[pallet::getter(fn claims_this_block)]
+    pub type ClaimsThisBlock<T> = StorageValue<_, u32, ValueQuery>;
+
+    /// The most recent claims of each account, oldest first. The oldest entry is
+    /// evicted once `MaxHistoryEntries` is reached.
+    #[pallet::storage]
+    #[pallet::getter(fn claim_history)]
+    pub type ClaimHistory<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<ClaimRecord<BalanceOf<T>, T::BlockNumber>, T::MaxHistoryEntries>,
+        ValueQuery,
+    >;
+
+    /// The lifetime amount each account has received from this pallet, through
+    /// claims and block-author rewards.
+    #[pallet::storage]
+    #[pallet::getter(fn total_claimed_by)]
+    pub type TotalClaimedBy<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+    /// The Merkle root of the current airdrop-style distribution and what is left of
+    /// the total set aside for it. Leaves are `blake2_256((account, amount).encode())`.
+    #[pallet::storage]
+    #[pallet::getter(fn claim_root)]
+    pub type ClaimRoot<T: Config> = StorageValue<_, (H256, BalanceOf<T>), OptionQuery>;
+
+    /// Leaves already claimed under each Merkle root.
+    #[pallet::storage]
+    pub type ClaimedLeaves<T> =
+        StorageDoubleMap<_, Identity, H256, Identity, H256, (), OptionQuery>;
+
+    /// The sr25519 key whose signed vouchers can be redeemed via `claim_with_voucher`.
+    #[pallet::storage]
+    #[pallet::getter(fn claim_signer)]
+    pub type ClaimSigner<T> = StorageValue<_, sr25519::Public, OptionQuery>;
+
+    /// Voucher nonces that have already been redeemed.
+    #[pallet::storage]
+    pub type UsedNonces<T> = StorageMap<_, Twox64Concat, u64, (), OptionQuery>;
+
+    /// Whitelist of accounts eligible to claim. While empty, nobody may claim
+    /// unless `OpenClaimsWhenEmpty` is set.
+    #[pallet::storage]
+    pub type Claimers<T: Config> =
+        CountedStorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+    /// Accounts banned from claiming.
+    #[pallet::storage]
+    pub type BannedClaimers<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+    /// The raw `Allocations` key the `on_idle` auto-payout sweep resumes after.
+    /// `None` means the next sweep starts from the beginning.
+    #[pallet::storage]
+    pub type AutoPayoutCursor<T> = StorageValue<_, BoundedVec<u8, ConstU32<128>>, OptionQuery>;
+
+    /// Claim keys already processed per account, with the block they were used in.
+    #[pallet::storage]
+    pub type ProcessedClaimKeys<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Identity,
+        ClaimKey,
+        T::BlockNumber,
+        OptionQuery,
+    >;
+
+    /// The account allowed to trigger claims on behalf of each principal.
+    #[pallet::storage]
+    #[pallet::getter(fn claim_delegate)]
+    pub type ClaimDelegate<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId, OptionQuery>;
+
+    /// Claim requests waiting to be processed in `on_initialize`, oldest first.
+    #[pallet::storage]
+    #[pallet::getter(fn pending_claims)]
+    pub type PendingClaims<T: Config> = StorageValue<
+        _,
+        BoundedVec<PendingClaim<T::AccountId, BalanceOf<T>>, T::MaxPendingClaims>,
+        ValueQuery,
+    >;
+
+    /// The identifier assigned to the next claim request.
+    #[pallet::storage]
+    pub type NextClaimRequestId<T> = StorageValue<_, u32, ValueQuery>;
+
+    /// The vesting lock on each account that made vested claims.
+    #[pallet::storage]
+    #[pallet::getter(fn vesting)]
+    pub type Vesting<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        VestingInfo<BalanceOf<T>, T::BlockNumber>,
+        OptionQuery,
+    >;
+
+    /// Points earned by each account, per era.
+    #[pallet::storage]
+    #[pallet::getter(fn era_points)]
+    pub type EraPoints<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, u32, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+    /// The total points earned in each era.
+    #[pallet::storage]
+    #[pallet::getter(fn era_total_points)]
+    pub type EraTotalPoints<T> = StorageMap<_, Twox64Concat, u32, u64, ValueQuery>;
+
+    /// The budget of each settled era that had points.
+    #[pallet::storage]
+    #[pallet::getter(fn era_reward)]
+    pub type EraRewards<T: Config> =
+        StorageMap<_, Twox64Concat, u32, EraReward<BalanceOf<T>>, OptionQuery>;
+
+    /// Rounding remainders of fully claimed eras, added to the next era's budget.
+    /// Counted in `TotalAllocated`.
+    #[pallet::storage]
+    #[pallet::getter(fn carried_dust)]
+    pub type CarriedDust<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+    /// The total value claimed manually in the current block. Reset in `on_initialize`.
+    #[pallet::storage]
+    #[pallet::getter(fn claimed_this_block)]
+    pub type ClaimedThisBlock<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+    /// Receipts of successful claims, by claim id.
+    #[pallet::storage]
+    #[pallet::getter(fn receipt)]
+    pub type Receipts<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        ClaimId,
+        Receipt<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+        OptionQuery,
+    >;
+
+    /// The id assigned to the next claim receipt.
+    #[pallet::storage]
+    #[pallet::getter(fn next_claim_id)]
+    pub type NextClaimId<T> = StorageValue<_, ClaimId, ValueQuery>;
+
+    /// The lowest claim id whose receipt has not been pruned.
+    #[pallet::storage]
+    #[pallet::getter(fn first_receipt_id)]
+    pub type FirstReceiptId<T> = StorageValue<_, ClaimId, ValueQuery>;
+
+    /// Accounts that allow anyone to trigger `claim_for` on their behalf, e.g.
+    /// contract reward addresses without keys.
+    #[pallet::storage]
+    #[pallet::getter(fn allows_third_party_claims)]
+    pub type ThirdPartyClaims<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, bool, ValueQuery>;
+
+    /// Block rewards accrued by each author and not yet claimed. Counted in
+    /// `TotalAllocated`.
+    #[pallet::storage]
+    #[pallet::getter(fn author_accrued)]
+    pub type AuthorAccrued<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+    /// The author noted for the current block, paid and cleared in `on_finalize`.
+    #[pallet::storage]
+    #[pallet::getter(fn noted_author)]
+    pub type NotedAuthor<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
+
+    /// The number of blocks each author produced in the current era, when
+    /// `PayAuthorsPerEra` is set. Drained at the era boundary.
+    #[pallet::storage]
+    #[pallet::getter(fn blocks_authored_in_era)]
+    pub type BlocksAuthoredInEra<T: Config> =
+        CountedStorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+    /// Authors whose block rewards are withheld, e.g. misbehaving collators.
+    #[pallet::storage]
+    pub type ExcludedAuthors<T: Config> =
+        CountedStorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+    /// Block rewards earned by each author, per era. Cleared at era rollover.
+    #[pallet::storage]
+    #[pallet::getter(fn author_era_rewards)]
+    pub type AuthorEraRewards<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        u32,
+        Blake2_128Concat,
+        T::AccountId,
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+
+    /// Whether block reward emission is halted, e.g. during incident response.
+    #[pallet::storage]
+    #[pallet::getter(fn block_rewards_paused)]
+    pub type BlockRewardsPaused<T> = StorageValue<_, bool, ValueQuery>;
+
+    /// The number of blocks whose reward was paid (or tallied for the era).
+    #[pallet::storage]
+    pub type BlocksRewarded<T> = StorageValue<_, u64, ValueQuery>;
+
+    /// The number of blocks with a non-zero reward configured that paid nothing.
+    #[pallet::storage]
+    pub type BlocksSkipped<T> = StorageValue<_, u64, ValueQuery>;
+
+    /// The last block whose reward was paid (or tallied for the era).
+    #[pallet::storage]
+    pub type LastRewardedBlock<T: Config> = StorageValue<_, T::BlockNumber, OptionQuery>;
+
+    /// Where each author's block rewards are sent, e.g. a stash kept off the
+    /// collator's hot machine.
+    #[pallet::storage]
+    #[pallet::getter(fn author_payout_destination)]
+    pub type AuthorPayoutAccount<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId, OptionQuery>;
+
+    /// Block reward activity of the current era, summarised and reset at the era
+    /// boundary.
+    #[pallet::storage]
+    #[pallet::getter(fn era_activity)]
+    pub type EraActivity<T: Config> = StorageValue<_, EraActivityInfo<BalanceOf<T>>, ValueQuery>;
+
+    /// The last era each author was seen in, for counting unique authors.
+    #[pallet::storage]
+    pub type AuthorLastSeenEra<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u32, OptionQuery>;
+
+    /// An on-chain account with the rights of both `FundingOrigin` and `ParamsOrigin`, rotatable
+    /// without a runtime upgrade.
+    #[pallet::storage]
+    #[pallet::getter(fn admin)]
+    pub type Admin<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
+
+    /// The account proposed as the next admin, until it accepts.
+    #[pallet::storage]
+    #[pallet::getter(fn pending_admin)]
+    pub type PendingAdmin<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
+
+    /// Whether the whole pallet is frozen. See `pause`.
+    #[pallet::storage]
+    #[pallet::getter(fn paused)]
+    pub type Paused<T> = StorageValue<_, bool, ValueQuery>;
+
+    /// Parameter changes waiting for their block, in scheduling order.
+    #[pallet::storage]
+    #[pallet::getter(fn scheduled_param_changes)]
+    pub type ScheduledParamChanges<T: Config> = StorageValue<
+        _,
+        BoundedVec<
+            ScheduledParamChange<T::BlockNumber, BalanceOf<T>>,
+            T::MaxScheduledParamChanges,
+        >,
+        ValueQuery,
+    >;
+
+    /// The identifier assigned to the next scheduled parameter change.
+    #[pallet::storage]
+    pub type NextParamChangeId<T> = StorageValue<_, u32, ValueQuery>;
+
+    /// The amount each operator may add to the pool per era via `top_up_pool`,
+    /// without satisfying `FundingOrigin`.
+    #[pallet::storage]
+    #[pallet::getter(fn top_up_allowance)]
+    pub type TopUpAllowance<T: Config> =
+        CountedStorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, OptionQuery>;
+
+    /// The allowance each operator has used, per era. Cleared at era rollover.
+    #[pallet::storage]
+    #[pallet::getter(fn top_up_spent)]
+    pub type TopUpSpent<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        u32,
+        Blake2_128Concat,
+        T::AccountId,
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+
+    /// Pending payout proposals by id.
+    #[pallet::storage]
+    #[pallet::getter(fn payout_proposals)]
+    pub type PayoutProposals<T: Config> = CountedStorageMap<
+        _,
+        Twox64Concat,
+        u32,
+        PayoutProposal<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+        OptionQuery,
+    >;
+
+    /// The identifier assigned to the next payout proposal.
+    #[pallet::storage]
+    pub type NextPayoutProposalId<T> = StorageValue<_, u32, ValueQuery>;
+
+    /// Accounts that may allocate rewards and trigger payouts, but not fund the pool
+    /// or change parameters.
+    #[pallet::storage]
+    pub type Operators<T: Config> =
+        CountedStorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+    /// Whether the pallet was set up, at genesis or by `initialize`.
+    #[pallet::storage]
+    #[pallet::getter(fn initialized)]
+    pub type Initialized<T> = StorageValue<_, bool, ValueQuery>;
+
+    /// Whether the parameters are frozen for good. See `freeze_params`.
+    #[pallet::storage]
+    #[pallet::getter(fn params_frozen)]
+    pub type ParamsFrozen<T> = StorageValue<_, bool, ValueQuery>;
+
+    /// Dust folded back into the pool during the current block, reported once in
+    /// `on_finalize`.
+    #[pallet::storage]
+    pub type DustCollectedThisBlock<T> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+    // ---------------------------------------------
+    //  Events
+    // ---------------------------------------------
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Reward pool was increased. (funder, amount_added, new_pool_total)
+        ///
+        /// `funder` is `None` when the funds were minted, or came from the treasury
+        /// on a top-up by root or a collective.
+        RewardPoolIncreased(Option<T::AccountId>, BalanceOf<T>, BalanceOf<T>),
+        /// A reward was claimed by an account. The memo, if any, is only emitted and
+        /// never stored.
+        RewardClaimed {
+            claim_id: ClaimId,
+            who: T::AccountId,
+            amount: BalanceOf<T>,
+            lifetime_claimed: BalanceOf<T>,
+            remaining_allocation: BalanceOf<T>,
+            memo: Option<Memo<T>>,
+        },
+        /// A block reward was distributed. (block_author, destination, amount,
+        /// halving_epoch, bonus)
+        BlockRewardDistributed(T::AccountId, T::AccountId, BalanceOf<T>, u32, Permill),
+        /// An account donated to the reward pool.
+        PoolDonated { who: T::AccountId, amount: BalanceOf<T>, new_total: BalanceOf<T> },
+        /// Captured fees were divided according to the `FeeSplit`.
+        FeesCaptured {
+            dev: BalanceOf<T>,
+            validator: BalanceOf<T>,
+            burned: BalanceOf<T>,
+            treasury: BalanceOf<T>,
+        },
+        /// The reward pool value was overwritten by governance.
+        RewardPoolForceSet { old: BalanceOf<T>, new: BalanceOf<T> },
+        /// Funds were withdrawn from the reward pool to a beneficiary.
+        RewardPoolDecreased {
+            amount: BalanceOf<T>,
+            beneficiary: T::AccountId,
+            remaining: BalanceOf<T>,
+        },
+        /// Part of an automatic inflow did not fit under `MaxPoolSize` and was handed
+        /// to the `OverflowHandler`. (amount)
+        PoolOverflow(BalanceOf<T>),
+        /// Pool funds were burned. (amount, remaining)
+        PoolBurned(BalanceOf<T>, BalanceOf<T>),
+        /// A named sub-pool was created. (pool_id)
+        SubPoolCreated(PoolId<T>),
+        /// A named sub-pool was topped up. (pool_id, funder, amount_added, new_pool_total)
+        SubPoolIncreased(PoolId<T>, T::AccountId, BalanceOf<T>, BalanceOf<T>),
+        /// A reward was claimed from a named sub-pool. (pool_id, who, amount)
+        SubPoolRewardClaimed(PoolId<T>, T::AccountId, BalanceOf<T>),
+        /// A reward was allocated from a named sub-pool. (pool_id, who, amount)
+        SubPoolRewardAllocated(PoolId<T>, T::AccountId, BalanceOf<T>),
+        /// The pool was credited with the per-era inflation. (era, amount)
+        PoolInflated(u32, BalanceOf<T>),
+        /// A drip top-up was scheduled.
+        DripScheduled { id: u32, total: BalanceOf<T>, per_block: BalanceOf<T> },
+        /// Part of a drip top-up was released into the pool.
+        DripReleased { id: u32, amount: BalanceOf<T>, remaining: BalanceOf<T> },
+        /// A drip top-up was cancelled and its remainder refunded.
+        DripCancelled { id: u32, refunded: BalanceOf<T>, refund_to: T::AccountId },
+        /// The minimum pool reserve was updated.
+        MinPoolReserveUpdated {
+            old: BalanceOf<T>,
+            new: BalanceOf<T>,
+            changed_by: Option<T::AccountId>,
+        },
+        /// The pool dropped below `LowPoolThreshold`.
+        RewardPoolLow { remaining: BalanceOf<T> },
+        /// The pool was topped back up to at least `LowPoolThreshold`.
+        RewardPoolRecovered { pool: BalanceOf<T> },
+        /// A reward was allocated to an account. (who, amount)
+        RewardAllocated(T::AccountId, BalanceOf<T>),
+        /// A batch of rewards was allocated.
+        RewardsAllocated { count: u32, total: BalanceOf<T> },
+        /// An allocation was revoked or reduced and returned to the pool.
+        AllocationRevoked { who: T::AccountId, amount: BalanceOf<T>, remaining: BalanceOf<T> },
+        /// A caller was tipped for triggering a payout on behalf of a beneficiary.
+        PayoutTipPaid { caller: T::AccountId, beneficiary: T::AccountId, amount: BalanceOf<T> },
+        /// An account changed where its rewards are paid. `None` pays the account.
+        PayoutDestinationUpdated {
+            who: T::AccountId,
+            old: Option<T::AccountId>,
+            new: Option<T::AccountId>,
+            changed_by: Option<T::AccountId>,
+        },
+        /// An account's claim history was pruned. (who)
+        ClaimHistoryPruned(T::AccountId),
+        /// A new Merkle claim root was set.
+        ClaimRootSet { root: H256, total: BalanceOf<T> },
+        /// The voucher signing key was changed.
+        ClaimSignerUpdated {
+            old: Option<sr25519::Public>,
+            new: Option<sr25519::Public>,
+            changed_by: Option<T::AccountId>,
+        },
+        /// A claim failed the eligibility checks after the claim deposit was
+        /// reserved, so the deposit was slashed into the pool.
+        ClaimDepositSlashed { who: T::AccountId, amount: BalanceOf<T>, error: DispatchError },
+        /// An account was added to the claimer whitelist. (who)
+        ClaimerAdded(T::AccountId),
+        /// An account was removed from the claimer whitelist. (who)
+        ClaimerRemoved(T::AccountId),
+        /// An account was banned from claiming. `slashed` is the allocation that was
+        /// returned to the pool.
+        ClaimerBanned { who: T::AccountId, slashed: BalanceOf<T> },
+        /// An account was unbanned. (who)
+        ClaimerUnbanned(T::AccountId),
+        /// The minimum balance required to claim was updated.
+        EligibilityMinBalanceUpdated {
+            old: BalanceOf<T>,
+            new: BalanceOf<T>,
+            changed_by: Option<T::AccountId>,
+        },
+        /// A manager-driven batch payout completed.
+        PayoutsCompleted { count: u32, total: BalanceOf<T> },
+        /// An expired allocation was returned to the pool.
+        AllocationExpiredSwept { who: T::AccountId, amount: BalanceOf<T> },
+        /// A principal changed its claim delegate. `None` means no delegate.
+        ClaimDelegateUpdated {
+            principal: T::AccountId,
+            old: Option<T::AccountId>,
+            new: Option<T::AccountId>,
+            changed_by: Option<T::AccountId>,
+        },
+        /// A delegate claimed on behalf of a principal.
+        DelegatedClaim { delegate: T::AccountId, principal: T::AccountId, amount: BalanceOf<T> },
+        /// A claim request was queued.
+        ClaimRequested { id: u32, who: T::AccountId, amount: BalanceOf<T> },
+        /// A queued claim request was rejected when processed.
+        ClaimRequestRejected { id: u32, who: T::AccountId, error: DispatchError },
+        /// A queued claim request was cancelled by its requester.
+        ClaimRequestCancelled { id: u32, who: T::AccountId },
+        /// A claim was paid out under a vesting lock.
+        VestedClaim { who: T::AccountId, amount: BalanceOf<T>, per_block: BalanceOf<T> },
+        /// An account's vesting lock was updated; `locked` is zero once fully vested.
+        VestingUpdated { who: T::AccountId, locked: BalanceOf<T> },
+        /// The per-era reward budget was updated.
+        EraBudgetUpdated {
+            old: BalanceOf<T>,
+            new: BalanceOf<T>,
+            changed_by: Option<T::AccountId>,
+        },
+        /// An era was settled: `budget` is shared among `total_points`. `dust` is the
+        /// carried rounding remainder included in `budget`.
+        EraSettled { era: u32, budget: BalanceOf<T>, total_points: u64, dust: BalanceOf<T> },
+        /// Every share of an era was claimed; the rounding remainder was carried over.
+        EraDustCarried { era: u32, dust: BalanceOf<T> },
+        /// An account claimed its share of an era's budget.
+        EraShareClaimed { era: u32, who: T::AccountId, points: u32, amount: BalanceOf<T> },
+        /// The unclaimed remainder of an expired era was returned to the pool.
+        EraSwept { era: u32, amount: BalanceOf<T> },
+        /// Receipts below `first_remaining` were pruned.
+        ReceiptsPruned { first_remaining: ClaimId },
+        /// An account opted in to or out of third-party triggered claims.
+        ThirdPartyClaimsSet { who: T::AccountId, allowed: bool },
+        /// A third party triggered the payout of a beneficiary's allocation.
+        ClaimedFor { caller: T::AccountId, beneficiary: T::AccountId, amount: BalanceOf<T> },
+        /// No block reward was paid in this block.
+        BlockRewardSkipped { reason: SkipReason },
+        /// The treasury share of a block reward was paid. (treasury, amount)
+        TreasuryRewardDistributed(T::AccountId, BalanceOf<T>),
+        /// The reward pool was drained to zero by a block reward.
+        RewardPoolDepleted,
+        /// A block reward was accrued for later claiming. (block_author, amount)
+        BlockRewardAccrued(T::AccountId, BalanceOf<T>),
+        /// An author claimed its accrued block rewards. (who, amount)
+        BlockRewardsClaimed(T::AccountId, BalanceOf<T>),
+        /// The author rewards of an era were paid out proportionally to blocks
+        /// authored.
+        EraRewardsDistributed { era: u32, total: BalanceOf<T>, authors: u32, blocks: u32 },
+        /// An author was excluded from block rewards.
+        AuthorExcluded(T::AccountId),
+        /// An author was included in block rewards again.
+        AuthorIncluded(T::AccountId),
+        /// A block reward was withheld from an excluded author and left in the pool.
+        BlockRewardWithheld { author: T::AccountId },
+        /// An author reached `MaxAuthorRewardPerEra` for the current era.
+        AuthorRewardCapReached { author: T::AccountId, era: u32 },
+        /// The per-block reward override changed. `None` means the `RewardPerBlock`
+        /// constant applies.
+        RewardPerBlockUpdated {
+            old: Option<BalanceOf<T>>,
+            new: Option<BalanceOf<T>>,
+            changed_by: Option<T::AccountId>,
+        },
+        /// Block reward emission was paused.
+        BlockRewardsPaused,
+        /// Block reward emission was resumed.
+        BlockRewardsResumed,
+        /// An author changed where its block rewards are sent. `None` falls back to
+        /// the author's payout destination.
+        AuthorPayoutAccountUpdated {
+            author: T::AccountId,
+            old: Option<T::AccountId>,
+            new: Option<T::AccountId>,
+            changed_by: Option<T::AccountId>,
+        },
+        /// Stranded remainders were flushed at the end of an era. (amount)
+        DustFlushed(BalanceOf<T>),
+        /// Summary of the block reward activity of an era.
+        EraSummary {
+            era: u32,
+            total_paid: BalanceOf<T>,
+            blocks_rewarded: u32,
+            blocks_skipped: u32,
+            unique_authors: u32,
+        },
+        /// A new admin was proposed and must call `accept_admin`. `old` is the
+        /// proposal it replaces, if any.
+        PendingAdminUpdated {
+            old: Option<T::AccountId>,
+            new: T::AccountId,
+            changed_by: Option<T::AccountId>,
+        },
+        /// The admin changed.
+        AdminUpdated {
+            old: Option<T::AccountId>,
+            new: T::AccountId,
+            changed_by: Option<T::AccountId>,
+        },
+        /// The parameters were updated.
+        ParamsUpdated {
+            old: ParamsOf<T>,
+            new: ParamsOf<T>,
+            changed_by: Option<T::AccountId>,
+        },
+        /// The pallet was paused.
+        Paused,
+        /// The pallet was unpaused.
+        Unpaused,
+        /// A parameter change was scheduled.
+        ParamChangeScheduled { id: u32, effective_at: T::BlockNumber },
+        /// A scheduled parameter change took effect.
+        ParamChangeApplied { id: u32, params: ParamsOf<T> },
+        /// A scheduled parameter change was dropped because the result was invalid.
+        ParamChangeRejected { id: u32 },
+        /// A scheduled parameter change was cancelled.
+        ParamChangeCancelled { id: u32 },
+        /// An operator's per-era top-up allowance was set. Zero revokes it.
+        TopUpAllowanceUpdated {
+            who: T::AccountId,
+            old: BalanceOf<T>,
+            new: BalanceOf<T>,
+            changed_by: Option<T::AccountId>,
+        },
+        /// Top-up allowances were restored for the new era.
+        TopUpAllowancesReset { era: u32 },
+        /// The minimum consensus fee was updated.
+        MinConsensusFeeUpdated {
+            old: BalanceOf<T>,
+            new: BalanceOf<T>,
+            changed_by: Option<T::AccountId>,
+        },
+        /// The fee split was updated.
+        FeeSplitUpdated { old: FeeSplit, new: FeeSplit, changed_by: Option<T::AccountId> },
+        /// A payout was proposed.
+        PayoutProposed {
+            id: u32,
+            proposer: T::AccountId,
+            beneficiary: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        /// A payout proposal was approved and paid.
+        PayoutApproved { id: u32, beneficiary: T::AccountId, amount: BalanceOf<T> },
+        /// A payout proposal was rejected.
+        PayoutRejected { id: u32 },
+        /// An expired payout proposal was removed.
+        PayoutProposalExpired { id: u32 },
+        /// An operator was added.
+        OperatorAdded { who: T::AccountId },
+        /// An operator was removed.
+        OperatorRemoved { who: T::AccountId },
+        /// The pallet was set up after genesis.
+        Initialized {
+            source: T::AccountId,
+            initial_pool: BalanceOf<T>,
+            params: ParamsOf<T>,
+        },
+        /// The parameters were frozen for good.
+        ParamsFrozen,
+        /// The claim cooldown was updated.
+        ClaimCooldownUpdated {
+            old: T::BlockNumber,
+            new: T::BlockNumber,
+            changed_by: Option<T::AccountId>,
+        },
+        /// The minimum claim amount was updated.
+        MinClaimAmountUpdated {
+            old: BalanceOf<T>,
+            new: BalanceOf<T>,
+            changed_by: Option<T::AccountId>,
+        },
+        /// Accounts were added to the claimer whitelist in bulk.
+        ClaimersAdded { added: u32, already_present: u32 },
+        /// Accounts were removed from the claimer whitelist in bulk.
+        ClaimersRemoved { removed: u32, not_present: u32 },
+        /// The dust threshold was updated.
+        DustThresholdUpdated {
+            old: BalanceOf<T>,
+            new: BalanceOf<T>,
+            changed_by: Option<T::AccountId>,
+        },
+        /// Residues below the dust threshold were folded back into the pool during
+        /// this block.
+        DustCollected { amount: BalanceOf<T> },
+    }
+
+    // ---------------------------------------------
+    //  Errors
+    // ---------------------------------------------
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Attempting to distribute or claim more than is available in the pool.
+        InsufficientRewardPool,
+        /// Attempting to claim zero (invalid) or negative (impossible) amount. Non-zero
+        /// claims below the `min_claim_amount` parameter fail with `ClaimBelowMinimum`
+        /// instead.
+        InvalidClaimAmount,
+        /// The origin did not match the required origin for this call.
+        BadOriginForTopUp,
+        /// The pot account does not hold enough free balance to back the pool.
+        InsufficientPotBalance,
+        /// The funding account does not have enough free balance for the top-up.
+        InsufficientFunderBalance,
+        /// Attempting to donate zero.
+        InvalidDonationAmount,
+        /// The donation is below `MinDonation`.
+        DonationBelowMinimum,
+        /// The operation would push the reward pool above `MaxPoolSize`.
+        PoolCapExceeded,
+        /// The payout would leave the destination account below the existential
+        /// deposit, so the funds would be lost.
+        BelowExistentialDeposit,
+        /// A sub-pool with this id already exists.
+        PoolAlreadyExists,
+        /// No sub-pool with this id exists.
+        UnknownPool,
+        /// `MaxPools` sub-pools already exist.
+        TooManyPools,
+        /// A drip needs a non-zero total and per-block amount.
+        InvalidDripSchedule,
+        /// `MaxPendingDrips` drips are already pending.
+        TooManyDrips,
+        /// No pending drip with this id exists.
+        UnknownDrip,
+        /// The claim would push the pool below the `min_pool_reserve` parameter.
+        WouldBreachReserve,
+        /// The caller has no allocation to claim from.
+        NothingToClaim,
+        /// The amount exceeds the account's remaining allocation. Claims are never
+        /// silently capped.
+        ExceedsAllocation,
+        /// Fewer than `claim_cooldown` blocks have passed since the last claim.
+        ClaimTooSoon,
+        /// The claim would exceed `ClaimCapPerPeriod` for the current period.
+        ClaimCapExceeded,
+        /// The claim is below `min_claim_amount` and does not empty the allocation.
+        ClaimBelowMinimum,
+        /// `MaxClaimsPerBlock` claims were already processed in this block.
+        TooManyClaimsThisBlock,
+        /// No Merkle claim root is set.
+        NoClaimRoot,
+        /// The Merkle proof does not match the claim root.
+        InvalidProof,
+        /// This leaf was already claimed.
+        AlreadyClaimed,
+        /// The claim exceeds what is left of the claim root's total.
+        ClaimRootExhausted,
+        /// No voucher signing key is set.
+        NoClaimSigner,
+        /// The voucher signature is not valid for this claimant, amount and nonce.
+        InvalidVoucherSignature,
+        /// The voucher nonce was already redeemed.
+        NonceAlreadyUsed,
+        /// The claimant cannot reserve the `ClaimDeposit`.
+        CannotReserveDeposit,
+        /// The caller is not on the claimer whitelist.
+        NotEligible,
+        /// The claimer whitelist already holds `MaxClaimers` accounts.
+        TooManyClaimers,
+        /// The account is already on the claimer whitelist.
+        AlreadyClaimer,
+        /// The account is not on the claimer whitelist.
+        NotClaimer,
+        /// The account is banned from claiming.
+        AccountBanned,
+        /// The account is not banned.
+        NotBanned,
+        /// The claimant's total balance is below the `eligibility_min_balance` parameter.
+        InsufficientStake,
+        /// The allocation has expired and can no longer be claimed.
+        AllocationExpired,
+        /// A claim with this key was already processed within the retention window.
+        DuplicateClaim,
+        /// The caller is not the claim delegate of the principal.
+        NotDelegate,
+        /// The claim request queue is full.
+        QueueFull,
+        /// No pending claim request with this id belongs to the caller.
+        UnknownClaimRequest,
+        /// The vesting rate must be non-zero.
+        ZeroVestingRate,
+        /// The account has no vesting lock.
+        NotVesting,
+        /// The era has not been settled, or had no budget.
+        EraNotSettled,
+        /// The caller earned no points in this era, or already claimed its share.
+        NoEraPoints,
+        /// The era's shares are past `EraClaimLifetime` and can no longer be claimed.
+        EraExpired,
+        /// The era's shares are still claimable, or never expire.
+        EraNotExpired,
+        /// The claim would exceed the value that may be claimed in this block.
+        BlockClaimBudgetExceeded,
+        /// The beneficiary has not opted in to third-party triggered claims.
+        ThirdPartyClaimsNotAllowed,
+        /// The author is already excluded from block rewards.
+        AlreadyExcluded,
+        /// The author is not excluded from block rewards.
+        NotExcluded,
+        /// `MaxExcludedAuthors` authors are already excluded.
+        TooManyExcludedAuthors,
+        /// The caller is not the pending admin.
+        NotPendingAdmin,
+        /// The parameters are inconsistent, e.g. they do not fit under `MaxPoolSize`.
+        InvalidParams,
+        /// The pallet is paused.
+        PalletPaused,
+        /// `MaxScheduledParamChanges` changes are already scheduled.
+        TooManyParamChanges,
+        /// No scheduled parameter change with this id exists.
+        UnknownParamChange,
+        /// A parameter change must be scheduled for a future block.
+        ParamChangeInPast,
+        /// The top-up exceeds the caller's remaining allowance for this era.
+        TopUpAllowanceExceeded,
+        /// `MaxTopUpOperators` accounts already hold a top-up allowance.
+        TooManyTopUpOperators,
+        /// The transaction paid less than the minimum consensus fee.
+        BelowMinConsensusFee,
+        /// The payout exceeds `DirectPayoutLimit`; use `propose_payout`.
+        ExceedsDirectPayoutLimit,
+        /// `MaxPayoutProposals` proposals are already pending.
+        TooManyPayoutProposals,
+        /// No payout proposal with this id exists.
+        UnknownPayoutProposal,
+        /// The payout proposal has expired.
+        PayoutProposalExpired,
+        /// The payout proposal has not expired yet.
+        PayoutProposalNotExpired,
+        /// The account is already an operator.
+        AlreadyOperator,
+        /// The account is not an operator.
+        NotOperator,
+        /// `MaxOperators` operators already exist.
+        TooManyOperators,
+        /// The pallet has not been initialized yet.
+        NotInitialized,
+        /// The pallet was already initialized.
+        AlreadyInitialized,
+        /// The parameters are frozen.
+        ParamsFrozen,
+    }
+
+    // ---------------------------------------------
+    //  Hooks: Automatic Block Reward Logic
+    // ---------------------------------------------
+
+    /// We use the `on_initialize` hook to mint the per-era pool inflation, if
+    /// configured, and to pre-charge the block author reward, which is paid in
+    /// `on_finalize` once the author is known.
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(n: T::BlockNumber) -> Weight {
+            // Reset the per-block claim counter and value accumulator, and pre-charge
+            // reporting the block's dust in `on_finalize`
+            ClaimsThisBlock::<T>::kill();
+            ClaimedThisBlock::<T>::kill();
+            let mut weight = T::DbWeight::get().reads_writes(1, 3);
+
+            // Paused, nothing is emitted or released; `on_finalize` only clears
+            // the noted author.
+            if Self::paused() {
+                return weight.saturating_add(T::WeightInfo::on_initialize_reward(0))
+            }
+
+            weight = weight.saturating_add(Self::maybe_inflate_pool(n));
+            weight = weight.saturating_add(Self::release_drip());
+            weight = weight.saturating_add(Self::process_claim_queue());
+            weight = weight.saturating_add(Self::apply_param_changes(n));
+
+            // Pre-charge the block reward paid in `on_finalize`. Every skip decided
+            // there is cheaper than the payout, so only the pause flag can lower it.
+            let rewarded = !Self::block_rewards_paused();
+            weight.saturating_add(T::WeightInfo::on_initialize_reward(rewarded as u32))
+        }
+
+        /// Pay the block reward to the author noted during the block.
+        fn on_finalize(_n: T::BlockNumber) {
+            let dust = DustCollectedThisBlock::<T>::take();
+            if !dust.is_zero() {
+                Self::deposit_event(Event::DustCollected { amount: dust });
+            }
+
+            if Self::paused() || Self::block_rewards_paused() {
+                NotedAuthor::<T>::kill();
+                return;
+            }
+            Self::reward_block_author();
         }
 
-        /// Claim `amount` of tokens from the reward pool (e.g., for developer rewards).
+        /// Pay out small allocations while there is spare weight left in the block.
+        fn on_idle(_n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+            if Self::paused() {
+                return T::DbWeight::get().reads(1)
+            }
+            Self::auto_payout_small_allocations(remaining_weight)
+        }
+
+        /// Reject emission parameters that can only misbehave on-chain.
+        fn integrity_test() {
+            Self::assert_config_consistent();
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn try_state(_n: T::BlockNumber) -> Result<(), &'static str> {
+            ensure!(
+                Self::accounting_consistent(),
+                "TotalToppedUp does not equal RewardPool + TotalAllocated + TotalDistributed"
+            );
+            Ok(())
+        }
+    }
+
+    // ---------------------------------------------
+    //  Extrinsics
+    // ---------------------------------------------
+
+    /// The callable functions (extrinsics) of this pallet.
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Top up the reward pool by `amount`. Must come from `FundingOrigin`, or
+        /// from an operator with enough `TopUpAllowance` left this era.
+        ///
+        /// `amount` is transferred into the pot from the account the origin yields,
+        /// or from the `TreasuryAccount` when it yields none (e.g. root), before
+        /// `RewardPool` is increased. The treasury is always kept alive.
         ///
         /// # Arguments
-        /// * `origin` - Any signed account that is eligible to claim (in real systems,
-        ///   you'd verify eligibility and usage metrics).
-        /// * `amount` - The amount to claim.
-        #[pallet::weight(10_000)]
-        pub fn claim_reward(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
-            let claimant = ensure_signed(origin)?;
+        /// * `origin` - Must satisfy the `FundingOrigin`, or be a signed operator.
+        /// * `amount` - The amount to add to the reward pool.
+        #[pallet::weight(T::WeightInfo::top_up_pool())]
+        pub fn top_up_pool(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            // Check that the origin is authorized, or spend the caller's allowance
+            let funder = match Self::ensure_funding_origin(origin.clone()) {
+                Ok(funder) => funder,
+                Err(_) => {
+                    let who = ensure_signed(origin).map_err(|_| Error::<T>::BadOriginForTopUp)?;
+                    Self::spend_top_up_allowance(&who, amount)?;
+                    Some(who)
+                },
+            };
+            let payer = funder.clone().unwrap_or_else(T::TreasuryAccount::get);
 
-            // Validate the requested amount
-            ensure!(!amount.is_zero(), Error::<T>::InvalidClaimAmount);
+            let pool_before = Self::reward_pool();
+            let new_pool = pool_before
+                .checked_add(&amount)
+                .ok_or(ArithmeticError::Overflow)?;
+            Self::ensure_within_cap(new_pool)?;
+
+            // Make sure the funder can actually cover the top-up
+            ensure!(
+                T::Currency::free_balance(&payer) >= amount,
+                Error::<T>::InsufficientFunderBalance
+            );
+
+            // Move the funds into the pot, never reaping the treasury
+            let existence = match funder {
+                Some(_) => ExistenceRequirement::AllowDeath,
+                None => ExistenceRequirement::KeepAlive,
+            };
+            T::Currency::transfer(&payer, &Self::account_id(), amount, existence)?;
+
+            // Update the storage
+            RewardPool::<T>::put(new_pool);
+            Self::note_inflow(amount);
+
+            // Emit event
+            Self::deposit_event(Event::RewardPoolIncreased(funder, amount, new_pool));
+
+            Ok(())
+        }
+
+        /// Mint `amount` directly into the pot and add it to the reward pool.
+        ///
+        /// This increases total issuance and is only meant for test chains.
+        ///
+        /// # Arguments
+        /// * `origin` - Must be Root.
+        /// * `amount` - The amount to mint into the reward pool.
+        #[pallet::weight(T::WeightInfo::force_top_up_pool())]
+        pub fn force_top_up_pool(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            ensure_root(origin)?;
 
-            // Check if the pool has enough funds
             let pool_before = Self::reward_pool();
-            ensure!(pool_before >= amount, Error::<T>::InsufficientRewardPool);
+            let new_pool = pool_before
+                .checked_add(&amount)
+                .ok_or(ArithmeticError::Overflow)?;
+            Self::ensure_within_cap(new_pool)?;
 
-            // Update the pool
-            let new_pool = pool_before - amount;
+            // Mint into the pot so the pool stays fully backed
+            T::Currency::deposit_creating(&Self::account_id(), amount);
+
+            // Update the storage
             RewardPool::<T>::put(new_pool);
+            Self::note_inflow(amount);
 
-            // Update the total distributed
-            let total_dist_before = Self::total_distributed();
-            let new_total_dist = total_dist_before + amount;
-            TotalDistributed::<T>::put(new_total_dist);
+            // Emit event
+            Self::deposit_event(Event::RewardPoolIncreased(None, amount, new_pool));
+
+            Ok(())
+        }
+
+        /// Donate `amount` from the caller into the reward pool. Anyone can call this.
+        ///
+        /// # Arguments
+        /// * `origin` - Any signed account.
+        /// * `amount` - The amount to donate. Must be at least `MinDonation`.
+        #[pallet::weight(T::WeightInfo::donate_to_pool())]
+        pub fn donate_to_pool(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let who = ensure_signed(origin)?;
+
+            // Validate the donation
+            ensure!(!amount.is_zero(), Error::<T>::InvalidDonationAmount);
+            ensure!(amount >= T::MinDonation::get(), Error::<T>::DonationBelowMinimum);
+
+            let pool_before = Self::reward_pool();
+            let new_pool = pool_before
+                .checked_add(&amount)
+                .ok_or(ArithmeticError::Overflow)?;
+            Self::ensure_within_cap(new_pool)?;
 
-            // Transfer to the claimant
-            T::Currency::deposit_creating(&claimant, amount);
+            // Move the funds into the pot
+            T::Currency::transfer(
+                &who,
+                &Self::account_id(),
+                amount,
+                ExistenceRequirement::AllowDeath,
+            )?;
+
+            // Update the storage
+            RewardPool::<T>::put(new_pool);
+            Self::note_inflow(amount);
 
             // Emit event
-            Self::deposit_event(Event::RewardClaimed(claimant, amount));
+            Self::deposit_event(Event::PoolDonated { who, amount, new_total: new_pool });
+
             Ok(())
         }
-    }
-}
 
+        /// Claim `amount` of tokens from the caller's allocation (e.g., for developer
+        /// rewards). If the caller has no allocation and `AllowUnallocatedClaims` is
+        /// set, the amount is drawn from the reward pool directly instead.
+        ///
+        /// # Arguments
+        /// * `origin` - Any signed account that is eligible to claim.
+        /// * `amount` - The amount to claim. Must not exceed the caller's allocation.
+        /// * `claim_key` - An optional idempotency key. A repeat submission with the
+        ///   same key within `ClaimKeyRetention` blocks fails with `DuplicateClaim`.
+        ///
+        /// Successful claims by whitelisted or allocated accounts are fee-free.
+        #[pallet::weight(T::WeightInfo::claim_reward())]
+        pub fn claim_reward(
+            origin: OriginFor<T>,
+            amount: BalanceOf<T>,
+            claim_key: Option<ClaimKey>,
+        ) -> DispatchResultWithPostInfo {
+            Self::ensure_not_paused()?;
+            let claimant = ensure_signed(origin)?;
+
+            // Validate the requested amount
+            ensure!(!amount.is_zero(), Error::<T>::InvalidClaimAmount);
+
+            // Reject retries before touching the pool
+            if let Some(key) = &claim_key {
+                ensure!(!Self::claim_key_active(&claimant, key), Error::<T>::DuplicateClaim);
+            }
+
+            // Decide fee eligibility before the claim consumes the allocation
+            let fee_free = Claimers::<T>::contains_key(&claimant) ||
+                !Self::allocations(&claimant).is_zero();
+
+            let paid_out = Self::claim_with_deposit(&claimant, amount, None, claim_key)?;
+
+            Ok(if fee_free && paid_out { Pays::No } else { Pays::Yes }.into())
+        }
+
+        /// Same as `claim_reward`, but labels the claim with `memo` (e.g. "bug bounty
+        /// #17") in the `RewardClaimed` event. The memo is not stored.
+        ///
+        /// # Arguments
+        /// * `origin` - Any signed account that is eligible to claim.
+        /// * `amount` - The amount to claim. Must not exceed the caller's allocation.
+        /// * `memo` - The label, at most `MaxMemoLength` bytes.
+        #[pallet::weight(T::WeightInfo::claim_reward_with_memo())]
+        pub fn claim_reward_with_memo(
+            origin: OriginFor<T>,
+            amount: BalanceOf<T>,
+            memo: Memo<T>,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let claimant = ensure_signed(origin)?;
+
+            // Validate the requested amount
+            ensure!(!amount.is_zero(), Error::<T>::InvalidClaimAmount);
+
+            Self::claim_with_deposit(&claimant, amount, Some(memo), None).map(|_| ())
+        }
+
+        /// Claim the caller's entire outstanding allocation.
+        ///
+        /// # Arguments
+        /// * `origin` - Any signed account with a non-zero allocation.
+        #[pallet::weight(T::WeightInfo::claim_all())]
+        pub fn claim_all(origin: OriginFor<T>) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let claimant = ensure_signed(origin)?;
+
+            let allocation = Self::allocations(&claimant);
+            ensure!(!allocation.is_zero(), Error::<T>::NothingToClaim);
+
+            Self::do_claim(&claimant, allocation, None)
+        }
+
+        /// Pay `beneficiary`'s full allocation to `beneficiary`. Anyone can call this,
+        /// e.g. reward bots or custodial setups; the funds never go to the caller
+        /// apart from the optional `PayoutCallerReward` tip.
+        ///
+        /// # Arguments
+        /// * `origin` - Any signed account.
+        /// * `beneficiary` - The account whose allocation is paid out.
+        #[pallet::weight(T::WeightInfo::payout_to())]
+        pub fn payout_to(origin: OriginFor<T>, beneficiary: T::AccountId) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let caller = ensure_signed(origin)?;
+
+            let allocation = Self::allocations(&beneficiary);
+            ensure!(!allocation.is_zero(), Error::<T>::NothingToClaim);
+
+            // Only tip third parties, and only when there's something left to pay
+            let tip = T::PayoutCallerReward::get();
+            let tip = if caller != beneficiary && tip < allocation { tip } else { Zero::zero() };
+
+            // The tip comes out of the beneficiary's allocation. Take it first, so the
+            // claim below empties what is left and never folds the tip away as dust.
+            if !tip.is_zero() {
+                Self::pay_from_pot(&caller, tip)?;
+
+                Self::set_allocation_amount(&beneficiary, allocation - tip);
+                TotalAllocated::<T>::mutate(|total| *total = total.saturating_sub(tip));
+                TotalDistributed::<T>::mutate(|total| *total = total.saturating_add(tip));
+            }
+
+            Self::do_claim(&beneficiary, allocation - tip, None)?;
+
+            if !tip.is_zero() {
+                Self::deposit_event(Event::PayoutTipPaid { caller, beneficiary, amount: tip });
+            }
+
+            Ok(())
+        }
+
+        /// Allocate `amount` from the reward pool to `who`, to be claimed later via
+        /// `claim_reward`. The amount is reserved from the pool immediately.
+        ///
+        /// # Arguments
+        /// * `origin` - An operator, or must satisfy the `ParamsOrigin`.
+        /// * `who` - The account receiving the allocation.
+        /// * `amount` - The amount to allocate.
+        #[pallet::weight(T::WeightInfo::allocate_reward())]
+        pub fn allocate_reward(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            Self::ensure_operator(origin)?;
+
+            ensure!(!amount.is_zero(), Error::<T>::InvalidClaimAmount);
+            Self::do_allocate(&who, amount)?;
+
+            // Emit event
+            Self::deposit_event(Event::RewardAllocated(who, amount));
+
+            Ok(())
+        }
+
+        /// Allocate rewards to many accounts at once. The total is validated against
+        /// the pool up front, so either every allocation is written or none is.
+        /// Repeated accounts accumulate.
+        ///
+        /// # Arguments
+        /// * `origin` - An operator, or must satisfy the `ParamsOrigin`.
+        /// * `allocations` - The `(who, amount)` pairs to allocate.
+        #[pallet::weight(T::WeightInfo::allocate_many(allocations.len() as u32))]
+        pub fn allocate_many(
+            origin: OriginFor<T>,
+            allocations: BoundedVec<(T::AccountId, BalanceOf<T>), T::MaxAllocationsPerCall>,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            Self::ensure_operator(origin)?;
+
+            // Validate the whole batch before touching storage
+            let mut total: BalanceOf<T> = Zero::zero();
+            for (_, amount) in allocations.iter() {
+                ensure!(!amount.is_zero(), Error::<T>::InvalidClaimAmount);
+                total = total.checked_add(amount).ok_or(ArithmeticError::Overflow)?;
+            }
+            let new_pool = Self::reward_pool()
+                .checked_sub(&total)
+                .ok_or(Error::<T>::InsufficientRewardPool)?;
+            ensure!(new_pool >= Self::min_pool_reserve(), Error::<T>::WouldBreachReserve);
+
+            // Write the allocations
+            let count = allocations.len() as u32;
+            for (who, amount) in allocations.into_iter() {
+                Self::do_allocate(&who, amount)?;
+            }
+
+            // Emit event
+            Self::deposit_event(Event::RewardsAllocated { count, total });
+
+            Ok(())
+        }
+
+        /// Revoke or reduce the outstanding allocation of `who`, returning the revoked
+        /// amount to the pool. Already-claimed portions are untouched.
+        ///
+        /// # Arguments
+        /// * `origin` - An operator, or must satisfy the `ParamsOrigin`.
+        /// * `who` - The account whose allocation is revoked.
+        /// * `amount` - `None` removes the whole allocation, `Some(x)` reduces it by `x`.
+        #[pallet::weight(T::WeightInfo::revoke_allocation())]
+        pub fn revoke_allocation(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+            amount: Option<BalanceOf<T>>,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            Self::ensure_operator(origin)?;
+
+            let allocation = Self::allocations(&who);
+            ensure!(!allocation.is_zero(), Error::<T>::NothingToClaim);
+            let amount = amount.unwrap_or(allocation);
+            ensure!(amount <= allocation, Error::<T>::ExceedsAllocation);
+
+            let remaining = Self::do_revoke(&who, amount);
+
+            // Emit event
+            Self::deposit_event(Event::AllocationRevoked { who, amount, remaining });
+
+            Ok(())
+        }
+
+        /// Overwrite the `RewardPool` storage value, e.g. to correct drift after a bug
+        /// or a manual transfer into the pot. This is an administrative correction,
+        /// not funding, and is evented separately from `top_up_pool`.
+        ///
+        /// # Arguments
+        /// * `origin` - Must be Root.
+        /// * `new_value` - The new pool value. Cannot exceed the pot balance.
+        #[pallet::weight(T::WeightInfo::force_set_reward_pool())]
+        pub fn force_set_reward_pool(
+            origin: OriginFor<T>,
+            new_value: BalanceOf<T>,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            ensure_root(origin)?;
+
+            // Never account for more than the pot actually holds
+            ensure!(
+                Self::pot() >= new_value.saturating_add(Self::total_allocated()),
+                Error::<T>::InsufficientPotBalance
+            );
+
+            let old = Self::reward_pool();
+            RewardPool::<T>::put(new_value);
+
+            // Keep the accounting identity intact by treating the correction as a
+            // net inflow or outflow.
+            if new_value >= old {
+                Self::note_inflow(new_value - old);
+            } else {
+                Self::note_outflow(old - new_value);
+            }
+
+            // Emit event
+            Self::deposit_event(Event::RewardPoolForceSet { old, new: new_value });
+
+            Ok(())
+        }
+
+        /// Withdraw `amount` of excess funds from the reward pool to `beneficiary`.
+        /// This is not a reward, so `TotalDistributed` is left untouched.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `FundingOrigin`.
+        /// * `amount` - The amount to remove from the pool.
+        /// * `beneficiary` - The account receiving the withdrawn funds.
+        #[pallet::weight(T::WeightInfo::withdraw_from_pool())]
+        pub fn withdraw_from_pool(
+            origin: OriginFor<T>,
+            amount: BalanceOf<T>,
+            beneficiary: T::AccountId,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            Self::ensure_funding_origin(origin)?;
+
+            let pool_before = Self::reward_pool();
+            let remaining = pool_before
+                .checked_sub(&amount)
+                .ok_or(Error::<T>::InsufficientRewardPool)?;
+
+            // Move the funds out of the pot
+            T::Currency::transfer(
+                &Self::account_id(),
+                &beneficiary,
+                amount,
+                ExistenceRequirement::AllowDeath,
+            )?;
+
+            // Update the storage
+            RewardPool::<T>::put(remaining);
+            Self::note_outflow(amount);
+
+            // Emit event
+            Self::deposit_event(Event::RewardPoolDecreased { amount, beneficiary, remaining });
+
+            Ok(())
+        }
+
+        /// Burn `amount` of the reward pool, e.g. when a reward program ends. The funds
+        /// are withdrawn from the pot and destroyed, so total issuance drops.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `FundingOrigin`.
+        /// * `amount` - The amount to burn. Passing the full pool drains it.
+        #[pallet::weight(T::WeightInfo::burn_pool())]
+        pub fn burn_pool(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            Self::ensure_funding_origin(origin)?;
+
+            let pool_before = Self::reward_pool();
+            let remaining = pool_before
+                .checked_sub(&amount)
+                .ok_or(Error::<T>::InsufficientRewardPool)?;
+
+            // The pot may hold less than the storage value says
+            ensure!(Self::pot() >= amount, Error::<T>::InsufficientPotBalance);
+
+            // Withdraw from the pot and drop the imbalance, which burns it
+            let imbalance = T::Currency::withdraw(
+                &Self::account_id(),
+                amount,
+                WithdrawReasons::TRANSFER,
+                ExistenceRequirement::AllowDeath,
+            )?;
+            drop(imbalance);
+
+            // Update the storage
+            RewardPool::<T>::put(remaining);
+            Self::note_outflow(amount);
+
+            // Emit event
+            Self::deposit_event(Event::PoolBurned(amount, remaining));
+
+            Ok(())
+        }
+
+        /// Absorb any surplus in the pot (e.g. from direct transfers) into the reward
+        /// pool. Anyone can call this. It never decreases the pool, and only credits
+        /// up to `MaxPoolSize`.
+        ///
+        /// # Arguments
+        /// * `origin` - Any signed account.
+        #[pallet::weight(T::WeightInfo::sync_pool())]
+        pub fn sync_pool(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+            Self::ensure_not_paused()?;
+            ensure_signed(origin)?;
+
+            let pool = Self::reward_pool();
+            let surplus = Self::pot().saturating_sub(pool.saturating_add(Self::total_allocated()));
+            let synced = match Self::remaining_capacity() {
+                Some(room) => surplus.min(room),
+                None => surplus,
+            };
+
+            // Nothing to sync, only the pool and pot reads were performed
+            if synced.is_zero() {
+                return Ok(Some(T::DbWeight::get().reads(2)).into())
+            }
+
+            // Update the storage
+            let new_pool = pool.saturating_add(synced);
+            RewardPool::<T>::put(new_pool);
+            Self::note_inflow(synced);
+
+            // Emit event
+            Self::deposit_event(Event::RewardPoolIncreased(None, synced, new_pool));
+
+            Ok(().into())
+        }
+
+        /// Create a new, empty named sub-pool.
+        ///
+        /// The default program keeps using `RewardPool` and the existing calls; named
+        /// sub-pools are fully isolated from it and from each other.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `ParamsOrigin`.
+        /// * `pool_id` - The identifier of the new sub-pool.
+        #[pallet::weight(T::WeightInfo::create_pool())]
+        pub fn create_pool(origin: OriginFor<T>, pool_id: PoolId<T>) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            Self::ensure_params_origin(origin)?;
+
+            ensure!(Self::pool_info(&pool_id).is_none(), Error::<T>::PoolAlreadyExists);
+            let count = Self::pool_count();
+            ensure!(count < T::MaxPools::get(), Error::<T>::TooManyPools);
+
+            // Update the storage
+            Pools::<T>::insert(&pool_id, PoolInfo::default());
+            PoolCount::<T>::put(count + 1);
+
+            // Emit event
+            Self::deposit_event(Event::SubPoolCreated(pool_id));
+
+            Ok(())
+        }
+
+        /// Top up the named sub-pool `pool_id` by transferring `amount` from the
+        /// caller into the sub-pool's account. The default pool id tops up
+        /// `RewardPool` as `top_up_pool` does.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `FundingOrigin` and be signed.
+        /// * `pool_id` - The sub-pool to fund.
+        /// * `amount` - The amount to add.
+        #[pallet::weight(T::WeightInfo::top_up_sub_pool())]
+        pub fn top_up_sub_pool(
+            origin: OriginFor<T>,
+            pool_id: PoolId<T>,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            if Self::is_default_pool(&pool_id) {
+                return Self::top_up_pool(origin, amount);
+            }
+            Self::ensure_funding_origin(origin.clone()).map_err(|_| Error::<T>::BadOriginForTopUp)?;
+            let funder = ensure_signed(origin)?;
+
+            let mut info = Self::pools(&pool_id).ok_or(Error::<T>::UnknownPool)?;
+            info.balance = info.balance.checked_add(&amount).ok_or(ArithmeticError::Overflow)?;
+
+            // Make sure the funder can actually cover the top-up
+            ensure!(
+                T::Currency::free_balance(&funder) >= amount,
+                Error::<T>::InsufficientFunderBalance
+            );
+
+            // Move the funds into the sub-pool's account
+            T::Currency::transfer(
+                &funder,
+                &Self::sub_pool_account(&pool_id),
+                amount,
+                ExistenceRequirement::AllowDeath,
+            )?;
+
+            // Update the storage
+            let new_total = info.balance;
+            Pools::<T>::insert(&pool_id, info);
+
+            // Emit event
+            Self::deposit_event(Event::SubPoolIncreased(pool_id, funder, amount, new_total));
+
+            Ok(())
+        }
+
+        /// Allocate `amount` from the named sub-pool `pool_id` to `who`, to be claimed
+        /// later via `claim_from_pool`. The default pool id allocates from
+        /// `RewardPool` as `allocate_reward` does.
+        ///
+        /// # Arguments
+        /// * `origin` - An operator, or must satisfy the `ParamsOrigin`.
+        /// * `pool_id` - The sub-pool to allocate from.
+        /// * `who` - The account receiving the allocation.
+        /// * `amount` - The amount to allocate.
+        #[pallet::weight(T::WeightInfo::allocate_from_pool())]
+        pub fn allocate_from_pool(
+            origin: OriginFor<T>,
+            pool_id: PoolId<T>,
+            who: T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            Self::ensure_operator(origin)?;
+
+            ensure!(!amount.is_zero(), Error::<T>::InvalidClaimAmount);
+            if Self::is_default_pool(&pool_id) {
+                Self::do_allocate(&who, amount)?;
+                Self::deposit_event(Event::RewardAllocated(who, amount));
+                return Ok(());
+            }
+
+            let mut info = Self::pools(&pool_id).ok_or(Error::<T>::UnknownPool)?;
+            info.balance =
+                info.balance.checked_sub(&amount).ok_or(Error::<T>::InsufficientRewardPool)?;
+            let allocation = Self::pool_allocations(&pool_id, &who)
+                .checked_add(&amount)
+                .ok_or(ArithmeticError::Overflow)?;
+
+            // Update the storage
+            Pools::<T>::insert(&pool_id, info);
+            PoolAllocations::<T>::insert(&pool_id, &who, allocation);
+
+            // Emit event
+            Self::deposit_event(Event::SubPoolRewardAllocated(pool_id, who, amount));
+
+            Ok(())
+        }
+
+        /// Claim `amount` from the named sub-pool `pool_id`. Never touches any other
+        /// pool. The claim is drawn from the caller's allocation in that sub-pool, or
+        /// from its balance when `AllowUnallocatedClaims` is set, and is subject to
+        /// the same eligibility checks and limits as `claim_reward`. The default
+        /// pool id claims from `RewardPool` as `claim_reward` does.
+        ///
+        /// # Arguments
+        /// * `origin` - Any signed account that is eligible to claim.
+        /// * `pool_id` - The sub-pool to claim from.
+        /// * `amount` - The amount to claim.
+        #[pallet::weight(T::WeightInfo::claim_from_pool())]
+        pub fn claim_from_pool(
+            origin: OriginFor<T>,
+            pool_id: PoolId<T>,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let claimant = ensure_signed(origin)?;
+
+            // Validate the requested amount
+            ensure!(!amount.is_zero(), Error::<T>::InvalidClaimAmount);
+
+            if Self::is_default_pool(&pool_id) {
+                return Self::claim_with_deposit(&claimant, amount, None, None).map(|_| ());
+            }
+
+            let now = frame_system::Pallet::<T>::block_number();
+            let mut info = Self::pools(&pool_id).ok_or(Error::<T>::UnknownPool)?;
+            let allocation = Self::pool_allocations(&pool_id, &claimant);
+            if allocation.is_zero() {
+                ensure!(T::AllowUnallocatedClaims::get(), Error::<T>::NothingToClaim);
+                info.balance =
+                    info.balance.checked_sub(&amount).ok_or(Error::<T>::InsufficientRewardPool)?;
+            } else {
+                ensure!(amount <= allocation, Error::<T>::ExceedsAllocation);
+            }
+            info.total_distributed = info.total_distributed.saturating_add(amount);
+
+            // Dust claims are rejected, unless they empty the allocation
+            ensure!(
+                amount >= Self::params().min_claim_amount || amount == allocation,
+                Error::<T>::ClaimBelowMinimum
+            );
+
+            Self::ensure_can_claim(&claimant, amount, now)?;
+
+            // A fresh account receiving less than the ED would be dusted
+            let dest = Self::payout_account(&claimant);
+            ensure!(Self::would_survive(&dest, amount), Error::<T>::BelowExistentialDeposit);
+
+            // Transfer to the claimant from the sub-pool's account
+            T::Currency::transfer(
+                &Self::sub_pool_account(&pool_id),
+                &dest,
+                amount,
+                ExistenceRequirement::AllowDeath,
+            )
+            .map_err(|_| Error::<T>::InsufficientPotBalance)?;
+
+            // Update the storage
+            Pools::<T>::insert(&pool_id, info);
+            if !allocation.is_zero() {
+                PoolAllocations::<T>::mutate_exists(&pool_id, &claimant, |remaining| {
+                    *remaining = remaining
+                        .map(|remaining| remaining.saturating_sub(amount))
+                        .filter(|remaining| !remaining.is_zero());
+                });
+            }
+            Self::note_claim(&claimant, amount, now);
+
+            // Emit event
+            Self::deposit_event(Event::SubPoolRewardClaimed(pool_id, claimant, amount));
+
+            Ok(())
+        }
+
+        /// Escrow `total` from the caller and release it into the reward pool at
+        /// `per_block` per block, avoiding a cliff in claimable funds.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `FundingOrigin` and be signed.
+        /// * `total` - The total amount to drip into the pool.
+        /// * `per_block` - The amount released each block.
+        #[pallet::weight(T::WeightInfo::schedule_top_up())]
+        pub fn schedule_top_up(
+            origin: OriginFor<T>,
+            total: BalanceOf<T>,
+            per_block: BalanceOf<T>,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            Self::ensure_funding_origin(origin.clone()).map_err(|_| Error::<T>::BadOriginForTopUp)?;
+            let funder = ensure_signed(origin)?;
+
+            ensure!(
+                !total.is_zero() && !per_block.is_zero(),
+                Error::<T>::InvalidDripSchedule
+            );
+            ensure!(
+                T::Currency::free_balance(&funder) >= total,
+                Error::<T>::InsufficientFunderBalance
+            );
+
+            let id = NextDripId::<T>::get();
+            PendingDrips::<T>::try_mutate(|drips| {
+                drips
+                    .try_push(DripInfo { id, funder: funder.clone(), remaining: total, per_block })
+                    .map_err(|_| Error::<T>::TooManyDrips)
+            })?;
+            NextDripId::<T>::put(id.wrapping_add(1));
+
+            // Escrow the funds until they are released
+            T::Currency::transfer(
+                &funder,
+                &Self::drip_account(),
+                total,
+                ExistenceRequirement::AllowDeath,
+            )?;
+
+            // Emit event
+            Self::deposit_event(Event::DripScheduled { id, total, per_block });
+
+            Ok(())
+        }
+
+        /// Cancel the pending drip `id` and refund its unreleased remainder.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `FundingOrigin`.
+        /// * `id` - The drip to cancel.
+        /// * `refund_to` - The account receiving the unreleased remainder.
+        #[pallet::weight(T::WeightInfo::cancel_top_up())]
+        pub fn cancel_top_up(
+            origin: OriginFor<T>,
+            id: u32,
+            refund_to: T::AccountId,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            Self::ensure_funding_origin(origin)?;
+
+            let mut drips = Self::pending_drips();
+            let index = drips.iter().position(|d| d.id == id).ok_or(Error::<T>::UnknownDrip)?;
+            let drip = drips.remove(index);
+
+            // Refund the remainder from escrow
+            T::Currency::transfer(
+                &Self::drip_account(),
+                &refund_to,
+                drip.remaining,
+                ExistenceRequirement::AllowDeath,
+            )?;
+
+            // Update the storage
+            PendingDrips::<T>::put(drips);
+
+            // Emit event
+            Self::deposit_event(Event::DripCancelled { id, refunded: drip.remaining, refund_to });
+
+            Ok(())
+        }
+
+        /// Set the minimum reserve that manual claims cannot breach.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `ParamsOrigin`.
+        /// * `reserve` - The new reserve. Zero disables the floor.
+        #[pallet::weight(T::WeightInfo::set_min_pool_reserve())]
+        pub fn set_min_pool_reserve(origin: OriginFor<T>, reserve: BalanceOf<T>) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let changed_by = Self::acting_account(&origin);
+            Self::ensure_params_origin(origin)?;
+
+            let old = Self::min_pool_reserve();
+            Self::mutate_params(|p| p.min_pool_reserve = reserve)?;
+
+            // Emit event
+            Self::deposit_event(Event::MinPoolReserveUpdated { old, new: reserve, changed_by });
+
+            Ok(())
+        }
+
+        /// Set (or clear with `None`) the account that receives the caller's rewards,
+        /// e.g. a cold wallet. Applies to claims and block-author rewards.
+        ///
+        /// # Arguments
+        /// * `origin` - Any signed account.
+        /// * `dest` - The payout destination, or `None` to pay the caller directly.
+        #[pallet::weight(T::WeightInfo::set_payout_destination())]
+        pub fn set_payout_destination(
+            origin: OriginFor<T>,
+            dest: Option<T::AccountId>,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let who = ensure_signed(origin)?;
+
+            // Clearing removes the entry to avoid storage bloat
+            let old = Self::payout_destination(&who);
+            match &dest {
+                Some(dest) => PayoutDestination::<T>::insert(&who, dest),
+                None => PayoutDestination::<T>::remove(&who),
+            }
+
+            // Emit event
+            let changed_by = Some(who.clone());
+            Self::deposit_event(Event::PayoutDestinationUpdated {
+                who,
+                old,
+                new: dest,
+                changed_by,
+            });
+
+            Ok(())
+        }
+
+        /// Remove the stored claim history of `who`.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `ParamsOrigin`.
+        /// * `who` - The account whose history is pruned.
+        #[pallet::weight(T::WeightInfo::prune_claim_history())]
+        pub fn prune_claim_history(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            Self::ensure_params_origin(origin)?;
+
+            ClaimHistory::<T>::remove(&who);
+
+            // Emit event
+            Self::deposit_event(Event::ClaimHistoryPruned(who));
+
+            Ok(())
+        }
+
+        /// Set the Merkle root of an airdrop-style distribution, replacing any
+        /// previous root. `total` is set aside from the pool, and whatever the
+        /// previous root left unclaimed is returned to it.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `ParamsOrigin`.
+        /// * `root` - The Merkle root over `(account, amount)` leaves.
+        /// * `total` - The sum of all amounts committed to by the root.
+        #[pallet::weight(T::WeightInfo::set_claim_root())]
+        pub fn set_claim_root(
+            origin: OriginFor<T>,
+            root: H256,
+            total: BalanceOf<T>,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            Self::ensure_params_origin(origin)?;
+
+            let unclaimed =
+                Self::claim_root().map(|(_, remaining)| remaining).unwrap_or_else(Zero::zero);
+            let new_pool = Self::reward_pool()
+                .saturating_add(unclaimed)
+                .checked_sub(&total)
+                .ok_or(Error::<T>::InsufficientRewardPool)?;
+            ensure!(new_pool >= Self::min_pool_reserve(), Error::<T>::WouldBreachReserve);
+
+            // Update the storage
+            RewardPool::<T>::put(new_pool);
+            TotalAllocated::<T>::mutate(|allocated| {
+                *allocated = allocated.saturating_sub(unclaimed).saturating_add(total)
+            });
+            ClaimRoot::<T>::put((root, total));
+
+            // Emit event
+            Self::deposit_event(Event::ClaimRootSet { root, total });
+
+            Ok(())
+        }
+
+        /// Claim `amount` by proving that the `(caller, amount)` leaf is part of the
+        /// current claim root. Each leaf can only be claimed once.
+        ///
+        /// # Arguments
+        /// * `origin` - The signed account named in the leaf.
+        /// * `amount` - The amount named in the leaf.
+        /// * `proof` - The sibling hashes from the leaf up to the root.
+        #[pallet::weight(T::WeightInfo::claim_with_proof(proof.len() as u32))]
+        pub fn claim_with_proof(
+            origin: OriginFor<T>,
+            amount: BalanceOf<T>,
+            proof: BoundedVec<H256, T::MaxProofLen>,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let claimant = ensure_signed(origin)?;
+
+            ensure!(!amount.is_zero(), Error::<T>::InvalidClaimAmount);
+
+            let (root, remaining) = Self::claim_root().ok_or(Error::<T>::NoClaimRoot)?;
+            let leaf = H256(blake2_256(&(&claimant, amount).encode()));
+            ensure!(!ClaimedLeaves::<T>::contains_key(root, leaf), Error::<T>::AlreadyClaimed);
+            ensure!(Self::verify_proof(root, leaf, &proof), Error::<T>::InvalidProof);
+
+            let now = frame_system::Pallet::<T>::block_number();
+            Self::ensure_can_claim(&claimant, amount, now)?;
+
+            // The root's total was set aside when it was set, so only check what is left
+            let remaining = remaining.checked_sub(&amount).ok_or(Error::<T>::ClaimRootExhausted)?;
+
+            Self::pay_from_pot(&Self::payout_account(&claimant), amount)?;
+
+            // Update the storage
+            ClaimRoot::<T>::put((root, remaining));
+            TotalAllocated::<T>::mutate(|total| *total = total.saturating_sub(amount));
+            ClaimedLeaves::<T>::insert(root, leaf, ());
+
+            Self::complete_claim(&claimant, amount, now, None);
+            Ok(())
+        }
+
+        /// Set (or clear) the key that signs off-chain reward vouchers.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `ParamsOrigin`.
+        /// * `signer` - The sr25519 public key, or `None` to disable vouchers.
+        #[pallet::weight(T::WeightInfo::set_claim_signer())]
+        pub fn set_claim_signer(
+            origin: OriginFor<T>,
+            signer: Option<sr25519::Public>,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let changed_by = Self::acting_account(&origin);
+            Self::ensure_params_origin(origin)?;
+
+            let old = Self::claim_signer();
+            match signer {
+                Some(key) => ClaimSigner::<T>::put(key),
+                None => ClaimSigner::<T>::kill(),
+            }
+
+            // Emit event
+            Self::deposit_event(Event::ClaimSignerUpdated { old, new: signer, changed_by });
+
+            Ok(())
+        }
+
+        /// Redeem a voucher signed by `ClaimSigner` over `voucher_payload`, paying
+        /// `amount` from the pool. Each nonce can only be used once.
+        ///
+        /// # Arguments
+        /// * `origin` - The signed account named in the voucher.
+        /// * `amount` - The amount named in the voucher.
+        /// * `nonce` - The voucher nonce.
+        /// * `signature` - The sr25519 signature of `ClaimSigner`.
+        #[pallet::weight(T::WeightInfo::claim_with_voucher())]
+        pub fn claim_with_voucher(
+            origin: OriginFor<T>,
+            amount: BalanceOf<T>,
+            nonce: u64,
+            signature: sr25519::Signature,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let claimant = ensure_signed(origin)?;
+
+            ensure!(!amount.is_zero(), Error::<T>::InvalidClaimAmount);
+
+            let signer = Self::claim_signer().ok_or(Error::<T>::NoClaimSigner)?;
+            ensure!(!UsedNonces::<T>::contains_key(nonce), Error::<T>::NonceAlreadyUsed);
+            let payload = Self::voucher_payload(&claimant, amount, nonce);
+            ensure!(
+                sp_io::crypto::sr25519_verify(&signature, &payload, &signer),
+                Error::<T>::InvalidVoucherSignature
+            );
+
+            let now = frame_system::Pallet::<T>::block_number();
+            Self::ensure_can_claim(&claimant, amount, now)?;
+
+            // Check if the pool has enough funds
+            let new_pool = Self::reward_pool()
+                .checked_sub(&amount)
+                .ok_or(Error::<T>::InsufficientRewardPool)?;
+            ensure!(new_pool >= Self::min_pool_reserve(), Error::<T>::WouldBreachReserve);
+
+            Self::pay_from_pot(&Self::payout_account(&claimant), amount)?;
+
+            // Update the storage
+            RewardPool::<T>::put(new_pool);
+            UsedNonces::<T>::insert(nonce, ());
+
+            Self::complete_claim(&claimant, amount, now, None);
+            Ok(())
+        }
+
+        /// Add `who` to the claimer whitelist.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `ParamsOrigin`.
+        /// * `who` - The account to add.
+        #[pallet::weight(T::WeightInfo::add_claimer())]
+        pub fn add_claimer(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            Self::ensure_params_origin(origin)?;
+
+            ensure!(!Claimers::<T>::contains_key(&who), Error::<T>::AlreadyClaimer);
+            ensure!(Claimers::<T>::count() < T::MaxClaimers::get(), Error::<T>::TooManyClaimers);
+
+            Claimers::<T>::insert(&who, ());
+
+            // Emit event
+            Self::deposit_event(Event::ClaimerAdded(who));
+
+            Ok(())
+        }
+
+        /// Remove `who` from the claimer whitelist.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `ParamsOrigin`.
+        /// * `who` - The account to remove.
+        #[pallet::weight(T::WeightInfo::remove_claimer())]
+        pub fn remove_claimer(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            Self::ensure_params_origin(origin)?;
+
+            ensure!(Claimers::<T>::contains_key(&who), Error::<T>::NotClaimer);
+
+            Claimers::<T>::remove(&who);
+
+            // Emit event
+            Self::deposit_event(Event::ClaimerRemoved(who));
+
+            Ok(())
+        }
+
+        /// Add many accounts to the claimer whitelist at once. Accounts already on it
+        /// are skipped; if the rest do not fit under `MaxClaimers`, nothing is added.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `ParamsOrigin`.
+        /// * `who` - The accounts to add.
+        #[pallet::weight(T::WeightInfo::add_claimers(who.len() as u32))]
+        pub fn add_claimers(
+            origin: OriginFor<T>,
+            who: BoundedVec<T::AccountId, T::MaxClaimersPerCall>,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            Self::ensure_params_origin(origin)?;
+
+            let mut added = 0u32;
+            let mut already_present = 0u32;
+            for account in who.into_iter() {
+                if Claimers::<T>::contains_key(&account) {
+                    already_present += 1;
+                    continue;
+                }
+                Claimers::<T>::insert(&account, ());
+                added += 1;
+            }
+
+            // All or nothing: any failure reverts the inserts above
+            ensure!(Claimers::<T>::count() <= T::MaxClaimers::get(), Error::<T>::TooManyClaimers);
+
+            // Emit event
+            Self::deposit_event(Event::ClaimersAdded { added, already_present });
+
+            Ok(())
+        }
+
+        /// Remove many accounts from the claimer whitelist at once. Accounts not on
+        /// it are skipped.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `ParamsOrigin`.
+        /// * `who` - The accounts to remove.
+        #[pallet::weight(T::WeightInfo::remove_claimers(who.len() as u32))]
+        pub fn remove_claimers(
+            origin: OriginFor<T>,
+            who: BoundedVec<T::AccountId, T::MaxClaimersPerCall>,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            Self::ensure_params_origin(origin)?;
+
+            let mut removed = 0u32;
+            let mut not_present = 0u32;
+            for account in who.into_iter() {
+                if !Claimers::<T>::contains_key(&account) {
+                    not_present += 1;
+                    continue;
+                }
+                Claimers::<T>::remove(&account);
+                removed += 1;
+            }
+
+            // Emit event
+            Self::deposit_event(Event::ClaimersRemoved { removed, not_present });
+
+            Ok(())
+        }
+
+        /// Ban `who` from claiming, e.g. after gaming the reward program.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `ParamsOrigin`.
+        /// * `who` - The account to ban.
+        /// * `slash_allocation` - Whether to return `who`'s outstanding allocation to
+        ///   the pool. Otherwise it is kept and becomes claimable again on unban.
+        #[pallet::weight(T::WeightInfo::ban_claimer())]
+        pub fn ban_claimer(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+            slash_allocation: bool,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            Self::ensure_params_origin(origin)?;
+
+            BannedClaimers::<T>::insert(&who, ());
+
+            let slashed = if slash_allocation {
+                let allocation = Self::allocations(&who);
+                Self::do_revoke(&who, allocation);
+                allocation
+            } else {
+                Zero::zero()
+            };
+
+            // Emit event
+            Self::deposit_event(Event::ClaimerBanned { who, slashed });
+
+            Ok(())
+        }
+
+        /// Lift the claiming ban on `who`.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `ParamsOrigin`.
+        /// * `who` - The account to unban.
+        #[pallet::weight(T::WeightInfo::unban_claimer())]
+        pub fn unban_claimer(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            Self::ensure_params_origin(origin)?;
+
+            ensure!(Self::is_banned(&who), Error::<T>::NotBanned);
+            BannedClaimers::<T>::remove(&who);
+
+            // Emit event
+            Self::deposit_event(Event::ClaimerUnbanned(who));
+
+            Ok(())
+        }
+
+        /// Set the minimum total balance an account must hold to claim.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `ParamsOrigin`.
+        /// * `min_balance` - The new threshold. Zero disables the check.
+        #[pallet::weight(T::WeightInfo::set_eligibility_min_balance())]
+        pub fn set_eligibility_min_balance(
+            origin: OriginFor<T>,
+            min_balance: BalanceOf<T>,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let changed_by = Self::acting_account(&origin);
+            Self::ensure_params_origin(origin)?;
+
+            let old = Self::eligibility_min_balance();
+            Self::mutate_params(|p| p.eligibility_min_balance = min_balance)?;
+
+            // Emit event
+            Self::deposit_event(Event::EligibilityMinBalanceUpdated {
+                old,
+                new: min_balance,
+                changed_by,
+            });
+
+            Ok(())
+        }
+
+        /// Pay rewards from the pool directly to many beneficiaries, for programs
+        /// where users never claim themselves. The total is validated up front and
+        /// the call is all-or-nothing. Each payout must be within `DirectPayoutLimit`.
+        ///
+        /// # Arguments
+        /// * `origin` - An operator, or must satisfy the `ParamsOrigin`.
+        /// * `payouts` - The `(beneficiary, amount)` pairs to pay.
+        #[pallet::weight(T::WeightInfo::force_payout_many(payouts.len() as u32))]
+        pub fn force_payout_many(
+            origin: OriginFor<T>,
+            payouts: BoundedVec<(T::AccountId, BalanceOf<T>), T::MaxPayoutsPerCall>,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            Self::ensure_operator(origin)?;
+
+            // Validate the whole batch before touching storage
+            let limit = T::DirectPayoutLimit::get();
+            let mut total: BalanceOf<T> = Zero::zero();
+            for (_, amount) in payouts.iter() {
+                ensure!(!amount.is_zero(), Error::<T>::InvalidClaimAmount);
+                ensure!(
+                    limit.is_zero() || *amount <= limit,
+                    Error::<T>::ExceedsDirectPayoutLimit
+                );
+                total = total.checked_add(amount).ok_or(ArithmeticError::Overflow)?;
+            }
+            let new_pool = Self::reward_pool()
+                .checked_sub(&total)
+                .ok_or(Error::<T>::InsufficientRewardPool)?;
+
+            // Pay everyone; any failure reverts the whole call
+            let count = payouts.len() as u32;
+            for (who, amount) in payouts.into_iter() {
+                Self::pay_from_pot(&Self::payout_account(&who), amount)?;
+                Self::record_payout(&who, amount, None);
+            }
+
+            // Update the pool
+            RewardPool::<T>::put(new_pool);
+            Self::check_pool_level();
+
+            // Emit event
+            Self::deposit_event(Event::PayoutsCompleted { count, total });
+
+            Ok(())
+        }
+
+        /// Return the expired allocations of `accounts` to the pool. Anyone can call
+        /// this; accounts without an expired allocation are skipped.
+        ///
+        /// # Arguments
+        /// * `origin` - Any signed account.
+        /// * `accounts` - The accounts to sweep.
+        #[pallet::weight(T::WeightInfo::sweep_expired(accounts.len() as u32))]
+        pub fn sweep_expired(
+            origin: OriginFor<T>,
+            accounts: BoundedVec<T::AccountId, T::MaxSweepPerCall>,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            ensure_signed(origin)?;
+
+            let now = frame_system::Pallet::<T>::block_number();
+            for who in accounts.into_iter() {
+                if !Self::allocation_expired(&who, now) {
+                    continue;
+                }
+
+                let amount = Self::allocations(&who);
+                Self::do_revoke(&who, amount);
+
+                // Emit event
+                Self::deposit_event(Event::AllocationExpiredSwept { who, amount });
+            }
+
+            Ok(())
+        }
+
+        /// Remove `who`'s processed claim keys that are past `ClaimKeyRetention`.
+        /// Anyone can call this; keys still within the window are kept.
+        ///
+        /// # Arguments
+        /// * `origin` - Any signed account.
+        /// * `who` - The account whose keys are pruned.
+        /// * `keys` - The keys to prune.
+        #[pallet::weight(T::WeightInfo::prune_claim_keys(keys.len() as u32))]
+        pub fn prune_claim_keys(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+            keys: BoundedVec<ClaimKey, T::MaxSweepPerCall>,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            ensure_signed(origin)?;
+
+            for key in keys.iter() {
+                if !Self::claim_key_active(&who, key) {
+                    ProcessedClaimKeys::<T>::remove(&who, key);
+                }
+            }
+
+            Ok(())
+        }
+
+        /// Set (or clear) the account allowed to claim on the caller's behalf, e.g. a
+        /// hot operational key. Payouts still go to the caller.
+        ///
+        /// # Arguments
+        /// * `origin` - The principal.
+        /// * `delegate` - The delegate, or `None` to revoke delegation immediately.
+        #[pallet::weight(T::WeightInfo::set_claim_delegate())]
+        pub fn set_claim_delegate(
+            origin: OriginFor<T>,
+            delegate: Option<T::AccountId>,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let principal = ensure_signed(origin)?;
+
+            let old = Self::claim_delegate(&principal);
+            match &delegate {
+                Some(delegate) => ClaimDelegate::<T>::insert(&principal, delegate),
+                None => ClaimDelegate::<T>::remove(&principal),
+            }
+
+            // Emit event
+            let changed_by = Some(principal.clone());
+            Self::deposit_event(Event::ClaimDelegateUpdated {
+                principal,
+                old,
+                new: delegate,
+                changed_by,
+            });
+
+            Ok(())
+        }
+
+        /// Claim `amount` on behalf of `principal`, as its delegate. The reward is
+        /// paid to the principal (or its payout destination), never to the delegate.
+        ///
+        /// # Arguments
+        /// * `origin` - The principal's claim delegate.
+        /// * `principal` - The account whose reward is claimed.
+        /// * `amount` - The amount to claim.
+        #[pallet::weight(T::WeightInfo::claim_as_delegate())]
+        pub fn claim_as_delegate(
+            origin: OriginFor<T>,
+            principal: T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let delegate = ensure_signed(origin)?;
+
+            ensure!(
+                Self::claim_delegate(&principal).as_ref() == Some(&delegate),
+                Error::<T>::NotDelegate
+            );
+            ensure!(!amount.is_zero(), Error::<T>::InvalidClaimAmount);
+
+            Self::do_claim(&principal, amount, None)?;
+
+            // Emit event
+            Self::deposit_event(Event::DelegatedClaim { delegate, principal, amount });
+
+            Ok(())
+        }
+
+        /// Queue a claim of `amount` to be processed in a later `on_initialize`, which
+        /// handles up to `ClaimsPerBlock` requests per block in FIFO order.
+        ///
+        /// # Arguments
+        /// * `origin` - Any signed account that is eligible to claim.
+        /// * `amount` - The amount to claim.
+        #[pallet::weight(T::WeightInfo::request_claim())]
+        pub fn request_claim(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let who = ensure_signed(origin)?;
+
+            ensure!(!amount.is_zero(), Error::<T>::InvalidClaimAmount);
+
+            let id = NextClaimRequestId::<T>::get();
+            PendingClaims::<T>::try_mutate(|queue| {
+                queue
+                    .try_push(PendingClaim { id, who: who.clone(), amount })
+                    .map_err(|_| Error::<T>::QueueFull)
+            })?;
+            NextClaimRequestId::<T>::put(id.wrapping_add(1));
+
+            // Emit event
+            Self::deposit_event(Event::ClaimRequested { id, who, amount });
+
+            Ok(())
+        }
+
+        /// Cancel the caller's queued claim request `id` before it is processed.
+        ///
+        /// # Arguments
+        /// * `origin` - The account that made the request.
+        /// * `id` - The request to cancel.
+        #[pallet::weight(T::WeightInfo::cancel_claim_request())]
+        pub fn cancel_claim_request(origin: OriginFor<T>, id: u32) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let who = ensure_signed(origin)?;
+
+            PendingClaims::<T>::try_mutate(|queue| {
+                let index = queue
+                    .iter()
+                    .position(|request| request.id == id && request.who == who)
+                    .ok_or(Error::<T>::UnknownClaimRequest)?;
+                queue.remove(index);
+                Ok::<_, Error<T>>(())
+            })?;
+
+            // Emit event
+            Self::deposit_event(Event::ClaimRequestCancelled { id, who });
+
+            Ok(())
+        }
+
+        /// Claim `amount` into a balance lock that unlocks linearly at `per_block`.
+        /// The lock sits on the account the reward is paid to; a further vested claim
+        /// adds to the still-locked amount and to the unlock rate.
+        ///
+        /// # Arguments
+        /// * `origin` - The account claiming the reward.
+        /// * `amount` - The amount to claim.
+        /// * `per_block` - The amount that unlocks per block.
+        #[pallet::weight(T::WeightInfo::claim_reward_vested())]
+        pub fn claim_reward_vested(
+            origin: OriginFor<T>,
+            amount: BalanceOf<T>,
+            per_block: BalanceOf<T>,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let who = ensure_signed(origin)?;
+
+            ensure!(!per_block.is_zero(), Error::<T>::ZeroVestingRate);
+
+            Self::do_claim(&who, amount, None)?;
+
+            // Merge the new funds into the destination's existing schedule
+            let dest = Self::payout_account(&who);
+            let now = frame_system::Pallet::<T>::block_number();
+            let (locked, rate) = match Self::vesting(&dest) {
+                Some(info) => (Self::locked_at(&info, now), info.per_block),
+                None => (Zero::zero(), Zero::zero()),
+            };
+            let info = VestingInfo {
+                locked: locked.saturating_add(amount),
+                per_block: rate.saturating_add(per_block),
+                starting_block: now,
+            };
+            T::Currency::set_lock(VESTING_ID, &dest, info.locked, WithdrawReasons::all());
+            Vesting::<T>::insert(&dest, info);
+
+            // Emit event
+            Self::deposit_event(Event::VestedClaim { who: dest, amount, per_block });
+
+            Ok(())
+        }
+
+        /// Shrink the caller's vesting lock to the amount still vesting, removing it
+        /// entirely once fully vested.
+        ///
+        /// # Arguments
+        /// * `origin` - The account holding the vesting lock.
+        #[pallet::weight(T::WeightInfo::vest_rewards())]
+        pub fn vest_rewards(origin: OriginFor<T>) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let who = ensure_signed(origin)?;
+
+            let info = Self::vesting(&who).ok_or(Error::<T>::NotVesting)?;
+            let now = frame_system::Pallet::<T>::block_number();
+            let locked = Self::locked_at(&info, now);
+
+            // Update the lock
+            if locked.is_zero() {
+                T::Currency::remove_lock(VESTING_ID, &who);
+                Vesting::<T>::remove(&who);
+            } else {
+                T::Currency::set_lock(VESTING_ID, &who, locked, WithdrawReasons::all());
+                Vesting::<T>::insert(
+                    &who,
+                    VestingInfo { locked, per_block: info.per_block, starting_block: now },
+                );
+            }
+
+            // Emit event
+            Self::deposit_event(Event::VestingUpdated { who, locked });
+
+            Ok(())
+        }
+
+        /// Set the amount taken from the pool at each era boundary and shared out
+        /// proportionally to the points earned in the ended era.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `ParamsOrigin`.
+        /// * `budget` - The new per-era budget. Zero disables point rewards.
+        #[pallet::weight(T::WeightInfo::set_era_budget())]
+        pub fn set_era_budget(origin: OriginFor<T>, budget: BalanceOf<T>) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let changed_by = Self::acting_account(&origin);
+            Self::ensure_params_origin(origin)?;
+
+            let old = Self::era_budget();
+            Self::mutate_params(|p| p.era_budget = budget)?;
+
+            // Emit event
+            Self::deposit_event(Event::EraBudgetUpdated { old, new: budget, changed_by });
+
+            Ok(())
+        }
+
+        /// Claim the caller's share of a settled era's budget, proportional to the
+        /// points it earned in that era.
+        ///
+        /// # Arguments
+        /// * `origin` - The account that earned the points.
+        /// * `era` - The settled era to claim from.
+        #[pallet::weight(T::WeightInfo::claim_era_share())]
+        pub fn claim_era_share(origin: OriginFor<T>, era: u32) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let who = ensure_signed(origin)?;
+
+            ensure!(!Self::era_expired(era), Error::<T>::EraExpired);
+            let points = Self::era_points(era, &who);
+            ensure!(points > 0, Error::<T>::NoEraPoints);
+            let mut reward = Self::era_reward(era).ok_or(Error::<T>::EraNotSettled)?;
+
+            let amount = Self::era_share(&reward, points).min(reward.remaining);
+            let now = frame_system::Pallet::<T>::block_number();
+            Self::ensure_can_claim(&who, amount, now)?;
+            if !amount.is_zero() {
+                Self::pay_from_pot(&Self::payout_account(&who), amount)?;
+            }
+
+            // Update the storage
+            EraPoints::<T>::remove(era, &who);
+            reward.remaining = reward.remaining.saturating_sub(amount);
+            reward.claimed_points = reward.claimed_points.saturating_add(points.into());
+            TotalAllocated::<T>::mutate(|total| *total = total.saturating_sub(amount));
+            if reward.claimed_points >= reward.total_points {
+                // Whatever rounding left behind goes to the next era, unless it is
+                // below the dust threshold
+                let dust = reward.remaining;
+                EraRewards::<T>::remove(era);
+                if !Self::handle_dust(dust) {
+                    CarriedDust::<T>::mutate(|carried| *carried = carried.saturating_add(dust));
+                    Self::deposit_event(Event::EraDustCarried { era, dust });
+                }
+            } else {
+                EraRewards::<T>::insert(era, reward);
+            }
+            if !amount.is_zero() {
+                Self::complete_claim(&who, amount, now, None);
+            }
+
+            // Emit event
+            Self::deposit_event(Event::EraShareClaimed { era, who, points, amount });
+
+            Ok(())
+        }
+
+        /// Return the unclaimed remainder of an expired era to the pool and remove
+        /// its points, at most `MaxSweepPerCall` accounts per call. Anyone can call
+        /// this; call again while points of the era are left.
+        ///
+        /// # Arguments
+        /// * `origin` - Any signed account.
+        /// * `era` - The era past `EraClaimLifetime` to sweep.
+        #[pallet::weight(T::WeightInfo::sweep_expired_era(T::MaxSweepPerCall::get()))]
+        pub fn sweep_expired_era(origin: OriginFor<T>, era: u32) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            ensure_signed(origin)?;
+
+            ensure!(Self::era_expired(era), Error::<T>::EraNotExpired);
+            let reward = EraRewards::<T>::take(era);
+            let cleared = EraPoints::<T>::clear_prefix(era, T::MaxSweepPerCall::get(), None);
+            ensure!(reward.is_some() || cleared.unique > 0, Error::<T>::EraNotSettled);
+
+            // The remainder is still counted in `TotalAllocated`
+            let amount = reward.map_or_else(Zero::zero, |reward| reward.remaining);
+            if !amount.is_zero() {
+                TotalAllocated::<T>::mutate(|total| *total = total.saturating_sub(amount));
+                RewardPool::<T>::mutate(|pool| *pool = pool.saturating_add(amount));
+                Self::check_pool_level();
+            }
+
+            // Emit event
+            Self::deposit_event(Event::EraSwept { era, amount });
+
+            Ok(())
+        }
+
+        /// Remove the receipts of claims up to and including `up_to_id`, oldest first,
+        /// at most `MaxSweepPerCall` per call.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `ParamsOrigin`.
+        /// * `up_to_id` - The last claim id to prune.
+        #[pallet::weight(T::WeightInfo::prune_receipts(T::MaxSweepPerCall::get()))]
+        pub fn prune_receipts(origin: OriginFor<T>, up_to_id: ClaimId) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            Self::ensure_params_origin(origin)?;
+
+            let first = Self::first_receipt_id();
+            let end = up_to_id
+                .saturating_add(1)
+                .min(Self::next_claim_id())
+                .min(first.saturating_add(T::MaxSweepPerCall::get().into()));
+            for id in first..end {
+                Receipts::<T>::remove(id);
+            }
+            let first_remaining = end.max(first);
+            FirstReceiptId::<T>::put(first_remaining);
+
+            // Emit event
+            Self::deposit_event(Event::ReceiptsPruned { first_remaining });
+
+            Ok(())
+        }
+
+        /// Opt in to (or out of) letting any account trigger `claim_for` on the
+        /// caller's behalf. Contract accounts opt in by dispatching this themselves.
+        ///
+        /// # Arguments
+        /// * `origin` - The beneficiary.
+        /// * `allowed` - Whether third parties may trigger the caller's payouts.
+        #[pallet::weight(T::WeightInfo::set_third_party_claims())]
+        pub fn set_third_party_claims(origin: OriginFor<T>, allowed: bool) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let who = ensure_signed(origin)?;
+
+            if allowed {
+                ThirdPartyClaims::<T>::insert(&who, true);
+            } else {
+                ThirdPartyClaims::<T>::remove(&who);
+            }
+
+            // Emit event
+            Self::deposit_event(Event::ThirdPartyClaimsSet { who, allowed });
+
+            Ok(())
+        }
+
+        /// Pay `beneficiary`'s full allocation to `beneficiary` (or its payout
+        /// destination), e.g. for a contract reward address that cannot sign. The
+        /// beneficiary must have opted in; the caller never receives any funds.
+        ///
+        /// # Arguments
+        /// * `origin` - Any signed account.
+        /// * `beneficiary` - The account whose allocation is paid out.
+        #[pallet::weight(T::WeightInfo::claim_for())]
+        pub fn claim_for(origin: OriginFor<T>, beneficiary: T::AccountId) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let caller = ensure_signed(origin)?;
+
+            ensure!(
+                Self::allows_third_party_claims(&beneficiary),
+                Error::<T>::ThirdPartyClaimsNotAllowed
+            );
+
+            let amount = Self::allocations(&beneficiary);
+            ensure!(!amount.is_zero(), Error::<T>::NothingToClaim);
+
+            Self::do_claim(&beneficiary, amount, None)?;
+
+            // Emit event
+            Self::deposit_event(Event::ClaimedFor { caller, beneficiary, amount });
+
+            Ok(())
+        }
+
+        /// Collect the caller's accrued block rewards in a single transfer to its
+        /// payout account.
+        ///
+        /// # Arguments
+        /// * `origin` - A block author with accrued rewards.
+        #[pallet::weight(T::WeightInfo::claim_block_rewards())]
+        pub fn claim_block_rewards(origin: OriginFor<T>) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let who = ensure_signed(origin)?;
+
+            let amount = Self::author_accrued(&who);
+            ensure!(!amount.is_zero(), Error::<T>::NothingToClaim);
+
+            // The pool was debited at accrual time, so pay straight from the pot
+            Self::pay_from_pot(&Self::block_reward_account(&who), amount)?;
+
+            // Update the storage
+            AuthorAccrued::<T>::remove(&who);
+            TotalAllocated::<T>::mutate(|total| *total = total.saturating_sub(amount));
+            TotalDistributed::<T>::mutate(|total| *total = total.saturating_add(amount));
+            Self::note_distribution();
+            Self::note_claimed_by(&who, amount);
+
+            // Emit event
+            Self::deposit_event(Event::BlockRewardsClaimed(who, amount));
+
+            Ok(())
+        }
+
+        /// Stop paying block rewards to `who` without removing it from the session
+        /// set. Its rewards stay in the pool.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `ParamsOrigin`.
+        /// * `who` - The author to exclude.
+        #[pallet::weight(T::WeightInfo::exclude_author())]
+        pub fn exclude_author(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            Self::ensure_params_origin(origin)?;
+
+            ensure!(!ExcludedAuthors::<T>::contains_key(&who), Error::<T>::AlreadyExcluded);
+            ensure!(
+                ExcludedAuthors::<T>::count() < T::MaxExcludedAuthors::get(),
+                Error::<T>::TooManyExcludedAuthors
+            );
+
+            ExcludedAuthors::<T>::insert(&who, ());
+
+            // Emit event
+            Self::deposit_event(Event::AuthorExcluded(who));
+
+            Ok(())
+        }
+
+        /// Resume paying block rewards to `who`.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `ParamsOrigin`.
+        /// * `who` - The author to include again.
+        #[pallet::weight(T::WeightInfo::include_author())]
+        pub fn include_author(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            Self::ensure_params_origin(origin)?;
+
+            ensure!(ExcludedAuthors::<T>::contains_key(&who), Error::<T>::NotExcluded);
+
+            ExcludedAuthors::<T>::remove(&who);
+
+            // Emit event
+            Self::deposit_event(Event::AuthorIncluded(who));
+
+            Ok(())
+        }
+
+        /// Override the per-block reward, or fall back to the `RewardPerBlock`
+        /// constant with `None`. `Some(0)` stops emission.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `ParamsOrigin`.
+        /// * `reward` - The new per-block reward, or `None` to clear the override.
+        #[pallet::weight(T::WeightInfo::set_reward_per_block())]
+        pub fn set_reward_per_block(
+            origin: OriginFor<T>,
+            reward: Option<BalanceOf<T>>,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let changed_by = Self::acting_account(&origin);
+            Self::ensure_params_origin(origin)?;
+
+            let old = Self::reward_per_block_override();
+            Self::mutate_params(|p| p.reward_per_block = reward)?;
+
+            // Emit event
+            Self::deposit_event(Event::RewardPerBlockUpdated { old, new: reward, changed_by });
+
+            Ok(())
+        }
+
+        /// Halt block reward emission without touching the pool or parameters.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `ParamsOrigin`.
+        #[pallet::weight(T::WeightInfo::pause_block_rewards())]
+        pub fn pause_block_rewards(origin: OriginFor<T>) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            Self::ensure_params_origin(origin)?;
+
+            BlockRewardsPaused::<T>::put(true);
+
+            // Emit event
+            Self::deposit_event(Event::BlockRewardsPaused);
+
+            Ok(())
+        }
+
+        /// Resume block reward emission.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `ParamsOrigin`.
+        #[pallet::weight(T::WeightInfo::resume_block_rewards())]
+        pub fn resume_block_rewards(origin: OriginFor<T>) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            Self::ensure_params_origin(origin)?;
+
+            BlockRewardsPaused::<T>::kill();
+
+            // Emit event
+            Self::deposit_event(Event::BlockRewardsResumed);
+
+            Ok(())
+        }
+
+        /// Set (or clear with `None`) the account receiving the caller's block
+        /// rewards. Takes precedence over `set_payout_destination` for block rewards.
+        ///
+        /// # Arguments
+        /// * `origin` - The block author.
+        /// * `dest` - The stash to pay, or `None` to pay the author directly.
+        #[pallet::weight(T::WeightInfo::set_author_payout_account())]
+        pub fn set_author_payout_account(
+            origin: OriginFor<T>,
+            dest: Option<T::AccountId>,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let author = ensure_signed(origin)?;
+
+            let old = Self::author_payout_destination(&author);
+            match &dest {
+                Some(dest) => AuthorPayoutAccount::<T>::insert(&author, dest),
+                None => AuthorPayoutAccount::<T>::remove(&author),
+            }
+
+            // Emit event
+            let changed_by = Some(author.clone());
+            Self::deposit_event(Event::AuthorPayoutAccountUpdated {
+                author,
+                old,
+                new: dest,
+                changed_by,
+            });
+
+            Ok(())
+        }
+
+        /// Propose `new` as the admin. The change only takes effect once `new` calls
+        /// `accept_admin`, so a mistyped address cannot lock management out.
+        ///
+        /// # Arguments
+        /// * `origin` - Root or the current admin.
+        /// * `new` - The proposed admin.
+        #[pallet::weight(T::WeightInfo::set_admin())]
+        pub fn set_admin(origin: OriginFor<T>, new: T::AccountId) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            Self::ensure_params_not_frozen()?;
+            let changed_by = Self::acting_account(&origin);
+            if ensure_root(origin.clone()).is_err() {
+                let who = ensure_signed(origin)?;
+                ensure!(Self::admin() == Some(who), DispatchError::BadOrigin);
+            }
+
+            let old = Self::pending_admin();
+            PendingAdmin::<T>::put(&new);
+
+            // Emit event
+            Self::deposit_event(Event::PendingAdminUpdated { old, new, changed_by });
+
+            Ok(())
+        }
+
+        /// Accept a pending admin proposal and become the admin.
+        ///
+        /// # Arguments
+        /// * `origin` - The pending admin.
+        #[pallet::weight(T::WeightInfo::accept_admin())]
+        pub fn accept_admin(origin: OriginFor<T>) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            Self::ensure_params_not_frozen()?;
+            let who = ensure_signed(origin)?;
+
+            ensure!(Self::pending_admin() == Some(who.clone()), Error::<T>::NotPendingAdmin);
+
+            // Update the storage
+            PendingAdmin::<T>::kill();
+            let old = Admin::<T>::get();
+            Admin::<T>::put(&who);
+
+            // Emit event
+            let changed_by = Some(who.clone());
+            Self::deposit_event(Event::AdminUpdated { old, new: who, changed_by });
+
+            Ok(())
+        }
+
+        /// Apply several parameter changes at once. Fields left as `None` keep their
+        /// current value; the result is validated as a whole and either fully
+        /// applied or not at all.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `ParamsOrigin`.
+        /// * `new` - The fields to change.
+        #[pallet::weight(T::WeightInfo::update_params())]
+        pub fn update_params(
+            origin: OriginFor<T>,
+            new: PartialParamsOf<T>,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let changed_by = Self::acting_account(&origin);
+            Self::ensure_params_origin(origin)?;
+
+            let old = Self::params();
+            let params = Self::mutate_params(|p| new.apply_to(p))?;
+
+            // Emit event
+            Self::deposit_event(Event::ParamsUpdated { old, new: params, changed_by });
+
+            Ok(())
+        }
+
+        /// Freeze the whole pallet: every call fails with `PalletPaused` and the
+        /// hooks neither emit nor move funds until `unpause`.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `PauseOrigin`.
+        #[pallet::weight(T::WeightInfo::pause())]
+        pub fn pause(origin: OriginFor<T>) -> DispatchResult {
+            T::PauseOrigin::ensure_origin(origin)?;
+
+            Paused::<T>::put(true);
+
+            // Emit event
+            Self::deposit_event(Event::Paused);
+
+            Ok(())
+        }
+
+        /// Lift a `pause`.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `PauseOrigin`.
+        #[pallet::weight(T::WeightInfo::unpause())]
+        pub fn unpause(origin: OriginFor<T>) -> DispatchResult {
+            T::PauseOrigin::ensure_origin(origin)?;
+
+            Paused::<T>::kill();
+
+            // Emit event
+            Self::deposit_event(Event::Unpaused);
+
+            Ok(())
+        }
+
+        /// Schedule a parameter change to take effect at `effective_at`. Changes due
+        /// in the same block apply in scheduling order, so the last one wins.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `ParamsOrigin`.
+        /// * `change` - The fields to change.
+        /// * `effective_at` - The block at which the change applies. Must be in the future.
+        #[pallet::weight(T::WeightInfo::schedule_param_change())]
+        pub fn schedule_param_change(
+            origin: OriginFor<T>,
+            change: PartialParamsOf<T>,
+            effective_at: T::BlockNumber,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            Self::ensure_params_origin(origin)?;
+            Self::ensure_params_not_frozen()?;
+
+            ensure!(
+                effective_at > frame_system::Pallet::<T>::block_number(),
+                Error::<T>::ParamChangeInPast
+            );
+
+            let id = NextParamChangeId::<T>::get();
+            ScheduledParamChanges::<T>::try_mutate(|changes| {
+                changes
+                    .try_push(ScheduledParamChange { id, change, effective_at })
+                    .map_err(|_| Error::<T>::TooManyParamChanges)
+            })?;
+            NextParamChangeId::<T>::put(id.wrapping_add(1));
+
+            // Emit event
+            Self::deposit_event(Event::ParamChangeScheduled { id, effective_at });
+
+            Ok(())
+        }
+
+        /// Cancel the scheduled parameter change `id` before it takes effect.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `ParamsOrigin`.
+        /// * `id` - The change to cancel.
+        #[pallet::weight(T::WeightInfo::cancel_param_change())]
+        pub fn cancel_param_change(origin: OriginFor<T>, id: u32) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            Self::ensure_params_origin(origin)?;
+
+            ScheduledParamChanges::<T>::try_mutate(|changes| {
+                let index = changes
+                    .iter()
+                    .position(|c| c.id == id)
+                    .ok_or(Error::<T>::UnknownParamChange)?;
+                changes.remove(index);
+                Ok::<_, DispatchError>(())
+            })?;
+
+            // Emit event
+            Self::deposit_event(Event::ParamChangeCancelled { id });
+
+            Ok(())
+        }
+
+        /// Let `who` top up the pool by up to `allowance` per era without satisfying
+        /// `FundingOrigin`. A zero `allowance` revokes it.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `ParamsOrigin`.
+        /// * `who` - The operator account.
+        /// * `allowance` - The per-era top-up allowance.
+        #[pallet::weight(T::WeightInfo::set_top_up_allowance())]
+        pub fn set_top_up_allowance(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+            allowance: BalanceOf<T>,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let changed_by = Self::acting_account(&origin);
+            Self::ensure_params_origin(origin)?;
+
+            let old = Self::top_up_allowance(&who).unwrap_or_else(Zero::zero);
+            if allowance.is_zero() {
+                TopUpAllowance::<T>::remove(&who);
+            } else {
+                ensure!(
+                    TopUpAllowance::<T>::contains_key(&who) ||
+                        TopUpAllowance::<T>::count() < T::MaxTopUpOperators::get(),
+                    Error::<T>::TooManyTopUpOperators
+                );
+                TopUpAllowance::<T>::insert(&who, allowance);
+            }
+
+            // Emit event
+            Self::deposit_event(Event::TopUpAllowanceUpdated {
+                who,
+                old,
+                new: allowance,
+                changed_by,
+            });
+
+            Ok(())
+        }
+
+        /// Set the minimum fee a transaction must have paid for `accrue_for_fee` to
+        /// attribute rewards to it, so rewards cannot be farmed with free calls.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `ParamsOrigin`.
+        /// * `fee` - The new minimum. Zero disables the check.
+        #[pallet::weight(T::WeightInfo::set_min_consensus_fee())]
+        pub fn set_min_consensus_fee(origin: OriginFor<T>, fee: BalanceOf<T>) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let changed_by = Self::acting_account(&origin);
+            Self::ensure_params_origin(origin)?;
+
+            let old = Self::min_consensus_fee();
+            Self::mutate_params(|p| p.min_consensus_fee = fee)?;
+
+            // Emit event
+            Self::deposit_event(Event::MinConsensusFeeUpdated { old, new: fee, changed_by });
+
+            Ok(())
+        }
+
+        /// Set how captured fees are divided between the reward pool, the block
+        /// author and burning. The remainder goes to the `TreasuryAccount`.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `ParamsOrigin`.
+        /// * `split` - The new split. Its parts must add up to at most 100%.
+        #[pallet::weight(T::WeightInfo::set_fee_split())]
+        pub fn set_fee_split(origin: OriginFor<T>, split: FeeSplit) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let changed_by = Self::acting_account(&origin);
+            Self::ensure_params_origin(origin)?;
+
+            let old = Self::params().fee_split;
+            Self::mutate_params(|p| p.fee_split = split)?;
+
+            // Emit event
+            Self::deposit_event(Event::FeeSplitUpdated { old, new: split, changed_by });
+
+            Ok(())
+        }
+
+        /// Propose paying `amount` from the pool to `beneficiary`. The payout is made
+        /// once `ParamsOrigin` approves it, for grants too large to pay directly.
+        ///
+        /// # Arguments
+        /// * `origin` - A signed operator or manager.
+        /// * `beneficiary` - The account to pay.
+        /// * `amount` - The amount to pay.
+        #[pallet::weight(T::WeightInfo::propose_payout())]
+        pub fn propose_payout(
+            origin: OriginFor<T>,
+            beneficiary: T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            Self::ensure_operator(origin.clone())?;
+            let proposer = ensure_signed(origin)?;
+
+            ensure!(!amount.is_zero(), Error::<T>::InvalidClaimAmount);
+            ensure!(
+                PayoutProposals::<T>::count() < T::MaxPayoutProposals::get(),
+                Error::<T>::TooManyPayoutProposals
+            );
+
+            let id = NextPayoutProposalId::<T>::get();
+            let expires_at = frame_system::Pallet::<T>::block_number()
+                .saturating_add(T::PayoutProposalExpiry::get());
+            PayoutProposals::<T>::insert(
+                id,
+                PayoutProposal {
+                    proposer: proposer.clone(),
+                    beneficiary: beneficiary.clone(),
+                    amount,
+                    expires_at,
+                },
+            );
+            NextPayoutProposalId::<T>::put(id.wrapping_add(1));
+
+            // Emit event
+            Self::deposit_event(Event::PayoutProposed { id, proposer, beneficiary, amount });
+
+            Ok(())
+        }
+
+        /// Approve payout proposal `id` and pay it from the pool.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `ParamsOrigin`.
+        /// * `id` - The proposal to approve.
+        #[pallet::weight(T::WeightInfo::approve_payout())]
+        pub fn approve_payout(origin: OriginFor<T>, id: u32) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            Self::ensure_params_origin(origin)?;
+
+            let proposal = PayoutProposals::<T>::take(id).ok_or(Error::<T>::UnknownPayoutProposal)?;
+            ensure!(
+                frame_system::Pallet::<T>::block_number() < proposal.expires_at,
+                Error::<T>::PayoutProposalExpired
+            );
+            let new_pool = Self::reward_pool()
+                .checked_sub(&proposal.amount)
+                .ok_or(Error::<T>::InsufficientRewardPool)?;
+
+            Self::pay_from_pot(&Self::payout_account(&proposal.beneficiary), proposal.amount)?;
+            Self::record_payout(&proposal.beneficiary, proposal.amount, None);
+
+            // Update the pool
+            RewardPool::<T>::put(new_pool);
+            Self::check_pool_level();
+
+            // Emit event
+            Self::deposit_event(Event::PayoutApproved {
+                id,
+                beneficiary: proposal.beneficiary,
+                amount: proposal.amount,
+            });
+
+            Ok(())
+        }
+
+        /// Reject payout proposal `id`.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `ParamsOrigin`.
+        /// * `id` - The proposal to reject.
+        #[pallet::weight(T::WeightInfo::reject_payout())]
+        pub fn reject_payout(origin: OriginFor<T>, id: u32) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            Self::ensure_params_origin(origin)?;
+
+            PayoutProposals::<T>::take(id).ok_or(Error::<T>::UnknownPayoutProposal)?;
+
+            // Emit event
+            Self::deposit_event(Event::PayoutRejected { id });
+
+            Ok(())
+        }
+
+        /// Remove payout proposal `id` once it has expired. Anyone can call this.
+        ///
+        /// # Arguments
+        /// * `origin` - Any signed account.
+        /// * `id` - The expired proposal.
+        #[pallet::weight(T::WeightInfo::remove_expired_payout())]
+        pub fn remove_expired_payout(origin: OriginFor<T>, id: u32) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            ensure_signed(origin)?;
+
+            let proposal = Self::payout_proposals(id).ok_or(Error::<T>::UnknownPayoutProposal)?;
+            ensure!(
+                frame_system::Pallet::<T>::block_number() >= proposal.expires_at,
+                Error::<T>::PayoutProposalNotExpired
+            );
+            PayoutProposals::<T>::remove(id);
+
+            // Emit event
+            Self::deposit_event(Event::PayoutProposalExpired { id });
+
+            Ok(())
+        }
+
+        /// Add `who` to the operators.
+        ///
+        /// # Arguments
+        /// * `origin` - Root or the admin.
+        /// * `who` - The new operator.
+        #[pallet::weight(T::WeightInfo::add_operator())]
+        pub fn add_operator(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            EnsureRewardManager::<T>::ensure_origin(origin)?;
+
+            ensure!(!Self::is_operator(&who), Error::<T>::AlreadyOperator);
+            ensure!(
+                Operators::<T>::count() < T::MaxOperators::get(),
+                Error::<T>::TooManyOperators
+            );
+
+            Operators::<T>::insert(&who, ());
+
+            // Emit event
+            Self::deposit_event(Event::OperatorAdded { who });
+
+            Ok(())
+        }
+
+        /// Remove `who` from the operators.
+        ///
+        /// # Arguments
+        /// * `origin` - Root or the admin.
+        /// * `who` - The operator to remove.
+        #[pallet::weight(T::WeightInfo::remove_operator())]
+        pub fn remove_operator(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            EnsureRewardManager::<T>::ensure_origin(origin)?;
+
+            ensure!(Self::is_operator(&who), Error::<T>::NotOperator);
+
+            Operators::<T>::remove(&who);
+
+            // Emit event
+            Self::deposit_event(Event::OperatorRemoved { who });
+
+            Ok(())
+        }
+
+        /// Set up a pallet added by a runtime upgrade, in place of the genesis
+        /// config: fund the pool from `source` and write the parameters. Can only
+        /// run once, and not at all on chains that set the pallet up at genesis.
+        ///
+        /// # Arguments
+        /// * `origin` - Must be Root.
+        /// * `source` - The account funding the pool.
+        /// * `initial_pool` - The amount moved from `source` into the pool.
+        /// * `params` - The initial parameters.
+        #[pallet::weight(T::WeightInfo::initialize())]
+        pub fn initialize(
+            origin: OriginFor<T>,
+            source: T::AccountId,
+            initial_pool: BalanceOf<T>,
+            params: ParamsOf<T>,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            ensure_root(origin)?;
+
+            ensure!(!Self::initialized(), Error::<T>::AlreadyInitialized);
+            Self::ensure_params_not_frozen()?;
+            Self::ensure_valid_params(&params)?;
+
+            let new_pool = Self::reward_pool()
+                .checked_add(&initial_pool)
+                .ok_or(ArithmeticError::Overflow)?;
+            Self::ensure_within_cap(new_pool)?;
+
+            // Move the funds into the pot
+            if !initial_pool.is_zero() {
+                T::Currency::transfer(
+                    &source,
+                    &Self::account_id(),
+                    initial_pool,
+                    ExistenceRequirement::AllowDeath,
+                )?;
+            }
+
+            // Update the storage
+            RewardPool::<T>::put(new_pool);
+            Self::note_inflow(initial_pool);
+            Params::<T>::put(params);
+            Initialized::<T>::put(true);
+
+            // Emit event
+            Self::deposit_event(Event::Initialized { source, initial_pool, params });
+
+            Ok(())
+        }
+
+        /// Freeze the parameters and the admin for good. Funding and claims keep
+        /// working, but nothing, not even root, can change the reward economics or
+        /// undo the freeze.
+        ///
+        /// # Arguments
+        /// * `origin` - Must be Root.
+        #[pallet::weight(T::WeightInfo::freeze_params())]
+        pub fn freeze_params(origin: OriginFor<T>) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            ensure_root(origin)?;
+
+            Self::ensure_params_not_frozen()?;
+            ParamsFrozen::<T>::put(true);
+
+            // Emit event
+            Self::deposit_event(Event::ParamsFrozen);
+
+            Ok(())
+        }
+
+        /// Set the minimum number of blocks between two claims by the same account.
+        /// Applies from the next claim, including to accounts already cooling down.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `ParamsOrigin`.
+        /// * `cooldown` - The new cooldown. Zero disables it.
+        #[pallet::weight(T::WeightInfo::set_claim_cooldown())]
+        pub fn set_claim_cooldown(
+            origin: OriginFor<T>,
+            cooldown: T::BlockNumber,
+        ) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let changed_by = Self::acting_account(&origin);
+            Self::ensure_params_origin(origin)?;
+
+            let old = Self::params().claim_cooldown;
+            Self::mutate_params(|p| p.claim_cooldown = cooldown)?;
+
+            // Emit event
+            Self::deposit_event(Event::ClaimCooldownUpdated { old, new: cooldown, changed_by });
+
+            Ok(())
+        }
+
+        /// Set the smallest amount a single claim may pay out, unless it empties the
+        /// claimant's allocation.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `ParamsOrigin`.
+        /// * `amount` - The new minimum.
+        #[pallet::weight(T::WeightInfo::set_min_claim_amount())]
+        pub fn set_min_claim_amount(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let changed_by = Self::acting_account(&origin);
+            Self::ensure_params_origin(origin)?;
+
+            let old = Self::params().min_claim_amount;
+            Self::mutate_params(|p| p.min_claim_amount = amount)?;
+
+            // Emit event
+            Self::deposit_event(Event::MinClaimAmountUpdated { old, new: amount, changed_by });
+
+            Ok(())
+        }
+
+        /// Set the threshold below which residues, such as what a partial claim
+        /// leaves of an allocation, are folded back into the reward pool.
+        ///
+        /// # Arguments
+        /// * `origin` - Must satisfy the `ParamsOrigin`.
+        /// * `threshold` - The new threshold. Zero disables folding.
+        #[pallet::weight(T::WeightInfo::set_dust_threshold())]
+        pub fn set_dust_threshold(origin: OriginFor<T>, threshold: BalanceOf<T>) -> DispatchResult {
+            Self::ensure_not_paused()?;
+            let changed_by = Self::acting_account(&origin);
+            Self::ensure_params_origin(origin)?;
+
+            let old = Self::params().dust_threshold;
+            Self::mutate_params(|p| p.dust_threshold = threshold)?;
+
+            // Emit event
+            Self::deposit_event(Event::DustThresholdUpdated { old, new: threshold, changed_by });
+
+            Ok(())
+        }
+    }
+
+    // ---------------------------------------------
+    //  Helper Functions
+    // ---------------------------------------------
+
+    impl<T: Config> Pallet<T> {
+        /// The account ID of the reward pot, derived from `T::PalletId`.
+        ///
+        /// This actually does computation. If you need to keep using it, make
+        /// sure you cache the value and only call this once.
+        pub fn account_id() -> T::AccountId {
+            T::PalletId::get().into_account_truncating()
+        }
+
+        /// The live free balance of the pot account backing the reward pool.
+        pub fn pot() -> BalanceOf<T> {
+            T::Currency::free_balance(&Self::account_id())
+        }
+
+        /// Record `amount` of new funds entering the pool in `TotalToppedUp`.
+        pub(crate) fn note_inflow(amount: BalanceOf<T>) {
+            TotalToppedUp::<T>::mutate(|total| *total = total.saturating_add(amount));
+            Self::check_pool_level();
+        }
+
+        /// Record `amount` of funds leaving the pool without being distributed as a
+        /// reward (e.g. a withdrawal) in `TotalToppedUp`.
+        pub(crate) fn note_outflow(amount: BalanceOf<T>) {
+            TotalToppedUp::<T>::mutate(|total| *total = total.saturating_sub(amount));
+            Self::check_pool_level();
+        }
+
+        /// Whether `TotalToppedUp == RewardPool + TotalAllocated + TotalDistributed`
+        /// currently holds.
+        pub fn accounting_consistent() -> bool {
+            Self::reward_pool()
+                .checked_add(&Self::total_allocated())
+                .and_then(|sum| sum.checked_add(&Self::total_distributed()))
+                .map_or(false, |sum| sum == Self::total_topped_up())
+        }
+
+        /// How much more the pool can take before hitting `MaxPoolSize`, or `None`
+        /// if the pool is unlimited.
+        pub fn remaining_capacity() -> Option<BalanceOf<T>> {
+            let max_pool = T::MaxPoolSize::get();
+            if max_pool.is_zero() {
+                None
+            } else {
+                Some(max_pool.saturating_sub(Self::reward_pool()))
+            }
+        }
+
+        /// Ensure `new_pool` does not exceed `MaxPoolSize`.
+        pub(crate) fn ensure_within_cap(new_pool: BalanceOf<T>) -> DispatchResult {
+            let max_pool = T::MaxPoolSize::get();
+            ensure!(max_pool.is_zero() || new_pool <= max_pool, Error::<T>::PoolCapExceeded);
+            Ok(())
+        }
+
+        /// Resolve `credit` into the pot and add it to the reward pool.
+        ///
+        /// Anything above `MaxPoolSize` is handed to `T::OverflowHandler` instead.
+        /// This never fails, so it is safe to call from other pallets' hooks (e.g. to
+        /// redirect slashes or expired deposits). Returns the amount credited.
+        pub fn fund_pool(credit: NegativeImbalanceOf<T>) -> BalanceOf<T> {
+            let credited = Self::absorb_imbalance(credit);
+            if !credited.is_zero() {
+                Self::deposit_event(Event::RewardPoolIncreased(
+                    None,
+                    credited,
+                    Self::reward_pool(),
+                ));
+            }
+            credited
+        }
+
+        /// Resolve an imbalance into the pot and credit the pool, saturating at the
+        /// pool cap. Returns the amount credited; emits no funding event.
+        fn absorb_imbalance(credit: NegativeImbalanceOf<T>) -> BalanceOf<T> {
+            // Only credit up to the pool cap; hand the rest to the overflow handler
+            let credit = match Self::remaining_capacity() {
+                Some(room) if credit.peek() > room => {
+                    let (credit, excess) = credit.split(room);
+                    Self::deposit_event(Event::PoolOverflow(excess.peek()));
+                    T::OverflowHandler::on_unbalanced(excess);
+                    credit
+                },
+                _ => credit,
+            };
+            let numeric_amount = credit.peek();
+            if numeric_amount.is_zero() {
+                return numeric_amount
+            }
+
+            // Resolve the imbalance into the pot account
+            T::Currency::resolve_creating(&Self::account_id(), credit);
+
+            // Credit the pool
+            RewardPool::<T>::mutate(|pool| *pool = pool.saturating_add(numeric_amount));
+            Self::note_inflow(numeric_amount);
+
+            numeric_amount
+        }
+
+        /// Whether `who` ends up at or above the existential deposit after receiving
+        /// `amount`. Existing accounts always survive.
+        pub fn would_survive(who: &T::AccountId, amount: BalanceOf<T>) -> bool {
+            T::Currency::total_balance(who).saturating_add(amount) >=
+                T::Currency::minimum_balance()
+        }
+
+        /// Compare the recorded `RewardPool` (plus outstanding allocations, which the
+        /// pot also backs) against the pot's live free balance.
+        pub fn pool_status() -> PoolStatus<BalanceOf<T>> {
+            let recorded_pool = Self::reward_pool();
+            let pot_free_balance = Self::pot();
+            let backed = recorded_pool.saturating_add(Self::total_allocated());
+            let discrepancy = if pot_free_balance > backed {
+                Discrepancy::Surplus(pot_free_balance - backed)
+            } else if pot_free_balance < backed {
+                Discrepancy::Deficit(backed - pot_free_balance)
+            } else {
+                Discrepancy::None
+            };
+
+            PoolStatus {
+                recorded_pool,
+                pot_free_balance,
+                total_distributed: Self::total_distributed(),
+                discrepancy,
+            }
+        }
+
+        /// The account backing the named sub-pool `pool_id`, a sub-account of the pot.
+        pub fn sub_pool_account(pool_id: &PoolId<T>) -> T::AccountId {
+            T::PalletId::get().into_sub_account_truncating(pool_id)
+        }
+
+        /// Whether `pool_id` addresses the single `RewardPool`.
+        pub fn is_default_pool(pool_id: &PoolId<T>) -> bool {
+            pool_id.as_slice() == DEFAULT_POOL_ID
+        }
+
+        /// The bookkeeping of `pool_id`, with the default pool id read from
+        /// `RewardPool` and `TotalDistributed`.
+        pub fn pool_info(pool_id: &PoolId<T>) -> Option<PoolInfo<BalanceOf<T>>> {
+            if Self::is_default_pool(pool_id) {
+                return Some(PoolInfo {
+                    balance: Self::reward_pool(),
+                    total_distributed: Self::total_distributed(),
+                });
+            }
+            Self::pools(pool_id)
+        }
+
+        /// The block author according to the pre-runtime digests.
+        fn digest_author() -> Option<T::AccountId> {
+            let digest = frame_system::Pallet::<T>::digest();
+            let pre_runtime_digests = digest.logs.iter().filter_map(|d| d.as_pre_runtime());
+            T::FindAuthor::find_author(pre_runtime_digests)
+        }
+
+        /// Distribute the per-block reward to the block author, if configured
+        /// (RewardPerBlock > 0).
+        fn reward_block_author() -> Weight {
+            let noted = NotedAuthor::<T>::take();
+            let noted_weight = T::DbWeight::get().reads_writes(1, 1);
+
+            // If the reward is zero, do nothing.
+            if Self::block_reward().0.is_zero() {
+                return noted_weight.saturating_add(Self::skip_zero_reward());
+            }
+
+            // Prefer the author noted during the block, otherwise read it from the
+            // pre-runtime digests, the way `pallet_authorship` does. Without an
+            // author there is no one to pay.
+            let author = noted.or_else(Self::digest_author);
+            let digest_weight = noted_weight.saturating_add(T::DbWeight::get().reads(1));
+            if let Some(author) = &author {
+                Self::note_era_author(author);
+            }
+            let (weight, rewarded) = match author {
+                // The reward is paid in `on_finalize`, so the block's contents are known
+                _ if !T::RewardEmptyBlocks::get() && Self::is_empty_block() => {
+                    Self::deposit_event(Event::BlockRewardSkipped {
+                        reason: SkipReason::EmptyBlock,
+                    });
+                    (T::DbWeight::get().reads(1), false)
+                },
+                Some(author) if Self::is_excluded_author(&author) => {
+                    Self::deposit_event(Event::BlockRewardWithheld { author });
+                    (T::DbWeight::get().reads(1), false)
+                },
+                // Tallied blocks are rewarded at the end of the era
+                Some(author) if T::PayAuthorsPerEra::get() =>
+                    (Self::note_authored_block(author), true),
+                Some(author) => Self::pay_block_reward(author),
+                None => {
+                    Self::deposit_event(Event::BlockRewardSkipped { reason: SkipReason::NoAuthor });
+                    (Weight::zero(), false)
+                },
+            };
+
+            // Update the emission counters
+            if rewarded {
+                BlocksRewarded::<T>::mutate(|count| *count = count.saturating_add(1));
+                LastRewardedBlock::<T>::put(frame_system::Pallet::<T>::block_number());
+                EraActivity::<T>::mutate(|activity| {
+                    activity.blocks_rewarded = activity.blocks_rewarded.saturating_add(1)
+                });
+            } else {
+                BlocksSkipped::<T>::mutate(|count| *count = count.saturating_add(1));
+                EraActivity::<T>::mutate(|activity| {
+                    activity.blocks_skipped = activity.blocks_skipped.saturating_add(1)
+                });
+            }
+
+            let counter_weight = T::DbWeight::get().reads_writes(5, 5);
+            digest_weight.saturating_add(weight).saturating_add(counter_weight)
+        }
+
+        /// The nominal per-block reward: the storage override if set, otherwise the
+        /// `RewardPerBlock` constant.
+        pub fn reward_per_block() -> BalanceOf<T> {
+            Self::reward_per_block_override().unwrap_or_else(T::RewardPerBlock::get)
+        }
+
+        /// The block reward for the current block and its halving epoch. In fixed
+        /// mode this is `reward_per_block() >> (n / HalvingInterval)`, reaching zero once
+        /// the shift exceeds the balance width, and smoothed to at most
+        /// `pool / SmoothingWindow`; in proportional mode it is `PoolEmissionRate` of
+        /// the pool, rounded down.
+        pub fn block_reward() -> (BalanceOf<T>, u32) {
+            if T::EmissionMode::get() == EmissionMode::Proportional {
+                return (T::PoolEmissionRate::get().mul_floor(Self::reward_pool()), 0);
+            }
+
+            let interval = T::HalvingInterval::get();
+            let (reward, epoch) = if interval.is_zero() {
+                (Self::reward_per_block(), 0)
+            } else {
+                let n = frame_system::Pallet::<T>::block_number();
+                let epoch = (n / interval).saturated_into::<u32>();
+                (Self::reward_per_block().checked_shr(epoch).unwrap_or_else(Zero::zero), epoch)
+            };
+
+            // Spread the pool over the window, so a burst top-up does not instantly
+            // restore full emission
+            let window = T::SmoothingWindow::get().saturated_into::<u32>();
+            if window == 0 {
+                return (reward, epoch);
+            }
+            (reward.min(Self::reward_pool() / window.into()), epoch)
+        }
+
+        /// Emit why the block reward is zero: either no emission is configured, or
+        /// the proportional reward of a tiny pool rounded down to nothing.
+        fn skip_zero_reward() -> Weight {
+            let reason = match T::EmissionMode::get() {
+                EmissionMode::Proportional if T::PoolEmissionRate::get().is_zero() =>
+                    SkipReason::ZeroReward,
+                EmissionMode::Proportional if Self::reward_pool().is_zero() =>
+                    SkipReason::InsufficientPool,
+                EmissionMode::Proportional => SkipReason::RoundedToZero,
+                EmissionMode::Fixed => SkipReason::ZeroReward,
+            };
+            Self::deposit_event(Event::BlockRewardSkipped { reason });
+
+            match T::EmissionMode::get() {
+                EmissionMode::Proportional => T::DbWeight::get().reads(1),
+                EmissionMode::Fixed => Weight::zero(),
+            }
+        }
+
+        /// Pay the per-block reward to `block_author`, either found through
+        /// `FindAuthor` or reported by `pallet_authorship`. Also returns whether any
+        /// reward was paid out.
+        fn pay_block_reward(block_author: T::AccountId) -> (Weight, bool) {
+            let db = T::DbWeight::get();
+            let (mut reward, epoch) = Self::block_reward();
+            // If the reward is zero, do nothing.
+            if reward.is_zero() {
+                return (Self::skip_zero_reward(), false);
+            }
+
+            // Authors at their per-era cap earn nothing more this era
+            let era = Self::current_era();
+            let cap = T::MaxAuthorRewardPerEra::get();
+            let earned = Self::author_era_rewards(era, &block_author);
+            if !cap.is_zero() && earned >= cap {
+                return (db.reads(2), false);
+            }
+
+            // Apply the author's performance bonus on top of the base reward
+            let base = reward;
+            let bonus = T::AuthorBonus::bonus(&block_author);
+            reward = reward.saturating_add(bonus.mul_floor(base));
+
+            let pool = Self::reward_pool();
+
+            // If there's not enough in the pool, either pay out what's left or skip
+            // distributing a block reward, depending on `PayPartialBlockReward`. The
+            // bonus alone never causes a skip, it is only bounded by the pool.
+            if pool < reward {
+                if pool < base && (!T::PayPartialBlockReward::get() || pool.is_zero()) {
+                    Self::deposit_event(Event::BlockRewardSkipped {
+                        reason: SkipReason::InsufficientPool,
+                    });
+                    return (db.reads(1), false);
+                }
+                reward = pool;
+            }
+
+            // Split the reward; the treasury takes the rounding remainder
+            let author_share = T::BlockRewardSplit::get().mul_floor(reward);
+            let treasury_share = reward.saturating_sub(author_share);
+            let dest = Self::block_reward_account(&block_author);
+            let treasury = T::TreasuryAccount::get();
+
+            // In accrual mode the author share is set aside for `claim_block_rewards`
+            let accrue = T::AccrueBlockRewards::get();
+
+            // Don't pay a reward that would be dusted; it stays in the pool.
+            if (!accrue && !author_share.is_zero() && !Self::would_survive(&dest, author_share)) ||
+                (!treasury_share.is_zero() && !Self::would_survive(&treasury, treasury_share))
+            {
+                return (db.reads(4), false);
+            }
+
+            // Transfer reward to block author and treasury from the pot. If the pot
+            // can't cover it, skip the reward rather than touching the bookkeeping.
+            if Self::pot() < reward {
+                return (db.reads(5), false);
+            }
+            if !accrue &&
+                !author_share.is_zero() &&
+                T::Currency::transfer(
+                    &Self::account_id(),
+                    &dest,
+                    author_share,
+                    ExistenceRequirement::KeepAlive,
+                )
+                .is_err()
+            {
+                return (db.reads(5), false);
+            }
+            let treasury_paid = !treasury_share.is_zero() &&
+                T::Currency::transfer(
+                    &Self::account_id(),
+                    &treasury,
+                    treasury_share,
+                    ExistenceRequirement::KeepAlive,
+                )
+                .is_ok();
+            let paid = if treasury_paid { reward } else { author_share };
+            EraActivity::<T>::mutate(|activity| {
+                activity.total_paid = activity.total_paid.saturating_add(paid)
+            });
+
+            // Deduct from the reward pool. `paid` never exceeds the pool, so failing
+            // here means corrupt storage; don't let it brick block production.
+            let new_pool = pool.checked_sub(&paid).unwrap_or_else(|| {
+                frame_support::defensive!("block reward exceeds the reward pool");
+                Zero::zero()
+            });
+            RewardPool::<T>::put(new_pool);
+
+            // Update total distributed; accrued rewards count as allocated until claimed
+            let distributed = if accrue { paid.saturating_sub(author_share) } else { paid };
+            let total_dist = Self::total_distributed();
+            let updated_dist = total_dist.checked_add(&distributed).unwrap_or_else(|| {
+                frame_support::defensive!("TotalDistributed overflow");
+                BalanceOf::<T>::max_value()
+            });
+            TotalDistributed::<T>::put(updated_dist);
+            Self::note_distribution();
+
+            // Track the author's earnings against the per-era cap
+            if !cap.is_zero() && !author_share.is_zero() {
+                let earned = earned.saturating_add(author_share);
+                AuthorEraRewards::<T>::insert(era, &block_author, earned);
+                if earned >= cap {
+                    Self::deposit_event(Event::AuthorRewardCapReached {
+                        author: block_author.clone(),
+                        era,
+                    });
+                }
+            }
+
+            // Credit the author and emit events
+            if accrue && !author_share.is_zero() {
+                AuthorAccrued::<T>::mutate(&block_author, |accrued| {
+                    *accrued = accrued.saturating_add(author_share)
+                });
+                TotalAllocated::<T>::mutate(|total| *total = total.saturating_add(author_share));
+                Self::deposit_event(Event::BlockRewardAccrued(block_author, author_share));
+            } else if !author_share.is_zero() {
+                Self::note_claimed_by(&block_author, author_share);
+                if T::EmitBlockRewardEvents::get() {
+                    Self::deposit_event(Event::BlockRewardDistributed(
+                        block_author,
+                        dest,
+                        author_share,
+                        epoch,
+                        bonus,
+                    ));
+                }
+            }
+            if treasury_paid {
+                Self::deposit_event(Event::TreasuryRewardDistributed(treasury, treasury_share));
+            }
+            if new_pool.is_zero() {
+                Self::deposit_event(Event::RewardPoolDepleted);
+            }
+
+            // Pool, destination, treasury, pot and totals; the two transfers write the
+            // pot and both recipients
+            (db.reads_writes(7, 7), !paid.is_zero())
+        }
+
+        /// Flush rounding dust that settling the era could not hand out (there were
+        /// no points to share it), to `DustDestination` or back into the pool.
+        /// Other remainders (split, capped, smoothed or era author rounding) never
+        /// leave the pool in the first place.
+        fn flush_dust() -> Weight {
+            let db = T::DbWeight::get();
+            let dust = CarriedDust::<T>::take();
+            if dust.is_zero() {
+                return db.reads_writes(1, 1);
+            }
+            if Self::handle_dust(dust) {
+                return db.reads_writes(4, 4);
+            }
+
+            // Dust is counted in `TotalAllocated` until flushed
+            TotalAllocated::<T>::mutate(|total| *total = total.saturating_sub(dust));
+            let forwarded = T::DustDestination::get().map_or(false, |dest| {
+                T::Currency::transfer(
+                    &Self::account_id(),
+                    &dest,
+                    dust,
+                    ExistenceRequirement::KeepAlive,
+                )
+                .is_ok()
+            });
+            if forwarded {
+                Self::note_outflow(dust);
+            } else {
+                RewardPool::<T>::mutate(|pool| *pool = pool.saturating_add(dust));
+                Self::check_pool_level();
+            }
+
+            // Emit event
+            Self::deposit_event(Event::DustFlushed(dust));
+
+            db.reads_writes(4, 5)
+        }
+
+        /// Whether the current block applied no extrinsics besides inherents. Inherents
+        /// are dispatched in the mandatory class, so any normal or operational weight
+        /// means a real extrinsic was included.
+        fn is_empty_block() -> bool {
+            let consumed = frame_system::Pallet::<T>::block_weight();
+            frame_system::Pallet::<T>::extrinsic_count() == 0 ||
+                (consumed.get(DispatchClass::Normal).is_zero() &&
+                    consumed.get(DispatchClass::Operational).is_zero())
+        }
+
+        /// Count `author` towards the current era's unique authors.
+        fn note_era_author(author: &T::AccountId) {
+            let era = Self::current_era();
+            if AuthorLastSeenEra::<T>::get(author) != Some(era) {
+                AuthorLastSeenEra::<T>::insert(author, era);
+                EraActivity::<T>::mutate(|activity| {
+                    activity.unique_authors = activity.unique_authors.saturating_add(1)
+                });
+            }
+        }
+
+        /// Emit the `EraSummary` of the ended `era` and reset the activity counters.
+        fn summarise_era(era: u32) -> Weight {
+            let activity = EraActivity::<T>::take();
+            Self::deposit_event(Event::EraSummary {
+                era,
+                total_paid: activity.total_paid,
+                blocks_rewarded: activity.blocks_rewarded,
+                blocks_skipped: activity.blocks_skipped,
+                unique_authors: activity.unique_authors,
+            });
+            T::DbWeight::get().reads_writes(1, 1)
+        }
+
+        /// Tally a block for `author`, to be paid at the end of the era.
+        fn note_authored_block(author: T::AccountId) -> Weight {
+            let db = T::DbWeight::get();
+            if !BlocksAuthoredInEra::<T>::contains_key(&author) &&
+                BlocksAuthoredInEra::<T>::count() >= T::MaxEraAuthors::get()
+            {
+                return db.reads(2);
+            }
+
+            BlocksAuthoredInEra::<T>::mutate(&author, |blocks| *blocks = blocks.saturating_add(1));
+            db.reads_writes(2, 2)
+        }
+
+        /// Split `RewardPerBlock * EraLength`, capped by the pool, across the authors
+        /// of the ended `era` in proportion to the blocks they authored. Rounding
+        /// dust and shares that would be dusted stay in the pool.
+        fn pay_era_authors(era: u32) -> Weight {
+            let db = T::DbWeight::get();
+            if BlocksAuthoredInEra::<T>::count() == 0 {
+                return db.reads(1);
+            }
+
+            // The tally is bounded by `MaxEraAuthors`, so it is cleared in one go
+            let tally: Vec<_> = BlocksAuthoredInEra::<T>::drain().collect();
+            let blocks: u32 = tally.iter().fold(0u32, |sum, (_, n)| sum.saturating_add(*n));
+            let era_length = T::EraLength::get().saturated_into::<u32>();
+            let pool = Self::reward_pool();
+            let budget = Self::block_reward().0.saturating_mul(era_length.into()).min(pool);
+
+            let mut total: BalanceOf<T> = Zero::zero();
+            if !budget.is_zero() && blocks > 0 {
+                let units: u128 = budget.saturated_into();
+                for (author, authored) in tally.iter() {
+                    let units = units.saturating_mul((*authored).into()) / u128::from(blocks);
+                    let share: BalanceOf<T> = units.saturated_into();
+                    if Self::is_excluded_author(author) {
+                        Self::deposit_event(Event::BlockRewardWithheld { author: author.clone() });
+                        continue;
+                    }
+                    let dest = Self::block_reward_account(author);
+                    if share.is_zero() || Self::pay_from_pot(&dest, share).is_err() {
+                        continue;
+                    }
+                    total = total.saturating_add(share);
+                    Self::note_claimed_by(author, share);
+                }
+            }
+
+            // Update the storage
+            if !total.is_zero() {
+                EraActivity::<T>::mutate(|activity| {
+                    activity.total_paid = activity.total_paid.saturating_add(total)
+                });
+                RewardPool::<T>::put(pool.saturating_sub(total));
+                TotalDistributed::<T>::mutate(|dist| *dist = dist.saturating_add(total));
+                Self::note_distribution();
+            }
+
+            // Emit event
+            let authors = tally.len() as u32;
+            Self::deposit_event(Event::EraRewardsDistributed { era, total, authors, blocks });
+
+            // The tally, plus a payout per author
+            let per_author = db.reads_writes(5, 5).saturating_mul(authors.into());
+            db.reads_writes(4, 4).saturating_add(per_author)
+        }
+
+        /// At each era boundary, mint `PoolInflationPerEra` into the pot and credit
+        /// the pool. This is the one place where minting is intentional. A boundary
+        /// missed while paused is rolled over in the first unpaused block, and the
+        /// next one stays aligned to `EraLength`.
+        fn maybe_inflate_pool(n: T::BlockNumber) -> Weight {
+            let db = T::DbWeight::get();
+            let era_length = T::EraLength::get();
+            if era_length.is_zero() || n.is_zero() {
+                return Weight::zero();
+            }
+
+            let next = Self::next_era_start();
+            let due = if next.is_zero() { (n % era_length).is_zero() } else { n >= next };
+            if !due {
+                return db.reads(1);
+            }
+            NextEraStart::<T>::put(n - n % era_length + era_length);
+
+            // Advance the era counter and settle the era that just ended
+            let era = CurrentEra::<T>::mutate(|era| {
+                *era = era.saturating_add(1);
+                *era
+            });
+            let settle_weight = Self::settle_era(era.saturating_sub(1))
+                .saturating_add(db.reads_writes(1, 1))
+                .saturating_add(Self::pay_era_authors(era.saturating_sub(1)))
+                .saturating_add(Self::flush_dust())
+                .saturating_add(Self::summarise_era(era.saturating_sub(1)));
+
+            // Clear the ended era's per-author earnings, bounded like the author tally
+            let limit = T::MaxEraAuthors::get();
+            let _ = AuthorEraRewards::<T>::clear_prefix(era.saturating_sub(1), limit, None);
+            let settle_weight = settle_weight.saturating_add(db.writes(limit.into()));
+            let settle_weight = settle_weight.saturating_add(Self::reset_top_up_allowances(era));
+
+            // Never mint past the pool cap
+            let inflation = T::PoolInflationPerEra::get();
+            let amount = match Self::remaining_capacity() {
+                Some(room) => inflation.min(room),
+                None => inflation,
+            };
+            if amount.is_zero() {
+                return settle_weight.saturating_add(db.reads_writes(2, 1));
+            }
+
+            // Mint into the pot and credit the pool
+            T::Currency::deposit_creating(&Self::account_id(), amount);
+            RewardPool::<T>::mutate(|pool| *pool = pool.saturating_add(amount));
+            Self::note_inflow(amount);
+
+            // Emit event
+            Self::deposit_event(Event::PoolInflated(era, amount));
+
+            // Era, pool, pot, totals and the low-pool flag
+            settle_weight.saturating_add(db.reads_writes(5, 5))
+        }
+
+        /// The escrow account holding funds of pending drip top-ups.
+        pub fn drip_account() -> T::AccountId {
+            T::PalletId::get().into_sub_account_truncating(b"drip")
+        }
+
+        /// Release the head drip into the pool and rotate it to the back of the
+        /// queue, or drop it once exhausted. Processes at most one drip per block.
+        fn release_drip() -> Weight {
+            let db = T::DbWeight::get();
+            let mut drips = Self::pending_drips();
+            if drips.is_empty() {
+                return db.reads(1);
+            }
+
+            let mut drip = drips.remove(0);
+            let mut amount = drip.per_block.min(drip.remaining);
+            if let Some(room) = Self::remaining_capacity() {
+                amount = amount.min(room);
+            }
+
+            // Move the released amount from escrow into the pot
+            if !amount.is_zero() &&
+                T::Currency::transfer(
+                    &Self::drip_account(),
+                    &Self::account_id(),
+                    amount,
+                    ExistenceRequirement::AllowDeath,
+                )
+                .is_ok()
+            {
+                drip.remaining = drip.remaining.saturating_sub(amount);
+                RewardPool::<T>::mutate(|pool| *pool = pool.saturating_add(amount));
+                Self::note_inflow(amount);
+
+                Self::deposit_event(Event::DripReleased {
+                    id: drip.id,
+                    amount,
+                    remaining: drip.remaining,
+                });
+            }
+
+            // Rotate unfinished drips to the back; finished ones are cleaned up
+            if !drip.remaining.is_zero() {
+                // Cannot fail, we just removed an element.
+                let _ = drips.try_push(drip);
+            }
+            PendingDrips::<T>::put(drips);
+
+            // Drips, escrow, pot, pool and totals
+            db.reads_writes(5, 5)
+        }
+
+        /// Apply the scheduled parameter changes due at `now`, in scheduling order.
+        /// A change that would leave the parameters invalid is dropped.
+        fn apply_param_changes(now: T::BlockNumber) -> Weight {
+            let db = T::DbWeight::get();
+            let mut changes = Self::scheduled_param_changes();
+            if !changes.iter().any(|c| c.effective_at <= now) {
+                return db.reads(1);
+            }
+
+            let mut applied = 0u64;
+            changes.retain(|c| {
+                if c.effective_at > now {
+                    return true;
+                }
+                applied += 1;
+                match Self::mutate_params(|p| c.change.apply_to(p)) {
+                    Ok(params) =>
+                        Self::deposit_event(Event::ParamChangeApplied { id: c.id, params }),
+                    Err(_) => Self::deposit_event(Event::ParamChangeRejected { id: c.id }),
+                }
+                false
+            });
+            ScheduledParamChanges::<T>::put(changes);
+
+            // The queue, plus the parameters per applied change
+            db.reads_writes(1u64.saturating_add(applied), 1u64.saturating_add(applied))
+        }
+
+        /// Count `who` as a claimant if this is their first claim.
+        pub(crate) fn note_claimant(who: &T::AccountId) {
+            if !Claimants::<T>::contains_key(who) {
+                Claimants::<T>::insert(who, ());
+                ClaimantCount::<T>::mutate(|count| *count = count.saturating_add(1));
+            }
+        }
+
+        /// Record that a distribution happened in the current block.
+        pub(crate) fn note_distribution() {
+            LastDistributionBlock::<T>::put(frame_system::Pallet::<T>::block_number());
+            Self::check_pool_level();
+        }
+
+        /// Emit `RewardPoolLow` / `RewardPoolRecovered` when the pool crosses
+        /// `LowPoolThreshold`, latching so each crossing is only reported once.
+        pub(crate) fn check_pool_level() {
+            let threshold = T::LowPoolThreshold::get();
+            if threshold.is_zero() {
+                return;
+            }
+
+            let pool = Self::reward_pool();
+            let latched = Self::pool_low();
+            if pool < threshold && !latched {
+                PoolLow::<T>::put(true);
+                Self::deposit_event(Event::RewardPoolLow { remaining: pool });
+            } else if pool >= threshold && latched {
+                PoolLow::<T>::put(false);
+                Self::deposit_event(Event::RewardPoolRecovered { pool });
+            }
+        }
+
+        /// Aggregate pool statistics for dashboards.
+        pub fn stats() -> RewardStats<BalanceOf<T>, T::BlockNumber> {
+            RewardStats {
+                current_pool: Self::reward_pool(),
+                total_distributed: Self::total_distributed(),
+                total_topped_up: Self::total_topped_up(),
+                unique_claimants: Self::claimant_count(),
+                last_distribution_block: Self::last_distribution_block(),
+            }
+        }
+
+        /// Reserve `amount` out of the pool for `who`.
+        pub(crate) fn do_allocate(who: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+            let pool_before = Self::reward_pool();
+            let new_pool = pool_before
+                .checked_sub(&amount)
+                .ok_or(Error::<T>::InsufficientRewardPool)?;
+
+            // Allocations must leave the reserve for block rewards
+            ensure!(new_pool >= Self::min_pool_reserve(), Error::<T>::WouldBreachReserve);
+
+            // Update the storage
+            RewardPool::<T>::put(new_pool);
+            TotalAllocated::<T>::mutate(|total| *total = total.saturating_add(amount));
+            let lifetime = T::AllocationLifetime::get();
+            let expires_at = if lifetime.is_zero() {
+                T::BlockNumber::max_value()
+            } else {
+                frame_system::Pallet::<T>::block_number().saturating_add(lifetime)
+            };
+            Allocations::<T>::mutate(who, |info| {
+                let amount = info.map_or(amount, |info| info.amount.saturating_add(amount));
+                *info = Some(AllocationInfo { amount, expires_at });
+            });
+
+            Ok(())
+        }
+
+        /// Pay `amount` to `claimant` from their allocation, or from the pool when
+        /// they have none and `AllowUnallocatedClaims` is set.
+        pub(crate) fn do_claim(
+            claimant: &T::AccountId,
+            amount: BalanceOf<T>,
+            memo: Option<Memo<T>>,
+        ) -> DispatchResult {
+            let now = frame_system::Pallet::<T>::block_number();
+
+            let allocation = Self::allocations(claimant);
+            let from_allocation = !allocation.is_zero();
+            if from_allocation {
+                ensure!(!Self::allocation_expired(claimant, now), Error::<T>::AllocationExpired);
+
+                // Don't silently cap, that surprises integrators
+                ensure!(amount <= allocation, Error::<T>::ExceedsAllocation);
+            } else {
+                ensure!(T::AllowUnallocatedClaims::get(), Error::<T>::NothingToClaim);
+            }
+
+            // Dust claims are rejected, unless they empty the allocation so nobody
+            // gets stranded with an unclaimable remainder.
+            ensure!(
+                amount >= Self::params().min_claim_amount ||
+                    (from_allocation && amount == allocation),
+                Error::<T>::ClaimBelowMinimum
+            );
+
+            Self::ensure_can_claim(claimant, amount, now)?;
+
+            let dest = Self::payout_account(claimant);
+            if from_allocation {
+                Self::pay_from_pot(&dest, amount)?;
+
+                // Update the allocation, folding a dust remainder back into the pool
+                TotalAllocated::<T>::mutate(|total| *total = total.saturating_sub(amount));
+                let remainder = allocation - amount;
+                let remainder = if Self::handle_dust(remainder) { Zero::zero() } else { remainder };
+                Self::set_allocation_amount(claimant, remainder);
+            } else {
+                // Check if the pool has enough funds
+                let pool_before = Self::reward_pool();
+                ensure!(pool_before >= amount, Error::<T>::InsufficientRewardPool);
+
+                // Manual claims must leave the reserve for block rewards
+                ensure!(
+                    pool_before - amount >= Self::min_pool_reserve(),
+                    Error::<T>::WouldBreachReserve
+                );
+
+                Self::pay_from_pot(&dest, amount)?;
+
+                // Update the pool
+                RewardPool::<T>::put(pool_before - amount);
+            }
+
+            Self::complete_claim(claimant, amount, now, memo);
+            Ok(())
+        }
+
+        /// Bookkeeping shared by every claim path once `amount` has been paid out.
+        pub(crate) fn complete_claim(
+            claimant: &T::AccountId,
+            amount: BalanceOf<T>,
+            now: T::BlockNumber,
+            memo: Option<Memo<T>>,
+        ) {
+            Self::note_claim(claimant, amount, now);
+            Self::record_payout(claimant, amount, memo);
+        }
+
+        /// Record `amount` paid out to `claimant` in the distribution totals and emit
+        /// `RewardClaimed`. Does not touch the per-account claim limits.
+        pub(crate) fn record_payout(
+            claimant: &T::AccountId,
+            amount: BalanceOf<T>,
+            memo: Option<Memo<T>>,
+        ) {
+            // Update the total distributed
+            let total_dist_before = Self::total_distributed();
+            let new_total_dist = total_dist_before.checked_add(&amount).unwrap_or_else(|| {
+                frame_support::defensive!("TotalDistributed overflow");
+                BalanceOf::<T>::max_value()
+            });
+            TotalDistributed::<T>::put(new_total_dist);
+            Self::note_claimant(claimant);
+            Self::note_distribution();
+
+            // Write the audit receipt
+            let claim_id = NextClaimId::<T>::mutate(|next| {
+                let id = *next;
+                *next = next.saturating_add(1);
+                id
+            });
+            let block = frame_system::Pallet::<T>::block_number();
+            Receipts::<T>::insert(claim_id, Receipt { who: claimant.clone(), amount, block });
+
+            // Emit event
+            let lifetime_claimed = Self::note_claimed_by(claimant, amount);
+            Self::deposit_event(Event::RewardClaimed {
+                claim_id,
+                who: claimant.clone(),
+                amount,
+                lifetime_claimed,
+                remaining_allocation: Self::allocations(claimant),
+                memo,
+            });
+        }
+
+        /// Transfer `amount` from the pot to `dest`, checking the pot can cover it and
+        /// that `dest` will not be dusted.
+        pub(crate) fn pay_from_pot(dest: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+            // The storage value alone is not enough, the pot must hold the funds too
+            ensure!(Self::pot() >= amount, Error::<T>::InsufficientPotBalance);
+
+            // A fresh account receiving less than the ED would be dusted
+            ensure!(Self::would_survive(dest, amount), Error::<T>::BelowExistentialDeposit);
+
+            T::Currency::transfer(&Self::account_id(), dest, amount, ExistenceRequirement::KeepAlive)
+        }
+
+        /// Move `amount` of `who`'s allocation back into the pool, returning the
+        /// allocation left over. `amount` is capped at the current allocation.
+        pub(crate) fn do_revoke(who: &T::AccountId, amount: BalanceOf<T>) -> BalanceOf<T> {
+            let allocation = Self::allocations(who);
+            let amount = amount.min(allocation);
+            let remaining = allocation - amount;
+
+            // Update the storage
+            Self::set_allocation_amount(who, remaining);
+            TotalAllocated::<T>::mutate(|total| *total = total.saturating_sub(amount));
+            RewardPool::<T>::mutate(|pool| *pool = pool.saturating_add(amount));
+            Self::check_pool_level();
+
+            remaining
+        }
+
+        /// The account that actually receives `who`'s rewards.
+        pub fn payout_account(who: &T::AccountId) -> T::AccountId {
+            Self::payout_destination(who).unwrap_or_else(|| who.clone())
+        }
+
+        /// The account that receives `author`'s block rewards: its author payout
+        /// account if set, otherwise its general payout account.
+        pub fn block_reward_account(author: &T::AccountId) -> T::AccountId {
+            Self::author_payout_destination(author).unwrap_or_else(|| Self::payout_account(author))
+        }
+
+        /// Check the per-account rules that gate every claim of `amount`.
+        pub(crate) fn ensure_can_claim(
+            claimant: &T::AccountId,
+            amount: BalanceOf<T>,
+            now: T::BlockNumber,
+        ) -> DispatchResult {
+            ensure!(
+                !T::RequireInitialization::get() || Self::initialized(),
+                Error::<T>::NotInitialized
+            );
+            ensure!(!Self::is_banned(claimant), Error::<T>::AccountBanned);
+            ensure!(Self::is_claimer(claimant), Error::<T>::NotEligible);
+
+            let min_balance = Self::eligibility_min_balance();
+            ensure!(
+                min_balance.is_zero() || T::Currency::total_balance(claimant) >= min_balance,
+                Error::<T>::InsufficientStake
+            );
+
+            ensure!(
+                Self::claims_this_block() < T::MaxClaimsPerBlock::get(),
+                Error::<T>::TooManyClaimsThisBlock
+            );
+
+            let block_budget = T::MaxClaimedPerBlock::get();
+            ensure!(
+                block_budget.is_zero() ||
+                    Self::claimed_this_block().saturating_add(amount) <= block_budget,
+                Error::<T>::BlockClaimBudgetExceeded
+            );
+
+            let cooldown = Self::params().claim_cooldown;
+            if !cooldown.is_zero() {
+                if let Some(last) = Self::last_claim_block(claimant) {
+                    ensure!(now >= last.saturating_add(cooldown), Error::<T>::ClaimTooSoon);
+                }
+            }
+
+            let cap = T::ClaimCapPerPeriod::get();
+            if !cap.is_zero() {
+                let claimed = Self::claimed_this_period(claimant, now);
+                ensure!(claimed.saturating_add(amount) <= cap, Error::<T>::ClaimCapExceeded);
+            }
+
+            Ok(())
+        }
+
+        /// Update the per-account claim tracking after a successful claim.
+        pub(crate) fn note_claim(claimant: &T::AccountId, amount: BalanceOf<T>, now: T::BlockNumber) {
+            LastClaimBlock::<T>::insert(claimant, now);
+            ClaimsThisBlock::<T>::mutate(|count| *count = count.saturating_add(1));
+            ClaimedThisBlock::<T>::mutate(|total| *total = total.saturating_add(amount));
+
+            if T::MaxHistoryEntries::get() > 0 {
+                ClaimHistory::<T>::mutate(claimant, |history| {
+                    // Evict the oldest record when full
+                    if history.len() as u32 >= T::MaxHistoryEntries::get() {
+                        history.remove(0);
+                    }
+                    let _ = history.try_push(ClaimRecord { amount, block: now });
+                });
+            }
+
+            if !T::ClaimCapPerPeriod::get().is_zero() {
+                let claimed = Self::claimed_this_period(claimant, now).saturating_add(amount);
+                PeriodClaims::<T>::insert(claimant, (Self::claim_period_index(now), claimed));
+            }
+        }
+
+        /// The index of the claim period containing block `now`.
+        pub fn claim_period_index(now: T::BlockNumber) -> T::BlockNumber {
+            let length = T::ClaimPeriodLength::get();
+            if length.is_zero() {
+                Zero::zero()
+            } else {
+                now / length
+            }
+        }
+
+        /// How much `who` has claimed in the period containing block `now`. Resets to
+        /// zero once the period index advances.
+        pub fn claimed_this_period(who: &T::AccountId, now: T::BlockNumber) -> BalanceOf<T> {
+            match Self::period_claims(who) {
+                Some((period, claimed)) if period == Self::claim_period_index(now) => claimed,
+                _ => Zero::zero(),
+            }
+        }
+
+        /// Add `amount` to `who`'s lifetime received total, returning the new total.
+        pub(crate) fn note_claimed_by(who: &T::AccountId, amount: BalanceOf<T>) -> BalanceOf<T> {
+            TotalClaimedBy::<T>::mutate(who, |total| {
+                *total = total.saturating_add(amount);
+                *total
+            })
+        }
+
+        /// Verify a Merkle proof using blake2-256 over sorted sibling pairs.
+        pub fn verify_proof(root: H256, leaf: H256, proof: &[H256]) -> bool {
+            Self::proof_root(leaf, proof) == root
+        }
+
+        /// The bytes `ClaimSigner` signs for a voucher: `VOUCHER_CONTEXT` and the
+        /// genesis hash, followed by `(claimant, amount, nonce)`.
+        pub fn voucher_payload(
+            claimant: &T::AccountId,
+            amount: BalanceOf<T>,
+            nonce: u64,
+        ) -> Vec<u8> {
+            let genesis = frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+            (VOUCHER_CONTEXT, genesis, claimant, amount, nonce).encode()
+        }
+
+        /// The root that `proof` hashes `leaf` up to.
+        pub fn proof_root(leaf: H256, proof: &[H256]) -> H256 {
+            proof.iter().fold(leaf, |node, sibling| {
+                let (left, right) = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+                let mut pair = [0u8; 64];
+                pair[..32].copy_from_slice(left.as_bytes());
+                pair[32..].copy_from_slice(right.as_bytes());
+                H256(blake2_256(&pair))
+            })
+        }
+
+        /// Run `do_claim` with the anti-spam `ClaimDeposit` reserved from the claimant.
+        ///
+        /// If the claimant fails the eligibility checks, the claim's changes are rolled
+        /// back but the deposit is slashed into the pool and the call still succeeds,
+        /// so the slash is kept. Any other failure returns the deposit and the error.
+        /// Returns whether the reward was actually paid out.
+        pub(crate) fn claim_with_deposit(
+            claimant: &T::AccountId,
+            amount: BalanceOf<T>,
+            memo: Option<Memo<T>>,
+            claim_key: Option<ClaimKey>,
+        ) -> Result<bool, DispatchError> {
+            let deposit = T::ClaimDeposit::get();
+            if deposit.is_zero() {
+                return Self::do_keyed_claim(claimant, amount, memo, claim_key).map(|_| true);
+            }
+
+            T::Currency::reserve(claimant, deposit)
+                .map_err(|_| Error::<T>::CannotReserveDeposit)?;
+
+            match frame_support::storage::with_storage_layer(|| {
+                Self::do_keyed_claim(claimant, amount, memo, claim_key)
+            }) {
+                Ok(()) => {
+                    T::Currency::unreserve(claimant, deposit);
+                    Ok(true)
+                },
+                Err(error) if Self::forfeits_deposit(&error) => {
+                    let (slashed, _) = T::Currency::slash_reserved(claimant, deposit);
+                    let amount = slashed.peek();
+                    Self::absorb_imbalance(slashed);
+                    Self::deposit_event(Event::ClaimDepositSlashed {
+                        who: claimant.clone(),
+                        amount,
+                        error,
+                    });
+                    Ok(false)
+                },
+                Err(error) => {
+                    T::Currency::unreserve(claimant, deposit);
+                    Err(error)
+                },
+            }
+        }
+
+        /// Whether a claim failing with `error` forfeits the claim deposit. Only
+        /// eligibility failures do; honest mistakes such as an amount over the
+        /// allocation or a drained pool don't.
+        fn forfeits_deposit(error: &DispatchError) -> bool {
+            [Error::<T>::NotEligible, Error::<T>::AccountBanned, Error::<T>::InsufficientStake]
+                .into_iter()
+                .any(|slashable| *error == slashable.into())
+        }
+
+        /// Whether `who` may claim under the whitelist. Everyone may while the
+        /// whitelist is empty only if `OpenClaimsWhenEmpty` is set.
+        pub fn is_claimer(who: &T::AccountId) -> bool {
+            Claimers::<T>::contains_key(who) ||
+                (T::OpenClaimsWhenEmpty::get() && Claimers::<T>::count() == 0)
+        }
+
+        /// Whether `who` is banned from claiming.
+        pub fn is_banned(who: &T::AccountId) -> bool {
+            BannedClaimers::<T>::contains_key(who)
+        }
+
+        /// The outstanding allocation of `who`, zero if none.
+        pub fn allocations(who: &T::AccountId) -> BalanceOf<T> {
+            Self::allocation_info(who).map_or_else(Zero::zero, |info| info.amount)
+        }
+
+        /// Whether `who` has an allocation that expired at or before `now`.
+        pub fn allocation_expired(who: &T::AccountId, now: T::BlockNumber) -> bool {
+            Self::allocation_info(who).map_or(false, |info| now >= info.expires_at)
+        }
+
+        /// Set the remaining amount of `who`'s allocation, keeping its expiry and
+        /// removing the entry once it reaches zero.
+        pub(crate) fn set_allocation_amount(who: &T::AccountId, remaining: BalanceOf<T>) {
+            Allocations::<T>::mutate_exists(who, |info| {
+                if remaining.is_zero() {
+                    *info = None;
+                } else if let Some(info) = info {
+                    info.amount = remaining;
+                }
+            });
+        }
+
+        /// Walk `Allocations` from the persisted cursor and pay out any allocation
+        /// below `AutoPayoutThreshold` that passes `ensure_can_claim` while `limit`
+        /// allows, then persist the cursor so the sweep resumes in the next block.
+        fn auto_payout_small_allocations(limit: Weight) -> Weight {
+            let threshold = T::AutoPayoutThreshold::get();
+            let db = T::DbWeight::get();
+            let mut used = db.reads(1);
+            if threshold.is_zero() || limit.any_lt(used) {
+                return Weight::zero();
+            }
+
+            // Allocation, pot, destination account, totals and history per entry, plus
+            // the eligibility checks and claim limits
+            let per_item = db.reads_writes(13, 11);
+
+            // Collect a batch first so payouts don't mutate the map mid-iteration.
+            // Entries removed concurrently are simply skipped by `iter_from`.
+            let mut iter = match AutoPayoutCursor::<T>::get() {
+                Some(cursor) => Allocations::<T>::iter_from(cursor.into_inner()),
+                None => Allocations::<T>::iter(),
+            };
+            let mut batch = Vec::new();
+            let mut exhausted = false;
+            while limit.all_gte(used.saturating_add(per_item)) {
+                match iter.next() {
+                    Some(entry) => {
+                        batch.push(entry);
+                        used = used.saturating_add(per_item);
+                    },
+                    None => {
+                        exhausted = true;
+                        break;
+                    },
+                }
+            }
+
+            // Persist the cursor, or start over next time once the map is exhausted
+            let cursor = if exhausted {
+                None
+            } else {
+                BoundedVec::try_from(iter.last_raw_key().to_vec()).ok()
+            };
+            AutoPayoutCursor::<T>::set(cursor);
+            used = used.saturating_add(db.writes(1));
+
+            let now = frame_system::Pallet::<T>::block_number();
+            for (who, info) in batch {
+                if info.amount >= threshold || now >= info.expires_at {
+                    continue;
+                }
+                // Only pay out what the account could have claimed itself
+                if Self::ensure_can_claim(&who, info.amount, now).is_err() {
+                    continue;
+                }
+                if Self::pay_from_pot(&Self::payout_account(&who), info.amount).is_err() {
+                    continue;
+                }
+
+                Self::set_allocation_amount(&who, Zero::zero());
+                TotalAllocated::<T>::mutate(|total| *total = total.saturating_sub(info.amount));
+                Self::complete_claim(&who, info.amount, now, None);
+            }
+
+            used
+        }
+
+        /// `do_claim`, recording `claim_key` as processed if the claim succeeds.
+        fn do_keyed_claim(
+            claimant: &T::AccountId,
+            amount: BalanceOf<T>,
+            memo: Option<Memo<T>>,
+            claim_key: Option<ClaimKey>,
+        ) -> DispatchResult {
+            Self::do_claim(claimant, amount, memo)?;
+
+            if let Some(key) = claim_key {
+                let now = frame_system::Pallet::<T>::block_number();
+                ProcessedClaimKeys::<T>::insert(claimant, key, now);
+            }
+
+            Ok(())
+        }
+
+        /// Whether `key` was processed for `who` within the retention window.
+        pub fn claim_key_active(who: &T::AccountId, key: &ClaimKey) -> bool {
+            let now = frame_system::Pallet::<T>::block_number();
+            ProcessedClaimKeys::<T>::get(who, key)
+                .map_or(false, |at| now < at.saturating_add(T::ClaimKeyRetention::get()))
+        }
+
+        /// Process up to `ClaimsPerBlock` queued claim requests, oldest first. Each
+        /// request either pays out (emitting `RewardClaimed`) or is rejected without
+        /// leaving partial state behind.
+        fn process_claim_queue() -> Weight {
+            let db = T::DbWeight::get();
+            let mut queue = Self::pending_claims();
+            if queue.is_empty() {
+                return db.reads(1);
+            }
+
+            let count = (T::ClaimsPerBlock::get() as usize).min(queue.len());
+            let requests: Vec<_> = queue.drain(..count).collect();
+            PendingClaims::<T>::put(queue);
+
+            for request in requests {
+                let result = frame_support::storage::with_storage_layer(|| {
+                    Self::do_claim(&request.who, request.amount, None)
+                });
+                if let Err(error) = result {
+                    Self::deposit_event(Event::ClaimRequestRejected {
+                        id: request.id,
+                        who: request.who,
+                        error,
+                    });
+                }
+            }
+
+            // Queue read and write, plus a full claim per processed request
+            db.reads_writes(1, 1).saturating_add(db.reads_writes(8, 8).saturating_mul(count as u64))
+        }
+
+        /// The amount of a vesting schedule still locked at block `now`.
+        pub fn locked_at(
+            info: &VestingInfo<BalanceOf<T>, T::BlockNumber>,
+            now: T::BlockNumber,
+        ) -> BalanceOf<T> {
+            let elapsed = now.saturating_sub(info.starting_block).saturated_into::<u32>();
+            let unlocked = info.per_block.saturating_mul(elapsed.into());
+            info.locked.saturating_sub(unlocked)
+        }
+
+        /// Credit `points` to `who` in the current era. Other pallets call this to
+        /// feed usage into the proportional era rewards.
+        pub fn note_points(who: &T::AccountId, points: u32) {
+            let era = Self::current_era();
+            EraPoints::<T>::mutate(era, who, |p| *p = p.saturating_add(points));
+            EraTotalPoints::<T>::mutate(era, |total| *total = total.saturating_add(points.into()));
+        }
+
+        /// Set aside the budget of the ended `era` from the pool, plus any carried
+        /// dust, to be claimed in proportion to points. Eras without points settle to
+        /// nothing and leave the dust carried.
+        fn settle_era(era: u32) -> Weight {
+            let db = T::DbWeight::get();
+            let total_points = EraTotalPoints::<T>::take(era);
+            let from_pool = Self::era_budget().min(Self::reward_pool());
+            let dust = Self::carried_dust();
+            let budget = from_pool.saturating_add(dust);
+            if total_points == 0 || budget.is_zero() {
+                return db.reads_writes(4, 1);
+            }
+
+            // Move the budget from the pool into allocated funds; dust already is
+            RewardPool::<T>::mutate(|pool| *pool = pool.saturating_sub(from_pool));
+            TotalAllocated::<T>::mutate(|total| *total = total.saturating_add(from_pool));
+            CarriedDust::<T>::kill();
+            EraRewards::<T>::insert(
+                era,
+                EraReward { budget, total_points, remaining: budget, claimed_points: 0 },
+            );
+            Self::check_pool_level();
+
+            // Emit event
+            Self::deposit_event(Event::EraSettled { era, budget, total_points, dust });
+
+            db.reads_writes(5, 5)
+        }
+
+        /// Fold `amount`, a residue counted in `TotalAllocated`, back into the reward
+        /// pool if it is below the dust threshold. Returns whether it was folded, in
+        /// which case the caller must drop it from its own accumulator.
+        pub fn handle_dust(amount: BalanceOf<T>) -> bool {
+            if amount.is_zero() || amount >= Self::params().dust_threshold {
+                return false;
+            }
+            TotalAllocated::<T>::mutate(|total| *total = total.saturating_sub(amount));
+            RewardPool::<T>::mutate(|pool| *pool = pool.saturating_add(amount));
+            DustCollectedThisBlock::<T>::mutate(|dust| *dust = dust.saturating_add(amount));
+            Self::check_pool_level();
+            true
+        }
+
+        /// The share of `reward` earned by `points`, rounded down so the shares of
+        /// an era never sum to more than its budget.
+        pub fn era_share(reward: &EraReward<BalanceOf<T>>, points: u32) -> BalanceOf<T> {
+            if reward.total_points == 0 {
+                return Zero::zero();
+            }
+            let budget: u128 = reward.budget.saturated_into();
+            let share = budget.saturating_mul(points.into()) / u128::from(reward.total_points);
+            share.saturated_into()
+        }
+
+        /// Whether the shares of `era` are past `EraClaimLifetime`, i.e. more than that
+        /// many eras have started since it ended.
+        pub fn era_expired(era: u32) -> bool {
+            let lifetime = T::EraClaimLifetime::get();
+            lifetime > 0 && Self::current_era() > era.saturating_add(lifetime)
+        }
+
+        /// Whether `who` is excluded from block rewards.
+        pub fn is_excluded_author(who: &T::AccountId) -> bool {
+            ExcludedAuthors::<T>::contains_key(who)
+        }
+
+        /// Block reward emission counters for dashboards.
+        pub fn emission_stats() -> EmissionStats<T::BlockNumber> {
+            EmissionStats {
+                blocks_rewarded: BlocksRewarded::<T>::get(),
+                blocks_skipped: BlocksSkipped::<T>::get(),
+                last_rewarded_block: LastRewardedBlock::<T>::get(),
+            }
+        }
+
+        /// Ensure `origin` satisfies `FundingOrigin` or `EnsureRewardManager`.
+        /// Yields the acting account, or `None` for root and collective origins.
+        pub(crate) fn ensure_funding_origin(
+            origin: OriginFor<T>,
+        ) -> Result<Option<T::AccountId>, DispatchError> {
+            if let Ok(who) = T::FundingOrigin::try_origin(origin.clone()) {
+                return Ok(who);
+            }
+            Ok(EnsureRewardManager::<T>::ensure_origin(origin)?)
+        }
+
+        /// Ensure `origin` satisfies `ParamsOrigin` or `EnsureRewardManager`.
+        pub(crate) fn ensure_params_origin(origin: OriginFor<T>) -> DispatchResult {
+            if T::ParamsOrigin::try_origin(origin.clone()).is_ok() {
+                return Ok(());
+            }
+            EnsureRewardManager::<T>::ensure_origin(origin)?;
+            Ok(())
+        }
+
+        /// The floor that manual claims cannot push the pool below, so that the
+        /// per-block author rewards never starve. Block rewards may dip into it.
+        pub fn min_pool_reserve() -> BalanceOf<T> {
+            Self::params().min_pool_reserve
+        }
+
+        /// The minimum total (free + reserved) balance a claimant must hold. Zero
+        /// disables the check.
+        pub fn eligibility_min_balance() -> BalanceOf<T> {
+            Self::params().eligibility_min_balance
+        }
+
+        /// The amount taken from the pool at each era boundary and shared out among
+        /// the accounts that earned points in the ended era.
+        pub fn era_budget() -> BalanceOf<T> {
+            Self::params().era_budget
+        }
+
+        /// Overrides the `RewardPerBlock` constant when set, so emission can change
+        /// without a runtime upgrade.
+        pub fn reward_per_block_override() -> Option<BalanceOf<T>> {
+            Self::params().reward_per_block
+        }
+
+        /// The fee a transaction must have paid for its usage to earn rewards. Zero
+        /// disables the check.
+        pub fn min_consensus_fee() -> BalanceOf<T> {
+            Self::params().min_consensus_fee
+        }
+
+        /// Check that a parameter set is consistent. The fee split must not exceed
+        /// 100%. With a `MaxPoolSize`, the reserve and era budget must fit in the pool
+        /// together and the per-block reward must fit on its own.
+        pub(crate) fn ensure_valid_params(p: &ParamsOf<T>) -> DispatchResult {
+            ensure!(p.fee_split.is_valid(), Error::<T>::InvalidParams);
+            let max_pool = T::MaxPoolSize::get();
+            if max_pool.is_zero() {
+                return Ok(());
+            }
+            let reward = p.reward_per_block.unwrap_or_else(T::RewardPerBlock::get);
+            ensure!(
+                p.min_pool_reserve.saturating_add(p.era_budget) <= max_pool && reward <= max_pool,
+                Error::<T>::InvalidParams
+            );
+            Ok(())
+        }
+
+        /// Apply `f` to the stored parameters, keeping the old ones if the result
+        /// is invalid or the parameters are frozen. Returns the new parameters.
+        pub(crate) fn mutate_params(
+            f: impl FnOnce(&mut ParamsOf<T>),
+        ) -> Result<ParamsOf<T>, DispatchError> {
+            Self::ensure_params_not_frozen()?;
+            Params::<T>::try_mutate(|p| {
+                let mut new = *p;
+                f(&mut new);
+                Self::ensure_valid_params(&new)?;
+                *p = new;
+                Ok(new)
+            })
+        }
+
+        /// Whether `who` is an operator.
+        pub fn is_operator(who: &T::AccountId) -> bool {
+            Operators::<T>::contains_key(who)
+        }
+
+        /// Ensure `origin` is a signed operator or satisfies `ensure_params_origin`.
+        pub(crate) fn ensure_operator(origin: OriginFor<T>) -> DispatchResult {
+            if Self::ensure_params_origin(origin.clone()).is_ok() {
+                return Ok(());
+            }
+            let who = ensure_signed(origin)?;
+            ensure!(Self::is_operator(&who), DispatchError::BadOrigin);
+            Ok(())
+        }
+
+        /// The signed account behind `origin`, if any, for attributing changes.
+        /// Root and collective origins yield `None`.
+        pub(crate) fn acting_account(origin: &OriginFor<T>) -> Option<T::AccountId> {
+            ensure_signed(origin.clone()).ok()
+        }
+
+        /// Panic if the runtime configuration can only misbehave on-chain. Shared by
+        /// `integrity_test` and the genesis build.
+        pub(crate) fn assert_config_consistent() {
+            let max_pool = T::MaxPoolSize::get();
+            assert!(
+                max_pool.is_zero() || T::RewardPerBlock::get() <= max_pool,
+                "RewardPerBlock exceeds MaxPoolSize, no pool could ever pay it"
+            );
+            assert!(
+                !T::AccrueBlockRewards::get() || !T::BlockRewardSplit::get().is_zero(),
+                "AccrueBlockRewards is set but BlockRewardSplit leaves authors nothing to accrue"
+            );
+            assert!(
+                !T::AccrueBlockRewards::get() || !T::PayAuthorsPerEra::get(),
+                "AccrueBlockRewards has no effect when PayAuthorsPerEra is set"
+            );
+            assert!(
+                T::EmissionMode::get() != EmissionMode::Proportional ||
+                    T::PoolEmissionRate::get() < Perbill::one(),
+                "PoolEmissionRate must be below 100%, or the first block empties the pool"
+            );
+            assert!(T::MaxClaimsPerBlock::get() > 0, "MaxClaimsPerBlock must allow claims");
+
+            let era_features = T::PayAuthorsPerEra::get() ||
+                !T::MaxAuthorRewardPerEra::get().is_zero() ||
+                !T::PoolInflationPerEra::get().is_zero();
+            assert!(
+                !era_features || !T::EraLength::get().is_zero(),
+                "EraLength must be non-zero when era features are configured"
+            );
+            assert!(
+                Self::ensure_valid_params(&DefaultParams::<T>()).is_ok(),
+                "Default parameters are invalid, e.g. they do not fit under MaxPoolSize"
+            );
+        }
+
+        /// Fail with `ParamsFrozen` once `freeze_params` has been called.
+        pub(crate) fn ensure_params_not_frozen() -> DispatchResult {
+            ensure!(!Self::params_frozen(), Error::<T>::ParamsFrozen);
+            Ok(())
+        }
+
+        /// Fail with `PalletPaused` while the pallet is paused.
+        pub(crate) fn ensure_not_paused() -> DispatchResult {
+            ensure!(!Self::paused(), Error::<T>::PalletPaused);
+            Ok(())
+        }
+
+        /// Charge `amount` against the caller's top-up allowance for the current era.
+        pub(crate) fn spend_top_up_allowance(
+            who: &T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let allowance = Self::top_up_allowance(who).ok_or(Error::<T>::BadOriginForTopUp)?;
+            let era = Self::current_era();
+            let spent = Self::top_up_spent(era, who)
+                .checked_add(&amount)
+                .ok_or(ArithmeticError::Overflow)?;
+            ensure!(spent <= allowance, Error::<T>::TopUpAllowanceExceeded);
+            TopUpSpent::<T>::insert(era, who, spent);
+            Ok(())
+        }
+
+        /// Forget the ended era's top-up spending so every allowance is whole again
+        /// in `era`.
+        fn reset_top_up_allowances(era: u32) -> Weight {
+            let db = T::DbWeight::get();
+            let limit = T::MaxTopUpOperators::get();
+            let _ = TopUpSpent::<T>::clear_prefix(era.saturating_sub(1), limit, None);
+            if TopUpAllowance::<T>::count() > 0 {
+                Self::deposit_event(Event::TopUpAllowancesReset { era });
+            }
+            db.reads_writes(1, limit.into())
+        }
+    }
+
+    // ---------------------------------------------
+    //  Fee Capture
+    // ---------------------------------------------
+
+    /// Divides a negative imbalance (e.g. transaction fees from a runtime
+    /// `DealWithFees` splitter) according to the `FeeSplit`: the dev share is
+    /// credited to the reward pool, the validator share paid to the block author,
+    /// the burn share dropped and the rest sent to the `TreasuryAccount`. Without a
+    /// known author the validator share goes to the treasury too.
+    impl<T: Config> OnUnbalanced<NegativeImbalanceOf<T>> for Pallet<T> {
+        fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<T>) {
+            let split = Self::params().fee_split;
+            let total = amount.peek();
+            let (dev, rest) = amount.split(split.dev * total);
+            let (validator, rest) = rest.split(split.validator * total);
+            let (burn, mut treasury) = rest.split(split.burn * total);
+
+            let dev = Self::absorb_imbalance(dev);
+            let validator = match NotedAuthor::<T>::get().or_else(Self::digest_author) {
+                Some(author) => {
+                    let paid = validator.peek();
+                    T::Currency::resolve_creating(&author, validator);
+                    paid
+                },
+                None => {
+                    treasury.subsume(validator);
+                    Zero::zero()
+                },
+            };
+            // Dropping the imbalance burns it
+            let burned = burn.peek();
+            drop(burn);
+            let to_treasury = treasury.peek();
+            T::Currency::resolve_creating(&T::TreasuryAccount::get(), treasury);
+
+            // Emit event
+            Self::deposit_event(Event::FeesCaptured {
+                dev,
+                validator,
+                burned,
+                treasury: to_treasury,
+            });
+        }
+    }
+
+    // ---------------------------------------------
+    //  Origins
+    // ---------------------------------------------
+
+    /// Succeeds for root and for the stored `Admin` account, so other pallets can
+    /// reuse this pallet's on-chain authority. Yields the admin account, or `None`
+    /// for root.
+    pub struct EnsureRewardManager<T>(PhantomData<T>);
+
+    impl<T: Config> EnsureOrigin<T::RuntimeOrigin> for EnsureRewardManager<T> {
+        type Success = Option<T::AccountId>;
+
+        fn try_origin(o: T::RuntimeOrigin) -> Result<Self::Success, T::RuntimeOrigin> {
+            o.into().and_then(|o| match o {
+                frame_system::RawOrigin::Root => Ok(None),
+                frame_system::RawOrigin::Signed(who)
+                    if Pallet::<T>::admin().as_ref() == Some(&who) =>
+                    Ok(Some(who)),
+                r => Err(T::RuntimeOrigin::from(r)),
+            })
+        }
+
+        #[cfg(feature = "runtime-benchmarks")]
+        fn try_successful_origin() -> Result<T::RuntimeOrigin, ()> {
+            Ok(frame_system::RawOrigin::Root.into())
+        }
+    }
+
+    // ---------------------------------------------
+    //  Authorship Integration
+    // ---------------------------------------------
+
+    /// Reports the block reward bonus an author earned, e.g. for meeting an uptime
+    /// criterion. `Permill::from_percent(20)` pays 1.2x the block reward.
+    pub trait AuthorBonus<AccountId> {
+        /// The bonus applied to `author`'s block rewards.
+        fn bonus(author: &AccountId) -> Permill;
+    }
+
+    impl<AccountId> AuthorBonus<AccountId> for () {
+        fn bonus(_author: &AccountId) -> Permill {
+            Permill::zero()
+        }
+    }
+
+    /// Notes the author reported by `pallet_authorship`, to be paid in `on_finalize`.
+    /// Wire this up as `pallet_authorship::Config::EventHandler`.
+    impl<T: Config> pallet_authorship::EventHandler<T::AccountId, T::BlockNumber> for Pallet<T> {
+        fn note_author(author: T::AccountId) {
+            NotedAuthor::<T>::put(author);
+        }
+
+        fn note_uncle(_author: T::AccountId, _age: T::BlockNumber) {}
+    }
+
+    // ---------------------------------------------
+    //  Reward Accrual
+    // ---------------------------------------------
+
+    /// Lets other runtime pallets (e.g. contracts or usage metering) credit rewards
+    /// without going through extrinsics. Runtimes pass this pallet wherever a
+    /// `RewardAccrual` implementation is expected.
+    pub trait RewardAccrual<AccountId, Balance> {
+        /// Add `amount` to `who`'s claimable allocation, reserving it from the pool.
+        /// Fails if the pool cannot cover it.
+        fn accrue(who: &AccountId, amount: Balance) -> DispatchResult;
+
+        /// Like `accrue`, for rewards attributed to a transaction that paid `fee`.
+        /// Fails if `fee` is below the minimum consensus fee.
+        fn accrue_for_fee(who: &AccountId, amount: Balance, fee: Balance) -> DispatchResult;
+
+        /// The amount `who` can currently claim.
+        fn pending(who: &AccountId) -> Balance;
+    }
+
+    impl<T: Config> RewardAccrual<T::AccountId, BalanceOf<T>> for Pallet<T> {
+        fn accrue(who: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+            ensure!(!amount.is_zero(), Error::<T>::InvalidClaimAmount);
+            Self::do_allocate(who, amount)?;
+
+            // Emit event
+            Self::deposit_event(Event::RewardAllocated(who.clone(), amount));
+
+            Ok(())
+        }
+
+        fn accrue_for_fee(
+            who: &T::AccountId,
+            amount: BalanceOf<T>,
+            fee: BalanceOf<T>,
+        ) -> DispatchResult {
+            ensure!(fee >= Self::min_consensus_fee(), Error::<T>::BelowMinConsensusFee);
+            Self::accrue(who, amount)
+        }
+
+        fn pending(who: &T::AccountId) -> BalanceOf<T> {
+            let now = frame_system::Pallet::<T>::block_number();
+            if Self::allocation_expired(who, now) {
+                return Zero::zero();
+            }
+            Self::allocations(who)
+        }
+    }
+
+    // ---------------------------------------------
+    //  Transaction Validation
+    // ---------------------------------------------
+
+    /// `InvalidTransaction::Custom` code returned by `CheckRewardPool` for a claim
+    /// that has nothing to draw from.
+    pub const NOTHING_TO_CLAIM: u8 = 1;
+
+    /// Rejects `claim_reward` and `claim_all` transactions that are bound to fail
+    /// because neither the caller's allocation nor the pool can pay them, so they
+    /// never enter the transaction pool and the caller is not charged a fee.
+    #[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct CheckRewardPool<T: Config + Send + Sync>(PhantomData<T>);
+
+    impl<T: Config + Send + Sync> CheckRewardPool<T> {
+        /// Create a new `CheckRewardPool` extension.
+        pub fn new() -> Self {
+            Self(PhantomData)
+        }
+    }
+
+    impl<T: Config + Send + Sync> Default for CheckRewardPool<T> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl<T: Config + Send + Sync> sp_std::fmt::Debug for CheckRewardPool<T> {
+        fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+            write!(f, "CheckRewardPool")
+        }
+    }
+
+    impl<T: Config + Send + Sync> SignedExtension for CheckRewardPool<T>
+    where
+        T::RuntimeCall: IsSubType<Call<T>>,
+    {
+        const IDENTIFIER: &'static str = "CheckRewardPool";
+        type AccountId = T::AccountId;
+        type Call = T::RuntimeCall;
+        type AdditionalSigned = ();
+        type Pre = ();
+
+        fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+            Ok(())
+        }
+
+        fn validate(
+            &self,
+            who: &Self::AccountId,
+            call: &Self::Call,
+            _info: &DispatchInfoOf<Self::Call>,
+            _len: usize,
+        ) -> TransactionValidity {
+            let has_allocation = || !Pallet::<T>::allocations(who).is_zero();
+            let can_pay = match call.is_sub_type() {
+                Some(Call::claim_reward { .. }) =>
+                    has_allocation() ||
+                        (T::AllowUnallocatedClaims::get() &&
+                            !Pallet::<T>::reward_pool().is_zero()),
+                Some(Call::claim_all {}) => has_allocation(),
+                _ => true,
+            };
+            ensure!(can_pay, InvalidTransaction::Custom(NOTHING_TO_CLAIM));
+
+            Ok(ValidTransaction::default())
+        }
+
+        fn pre_dispatch(
+            self,
+            who: &Self::AccountId,
+            call: &Self::Call,
+            info: &DispatchInfoOf<Self::Call>,
+            len: usize,
+        ) -> Result<Self::Pre, TransactionValidityError> {
+            self.validate(who, call, info, len).map(|_| ())
+        }
+    }
+}
@@ -0,0 +1,376 @@
+//! Test runtime for the reward pallet.
+//!
+//! Most of the pallet's constants are `static` parameters, so a test can switch a
+//! feature on with e.g. `ClaimDeposit::set(5)` before building its externalities.
+
+use crate as pallet_archway_reward;
+use frame_support::{
+    parameter_types,
+    traits::{ConstU32, ConstU64, ConstU8, EnsureOrigin, FindAuthor, GenesisBuild, Hooks},
+    weights::{constants::RocksDbWeight, IdentityFee, Weight, WeightToFee},
+    PalletId,
+};
+use frame_system::{EnsureRoot, RawOrigin};
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    ConsensusEngineId, Perbill, Permill,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub type AccountId = u128;
+pub type Balance = u64;
+pub type BlockNumber = u64;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
+/// A signed account accepted by `FundingOrigin`.
+pub const FUNDER: AccountId = 10;
+/// The `TreasuryAccount`.
+pub const TREASURY: AccountId = 99;
+
+/// The free balance every endowed test account starts with.
+pub const INITIAL_BALANCE: Balance = 10_000;
+
+frame_support::construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system,
+        Balances: pallet_balances,
+        TransactionPayment: pallet_transaction_payment,
+        RewardPallet: pallet_archway_reward,
+    }
+);
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = RocksDbWeight;
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Index = u64;
+    type BlockNumber = BlockNumber;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = ConstU64<250>;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+parameter_types! {
+    pub static ExistentialDeposit: Balance = 1;
+}
+
+impl pallet_balances::Config for Test {
+    type Balance = Balance;
+    type DustRemoval = ();
+    type RuntimeEvent = RuntimeEvent;
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type MaxLocks = ConstU32<10>;
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+}
+
+/// Charges a flat fee of one per extrinsic for its weight, so fees stay small
+/// next to the test balances.
+pub struct UnitWeightFee;
+
+impl WeightToFee for UnitWeightFee {
+    type Balance = Balance;
+
+    fn weight_to_fee(_weight: &Weight) -> Balance {
+        1
+    }
+}
+
+/// Transaction fees and tips are handed to the reward pallet, as a runtime's
+/// `DealWithFees` would.
+impl pallet_transaction_payment::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type OnChargeTransaction = pallet_transaction_payment::CurrencyAdapter<Balances, RewardPallet>;
+    type OperationalFeeMultiplier = ConstU8<5>;
+    type WeightToFee = UnitWeightFee;
+    type LengthToFee = IdentityFee<Balance>;
+    type FeeMultiplierUpdate = ();
+}
+
+/// Reports `BlockAuthor` as the author of every block, standing in for a
+/// consensus engine's digest lookup.
+pub struct StaticAuthor;
+
+impl FindAuthor<AccountId> for StaticAuthor {
+    fn find_author<'a, I>(_digests: I) -> Option<AccountId>
+    where
+        I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+    {
+        BlockAuthor::get()
+    }
+}
+
+/// Accepts root, yielding `None`, and `FUNDER`, yielding the account.
+pub struct EnsureFunder;
+
+impl EnsureOrigin<RuntimeOrigin> for EnsureFunder {
+    type Success = Option<AccountId>;
+
+    fn try_origin(o: RuntimeOrigin) -> Result<Self::Success, RuntimeOrigin> {
+        o.into().and_then(|o| match o {
+            RawOrigin::Root => Ok(None),
+            RawOrigin::Signed(who) if who == FUNDER => Ok(Some(who)),
+            r => Err(RuntimeOrigin::from(r)),
+        })
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+        Ok(RawOrigin::Root.into())
+    }
+}
+
+parameter_types! {
+    pub static AuthorBonuses: Vec<(AccountId, Permill)> = Vec::new();
+}
+
+/// Reports the bonus listed for an author in `AuthorBonuses`, or none.
+pub struct MockAuthorBonus;
+
+impl pallet_archway_reward::AuthorBonus<AccountId> for MockAuthorBonus {
+    fn bonus(author: &AccountId) -> Permill {
+        AuthorBonuses::get()
+            .into_iter()
+            .find_map(|(who, bonus)| (who == *author).then_some(bonus))
+            .unwrap_or_default()
+    }
+}
+
+parameter_types! {
+    pub const RewardPalletId: PalletId = PalletId(*b"py/rewrd");
+    pub const TreasuryAccount: AccountId = TREASURY;
+}
+
+parameter_types! {
+    pub static BlockAuthor: Option<AccountId> = None;
+    pub static RewardPerBlock: Balance = 0;
+    pub static BlockRewardSplit: Permill = Permill::one();
+    pub static MinDonation: Balance = 1;
+    pub static MaxPoolSize: Balance = 0;
+    pub static EraLength: BlockNumber = 10;
+    pub static EraClaimLifetime: u32 = 0;
+    pub static PoolInflationPerEra: Balance = 0;
+    pub static LowPoolThreshold: Balance = 0;
+    pub static AllowUnallocatedClaims: bool = false;
+    pub static OpenClaimsWhenEmpty: bool = false;
+    pub static PayoutCallerReward: Balance = 0;
+    pub static ClaimCooldown: BlockNumber = 0;
+    pub static ClaimCapPerPeriod: Balance = 0;
+    pub static MinClaimAmount: Balance = 0;
+    pub static MaxClaimsPerBlock: u32 = 100;
+    pub static ClaimDeposit: Balance = 0;
+    pub static MaxClaimers: u32 = 16;
+    pub static DirectPayoutLimit: Balance = 0;
+    pub static AllocationLifetime: BlockNumber = 0;
+    pub static AutoPayoutThreshold: Balance = 0;
+    pub static MaxClaimedPerBlock: Balance = 0;
+    pub static PayPartialBlockReward: bool = false;
+    pub static AccrueBlockRewards: bool = false;
+    pub static HalvingInterval: BlockNumber = 0;
+    pub static Emission: crate::EmissionMode = crate::EmissionMode::Fixed;
+    pub static PoolEmissionRate: Perbill = Perbill::zero();
+    pub static PayAuthorsPerEra: bool = false;
+    pub static MaxAuthorRewardPerEra: Balance = 0;
+    pub static SmoothingWindow: BlockNumber = 0;
+    pub static DustDestination: Option<AccountId> = None;
+    pub static RewardEmptyBlocks: bool = true;
+    pub static RequireInitialization: bool = false;
+    pub static EmitBlockRewardEvents: bool = true;
+}
+
+impl pallet_archway_reward::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Currency = Balances;
+    type RewardPerBlock = RewardPerBlock;
+    type FindAuthor = StaticAuthor;
+    type BlockRewardSplit = BlockRewardSplit;
+    type TreasuryAccount = TreasuryAccount;
+    type PalletId = RewardPalletId;
+    type MinDonation = MinDonation;
+    type FundingOrigin = EnsureFunder;
+    type ParamsOrigin = EnsureRoot<AccountId>;
+    type PauseOrigin = EnsureRoot<AccountId>;
+    type MaxPoolSize = MaxPoolSize;
+    type OverflowHandler = ();
+    type MaxPoolIdLength = ConstU32<8>;
+    type MaxPools = ConstU32<4>;
+    type EraLength = EraLength;
+    type EraClaimLifetime = EraClaimLifetime;
+    type PoolInflationPerEra = PoolInflationPerEra;
+    type MaxPendingDrips = ConstU32<4>;
+    type MaxScheduledParamChanges = ConstU32<4>;
+    type LowPoolThreshold = LowPoolThreshold;
+    type AllowUnallocatedClaims = AllowUnallocatedClaims;
+    type MaxAllocationsPerCall = ConstU32<64>;
+    type PayoutCallerReward = PayoutCallerReward;
+    type ClaimCooldown = ClaimCooldown;
+    type ClaimCapPerPeriod = ClaimCapPerPeriod;
+    type ClaimPeriodLength = ConstU64<10>;
+    type MinClaimAmount = MinClaimAmount;
+    type MaxClaimsPerBlock = MaxClaimsPerBlock;
+    type MaxHistoryEntries = ConstU32<4>;
+    type MaxProofLen = ConstU32<8>;
+    type MaxMemoLength = ConstU32<32>;
+    type ClaimDeposit = ClaimDeposit;
+    type MaxClaimers = MaxClaimers;
+    type OpenClaimsWhenEmpty = OpenClaimsWhenEmpty;
+    type MaxClaimersPerCall = ConstU32<8>;
+    type MaxPayoutsPerCall = ConstU32<8>;
+    type DirectPayoutLimit = DirectPayoutLimit;
+    type MaxPayoutProposals = ConstU32<4>;
+    type PayoutProposalExpiry = ConstU64<10>;
+    type AllocationLifetime = AllocationLifetime;
+    type MaxSweepPerCall = ConstU32<8>;
+    type AutoPayoutThreshold = AutoPayoutThreshold;
+    type ClaimKeyRetention = ConstU64<10>;
+    type MaxPendingClaims = ConstU32<8>;
+    type ClaimsPerBlock = ConstU32<2>;
+    type MaxClaimedPerBlock = MaxClaimedPerBlock;
+    type PayPartialBlockReward = PayPartialBlockReward;
+    type AccrueBlockRewards = AccrueBlockRewards;
+    type HalvingInterval = HalvingInterval;
+    type EmissionMode = Emission;
+    type PoolEmissionRate = PoolEmissionRate;
+    type PayAuthorsPerEra = PayAuthorsPerEra;
+    type MaxEraAuthors = ConstU32<8>;
+    type MaxExcludedAuthors = ConstU32<4>;
+    type MaxOperators = ConstU32<4>;
+    type MaxTopUpOperators = ConstU32<4>;
+    type MaxAuthorRewardPerEra = MaxAuthorRewardPerEra;
+    type SmoothingWindow = SmoothingWindow;
+    type DustDestination = DustDestination;
+    type RewardEmptyBlocks = RewardEmptyBlocks;
+    type RequireInitialization = RequireInitialization;
+    type EmitBlockRewardEvents = EmitBlockRewardEvents;
+    type AuthorBonus = MockAuthorBonus;
+    type WeightInfo = ();
+    type Balance = Balance;
+}
+
+/// Builds test externalities. `ALICE`, `BOB`, `CHARLIE`, `FUNDER` and the treasury
+/// are endowed with `INITIAL_BALANCE`, and the pot with the existential deposit.
+/// The pallet's genesis only runs when a `pool` is given, and then `ALICE`, `BOB`
+/// and `CHARLIE` are whitelisted as claimers unless `claimers` says otherwise.
+#[derive(Default)]
+pub struct ExtBuilder {
+    pool: Option<Balance>,
+    balances: Vec<(AccountId, Balance)>,
+    claimers: Option<Vec<AccountId>>,
+}
+
+impl ExtBuilder {
+    /// Run the pallet's genesis with `initial_reward_pool` set to `amount`.
+    pub fn pool(mut self, amount: Balance) -> Self {
+        self.pool = Some(amount);
+        self
+    }
+
+    /// Endow `who` with `amount` on top of the default accounts.
+    pub fn balance(mut self, who: AccountId, amount: Balance) -> Self {
+        self.balances.push((who, amount));
+        self
+    }
+
+    /// Whitelist exactly `claimers`.
+    pub fn claimers(mut self, claimers: Vec<AccountId>) -> Self {
+        self.claimers = Some(claimers);
+        self
+    }
+
+    pub fn build(self) -> sp_io::TestExternalities {
+        let mut storage =
+            frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+        let mut balances: Vec<_> = [ALICE, BOB, CHARLIE, FUNDER, TREASURY]
+            .into_iter()
+            .map(|who| (who, INITIAL_BALANCE))
+            .collect();
+        balances.push((RewardPallet::account_id(), ExistentialDeposit::get()));
+        balances.extend(self.balances);
+        pallet_balances::GenesisConfig::<Test> { balances }
+            .assimilate_storage(&mut storage)
+            .unwrap();
+
+        let claimers = self.claimers.unwrap_or_else(|| match self.pool {
+            Some(_) => vec![ALICE, BOB, CHARLIE],
+            None => Vec::new(),
+        });
+        if let Some(initial_reward_pool) = self.pool {
+            pallet_archway_reward::GenesisConfig::<Test> {
+                initial_reward_pool,
+                _phantom: Default::default(),
+            }
+            .assimilate_storage(&mut storage)
+            .unwrap();
+        }
+
+        let mut ext = sp_io::TestExternalities::new(storage);
+        ext.register_extension(sp_keystore::KeystoreExt(std::sync::Arc::new(
+            sp_keystore::testing::KeyStore::new(),
+        )));
+        ext.execute_with(|| {
+            System::set_block_number(1);
+            for who in claimers {
+                pallet_archway_reward::Claimers::<Test>::insert(who, ());
+            }
+        });
+        ext
+    }
+}
+
+/// Externalities without the pallet's genesis, as the benchmarks expect.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    ExtBuilder::default().build()
+}
+
+/// Finalize the current block and initialize blocks up to and including `n`,
+/// running the pallet's hooks.
+pub fn run_to_block(n: BlockNumber) {
+    while System::block_number() < n {
+        let now = System::block_number();
+        RewardPallet::on_finalize(now);
+        System::on_finalize(now);
+        System::set_block_number(now + 1);
+        System::on_initialize(now + 1);
+        RewardPallet::on_initialize(now + 1);
+    }
+}
+
+/// The events emitted by the pallet so far, oldest first.
+pub fn reward_events() -> Vec<pallet_archway_reward::Event<Test>> {
+    System::events()
+        .into_iter()
+        .filter_map(|record| match record.event {
+            RuntimeEvent::RewardPallet(event) => Some(event),
+            _ => None,
+        })
+        .collect()
+}
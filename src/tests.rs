@@ -0,0 +1,4072 @@
+//! Unit tests for the reward pallet.
+
+use crate::{mock::*, *};
+use crate::{Error, Event};
+use codec::{Decode, Encode};
+use frame_support::{
+    assert_noop, assert_ok,
+    dispatch::{DispatchInfo, GetDispatchInfo, Pays},
+    traits::{
+        Currency, EnsureOrigin, ExistenceRequirement, Hooks, Imbalance, OnUnbalanced,
+        ReservableCurrency, WithdrawReasons,
+    },
+    weights::Weight,
+    BoundedVec,
+};
+use sp_core::{crypto::KeyTypeId, sr25519, H256};
+use sp_io::hashing::blake2_256;
+use sp_runtime::{
+    traits::{AccountIdConversion, Dispatchable, SignedExtension},
+    transaction_validity::InvalidTransaction,
+    ArithmeticError, DispatchError, DispatchResult, Perbill, Permill,
+};
+
+// ---------------------------------------------
+//  Mock Runtime
+// ---------------------------------------------
+
+#[test]
+fn genesis_pool_is_backed_by_the_pot() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_eq!(RewardPallet::reward_pool(), 1_000);
+        assert_eq!(RewardPallet::pot(), 1_000 + ExistentialDeposit::get());
+        assert_eq!(RewardPallet::pool_status().discrepancy, Discrepancy::Surplus(1));
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+#[test]
+fn mock_dispatches_signed_calls() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_noop!(RewardPallet::claim_all(RuntimeOrigin::none()), DispatchError::BadOrigin);
+        assert_noop!(
+            RewardPallet::claim_all(RuntimeOrigin::signed(ALICE)),
+            Error::<Test>::NothingToClaim
+        );
+
+        assert_ok!(RewardPallet::donate_to_pool(RuntimeOrigin::signed(ALICE), 10));
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - 10);
+        System::assert_last_event(
+            Event::PoolDonated { who: ALICE, amount: 10, new_total: 1_010 }.into(),
+        );
+    });
+}
+
+// ---------------------------------------------
+//  Hook Weights
+// ---------------------------------------------
+
+#[test]
+fn on_initialize_weight_depends_on_the_reward_branch() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        let payout = RewardPallet::on_initialize(2);
+
+        assert_ok!(RewardPallet::pause_block_rewards(RuntimeOrigin::root()));
+        let skip = RewardPallet::on_initialize(3);
+
+        assert_ne!(skip, Weight::zero());
+        assert!(payout.ref_time() > skip.ref_time());
+        assert_eq!(
+            payout - skip,
+            <() as WeightInfo>::on_initialize_reward(1) -
+                <() as WeightInfo>::on_initialize_reward(0)
+        );
+
+        // Paused, only the claim counters are reset and the skip is charged
+        assert_ok!(RewardPallet::pause(RuntimeOrigin::root()));
+        let paused = RewardPallet::on_initialize(4);
+        assert_ne!(paused, Weight::zero());
+        assert!(skip.ref_time() > paused.ref_time());
+    });
+}
+
+#[test]
+fn on_initialize_weight_counts_database_accesses() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        let db = <Test as frame_system::Config>::DbWeight::get();
+        let idle = RewardPallet::on_initialize(2);
+
+        // Processing a queued claim is charged as a full claim
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+        assert_ok!(RewardPallet::request_claim(RuntimeOrigin::signed(ALICE), 10));
+        let busy = RewardPallet::on_initialize(3);
+        assert_eq!(busy - idle, db.writes(1) + db.reads_writes(8, 8));
+
+        assert_ok!(RewardPallet::pause(RuntimeOrigin::root()));
+        assert_eq!(
+            RewardPallet::on_initialize(4),
+            db.reads_writes(1, 3) + <() as WeightInfo>::on_initialize_reward(0)
+        );
+    });
+}
+
+// ---------------------------------------------
+//  Dust
+// ---------------------------------------------
+
+#[test]
+fn payout_tip_below_dust_threshold_is_paid_once() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        PayoutCallerReward::set(5);
+        assert_ok!(RewardPallet::set_dust_threshold(RuntimeOrigin::root(), 10));
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+
+        assert_ok!(RewardPallet::payout_to(RuntimeOrigin::signed(BOB), ALICE));
+
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 95);
+        assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 5);
+        assert_eq!(RewardPallet::allocations(&ALICE), 0);
+        assert_eq!(RewardPallet::reward_pool(), 900);
+        assert_eq!(RewardPallet::total_allocated(), 0);
+        assert_eq!(RewardPallet::total_distributed(), 100);
+        assert!(DustCollectedThisBlock::<Test>::get().is_zero());
+        assert!(RewardPallet::accounting_consistent());
+        System::assert_last_event(
+            Event::PayoutTipPaid { caller: BOB, beneficiary: ALICE, amount: 5 }.into(),
+        );
+    });
+}
+
+#[test]
+fn claim_remainder_below_dust_threshold_returns_to_pool() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_ok!(RewardPallet::set_dust_threshold(RuntimeOrigin::root(), 10));
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 95, None));
+
+        assert_eq!(RewardPallet::allocations(&ALICE), 0);
+        assert_eq!(RewardPallet::reward_pool(), 905);
+        assert_eq!(RewardPallet::total_allocated(), 0);
+        assert_eq!(DustCollectedThisBlock::<Test>::get(), 5);
+        assert!(RewardPallet::accounting_consistent());
+
+        run_to_block(2);
+        assert!(reward_events().contains(&Event::DustCollected { amount: 5 }));
+    });
+}
+
+// ---------------------------------------------
+//  Pause
+// ---------------------------------------------
+
+#[test]
+fn era_boundary_during_pause_rolls_over_after_unpause() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_ok!(RewardPallet::pause(RuntimeOrigin::root()));
+        run_to_block(12);
+        assert_eq!(RewardPallet::current_era(), 0);
+
+        assert_ok!(RewardPallet::unpause(RuntimeOrigin::root()));
+        run_to_block(13);
+        assert_eq!(RewardPallet::current_era(), 1);
+        assert_eq!(RewardPallet::next_era_start(), 20);
+
+        // The following boundary stays aligned to the era length
+        run_to_block(19);
+        assert_eq!(RewardPallet::current_era(), 1);
+        run_to_block(20);
+        assert_eq!(RewardPallet::current_era(), 2);
+        assert_eq!(RewardPallet::next_era_start(), 30);
+    });
+}
+
+#[test]
+fn paused_pallet_rejects_calls_until_unpaused() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_noop!(RewardPallet::pause(RuntimeOrigin::signed(ALICE)), DispatchError::BadOrigin);
+        assert_ok!(RewardPallet::pause(RuntimeOrigin::root()));
+        System::assert_last_event(Event::Paused.into());
+
+        assert_noop!(
+            RewardPallet::donate_to_pool(RuntimeOrigin::signed(ALICE), 10),
+            Error::<Test>::PalletPaused
+        );
+
+        assert_ok!(RewardPallet::unpause(RuntimeOrigin::root()));
+        System::assert_last_event(Event::Unpaused.into());
+        assert_ok!(RewardPallet::donate_to_pool(RuntimeOrigin::signed(ALICE), 10));
+    });
+}
+
+// ---------------------------------------------
+//  Sub-Pools
+// ---------------------------------------------
+
+fn pool(id: &[u8]) -> PoolId<Test> {
+    PoolId::<Test>::truncate_from(id.to_vec())
+}
+
+/// Create the sub-pools `a` and `b`, funded with 500 each.
+fn two_funded_pools() {
+    for id in [pool(b"a"), pool(b"b")] {
+        assert_ok!(RewardPallet::create_pool(RuntimeOrigin::root(), id.clone()));
+        assert_ok!(RewardPallet::top_up_sub_pool(RuntimeOrigin::signed(FUNDER), id, 500));
+    }
+}
+
+#[test]
+fn claiming_from_one_sub_pool_never_touches_another() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        two_funded_pools();
+        let (a, b) = (pool(b"a"), pool(b"b"));
+        assert_ok!(RewardPallet::allocate_from_pool(RuntimeOrigin::root(), a.clone(), ALICE, 100));
+
+        assert_ok!(RewardPallet::claim_from_pool(RuntimeOrigin::signed(ALICE), a.clone(), 60));
+        assert_ok!(RewardPallet::claim_from_pool(RuntimeOrigin::signed(ALICE), a.clone(), 40));
+        System::assert_last_event(Event::SubPoolRewardClaimed(a.clone(), ALICE, 40).into());
+
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 100);
+        assert_eq!(
+            RewardPallet::pools(&a),
+            Some(PoolInfo { balance: 400, total_distributed: 100 })
+        );
+        assert_eq!(Balances::free_balance(RewardPallet::sub_pool_account(&a)), 400);
+        assert!(!PoolAllocations::<Test>::contains_key(&a, ALICE));
+
+        // Pool `b` and the default pool are untouched
+        assert_eq!(RewardPallet::pools(&b), Some(PoolInfo { balance: 500, total_distributed: 0 }));
+        assert_eq!(Balances::free_balance(RewardPallet::sub_pool_account(&b)), 500);
+        assert_eq!(RewardPallet::reward_pool(), 1_000);
+        assert_eq!(RewardPallet::total_distributed(), 0);
+
+        // The allocation in `a` does not entitle Alice to anything in `b`
+        assert_noop!(
+            RewardPallet::claim_from_pool(RuntimeOrigin::signed(ALICE), b, 1),
+            Error::<Test>::NothingToClaim
+        );
+    });
+}
+
+#[test]
+fn sub_pool_claims_are_gated_like_default_claims() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        two_funded_pools();
+        let a = pool(b"a");
+        assert_ok!(RewardPallet::allocate_from_pool(RuntimeOrigin::root(), a.clone(), ALICE, 100));
+
+        assert_noop!(
+            RewardPallet::claim_from_pool(RuntimeOrigin::signed(BOB), a.clone(), 10),
+            Error::<Test>::NothingToClaim
+        );
+        assert_noop!(
+            RewardPallet::claim_from_pool(RuntimeOrigin::signed(ALICE), a.clone(), 101),
+            Error::<Test>::ExceedsAllocation
+        );
+
+        assert_ok!(RewardPallet::ban_claimer(RuntimeOrigin::root(), ALICE, false));
+        assert_noop!(
+            RewardPallet::claim_from_pool(RuntimeOrigin::signed(ALICE), a.clone(), 10),
+            Error::<Test>::AccountBanned
+        );
+
+        assert_ok!(RewardPallet::pause(RuntimeOrigin::root()));
+        assert_noop!(
+            RewardPallet::claim_from_pool(RuntimeOrigin::signed(BOB), a, 10),
+            Error::<Test>::PalletPaused
+        );
+    });
+}
+
+#[test]
+fn unallocated_sub_pool_claims_draw_on_the_balance_when_allowed() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        two_funded_pools();
+        let a = pool(b"a");
+        AllowUnallocatedClaims::set(true);
+
+        assert_ok!(RewardPallet::claim_from_pool(RuntimeOrigin::signed(BOB), a.clone(), 50));
+        assert_eq!(RewardPallet::pools(&a), Some(PoolInfo { balance: 450, total_distributed: 50 }));
+        assert_noop!(
+            RewardPallet::claim_from_pool(RuntimeOrigin::signed(CHARLIE), a, 451),
+            Error::<Test>::InsufficientRewardPool
+        );
+    });
+}
+
+#[test]
+fn default_pool_id_addresses_the_reward_pool() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        let default = pool(DEFAULT_POOL_ID);
+        assert_eq!(
+            RewardPallet::pool_info(&default),
+            Some(PoolInfo { balance: 1_000, total_distributed: 0 })
+        );
+        assert_noop!(
+            RewardPallet::create_pool(RuntimeOrigin::root(), default.clone()),
+            Error::<Test>::PoolAlreadyExists
+        );
+
+        assert_ok!(RewardPallet::allocate_from_pool(
+            RuntimeOrigin::root(),
+            default.clone(),
+            ALICE,
+            100
+        ));
+        assert_eq!(RewardPallet::allocations(&ALICE), 100);
+        assert_ok!(RewardPallet::claim_from_pool(
+            RuntimeOrigin::signed(ALICE),
+            default.clone(),
+            100
+        ));
+
+        assert_eq!(
+            RewardPallet::pool_info(&default),
+            Some(PoolInfo { balance: 900, total_distributed: 100 })
+        );
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+#[test]
+fn sub_pools_are_bounded() {
+    new_test_ext().execute_with(|| {
+        for id in [b"a", b"b", b"c", b"d"] {
+            assert_ok!(RewardPallet::create_pool(RuntimeOrigin::root(), pool(id)));
+        }
+        assert_noop!(
+            RewardPallet::create_pool(RuntimeOrigin::root(), pool(b"e")),
+            Error::<Test>::TooManyPools
+        );
+        assert_noop!(
+            RewardPallet::create_pool(RuntimeOrigin::root(), pool(b"a")),
+            Error::<Test>::PoolAlreadyExists
+        );
+        assert_noop!(
+            RewardPallet::claim_from_pool(RuntimeOrigin::signed(ALICE), pool(b"z"), 1),
+            Error::<Test>::UnknownPool
+        );
+    });
+}
+
+// ---------------------------------------------
+//  Claimer Whitelist
+// ---------------------------------------------
+
+#[test]
+fn removed_claimer_can_no_longer_claim() {
+    ExtBuilder::default().pool(1_000).claimers(vec![ALICE]).build().execute_with(|| {
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), BOB, 100));
+
+        assert_noop!(
+            RewardPallet::claim_reward(RuntimeOrigin::signed(BOB), 50, None),
+            Error::<Test>::NotEligible
+        );
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 50, None));
+
+        assert_ok!(RewardPallet::remove_claimer(RuntimeOrigin::root(), ALICE));
+        System::assert_last_event(Event::ClaimerRemoved(ALICE).into());
+        assert!(!RewardPallet::is_claimer(&ALICE));
+
+        // Emptying the whitelist closes claims rather than opening them
+        assert_noop!(
+            RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 50, None),
+            Error::<Test>::NotEligible
+        );
+        assert_noop!(
+            RewardPallet::claim_reward(RuntimeOrigin::signed(BOB), 50, None),
+            Error::<Test>::NotEligible
+        );
+
+        assert_ok!(RewardPallet::add_claimer(RuntimeOrigin::root(), BOB));
+        System::assert_last_event(Event::ClaimerAdded(BOB).into());
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(BOB), 50, None));
+    });
+}
+
+#[test]
+fn empty_whitelist_is_open_only_when_configured() {
+    ExtBuilder::default().pool(1_000).claimers(vec![]).build().execute_with(|| {
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+        assert_noop!(
+            RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 50, None),
+            Error::<Test>::NotEligible
+        );
+
+        OpenClaimsWhenEmpty::set(true);
+        assert!(RewardPallet::is_claimer(&ALICE));
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 50, None));
+
+        // A non-empty whitelist is enforced either way
+        assert_ok!(RewardPallet::add_claimer(RuntimeOrigin::root(), BOB));
+        assert!(!RewardPallet::is_claimer(&ALICE));
+    });
+}
+
+#[test]
+fn claimer_whitelist_is_bounded() {
+    new_test_ext().execute_with(|| {
+        let max = MaxClaimers::get() as AccountId;
+        for who in 100..100 + max {
+            assert_ok!(RewardPallet::add_claimer(RuntimeOrigin::root(), who));
+        }
+        assert_noop!(
+            RewardPallet::add_claimer(RuntimeOrigin::root(), 100 + max),
+            Error::<Test>::TooManyClaimers
+        );
+        assert_noop!(
+            RewardPallet::add_claimer(RuntimeOrigin::root(), 100),
+            Error::<Test>::AlreadyClaimer
+        );
+        assert_noop!(
+            RewardPallet::add_claimer(RuntimeOrigin::signed(ALICE), ALICE),
+            DispatchError::BadOrigin
+        );
+
+        // Removing one frees a slot
+        assert_ok!(RewardPallet::remove_claimer(RuntimeOrigin::root(), 100));
+        assert_ok!(RewardPallet::add_claimer(RuntimeOrigin::root(), 100 + max));
+        assert_noop!(
+            RewardPallet::remove_claimer(RuntimeOrigin::root(), 100),
+            Error::<Test>::NotClaimer
+        );
+    });
+}
+
+#[test]
+fn bulk_claimer_updates_count_duplicates_once() {
+    new_test_ext().execute_with(|| {
+        let batch = |who: Vec<AccountId>| BoundedVec::try_from(who).unwrap();
+        assert_ok!(RewardPallet::add_claimer(RuntimeOrigin::root(), ALICE));
+
+        let added = batch(vec![100, 100, ALICE, 101]);
+        assert_ok!(RewardPallet::add_claimers(RuntimeOrigin::root(), added));
+        System::assert_last_event(Event::ClaimersAdded { added: 2, already_present: 2 }.into());
+        assert_eq!(Claimers::<Test>::count(), 3);
+
+        let removed = batch(vec![100, 100, 102]);
+        assert_ok!(RewardPallet::remove_claimers(RuntimeOrigin::root(), removed));
+        System::assert_last_event(Event::ClaimersRemoved { removed: 1, not_present: 2 }.into());
+        assert!(!Claimers::<Test>::contains_key(100));
+        assert!(Claimers::<Test>::contains_key(101));
+    });
+}
+
+#[test]
+fn bulk_claimer_additions_are_all_or_nothing() {
+    new_test_ext().execute_with(|| {
+        MaxClaimers::set(6);
+        let batch = BoundedVec::try_from((100..108).collect::<Vec<_>>()).unwrap();
+        assert_noop!(
+            RewardPallet::add_claimers(RuntimeOrigin::root(), batch.clone()),
+            Error::<Test>::TooManyClaimers
+        );
+        assert_noop!(
+            RewardPallet::add_claimers(RuntimeOrigin::signed(ALICE), batch),
+            DispatchError::BadOrigin
+        );
+        assert_eq!(Claimers::<Test>::count(), 0);
+    });
+}
+
+// ---------------------------------------------
+//  Claim Deposit
+// ---------------------------------------------
+
+#[test]
+fn claim_deposit_is_reserved_and_returned_on_success() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        ClaimDeposit::set(10);
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 50, None));
+
+        assert_eq!(Balances::reserved_balance(ALICE), 0);
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 50);
+        assert_eq!(RewardPallet::reward_pool(), 900);
+    });
+}
+
+#[test]
+fn claim_deposit_must_be_reservable() {
+    ExtBuilder::default().pool(1_000).balance(42, 5).claimers(vec![42]).build().execute_with(
+        || {
+            ClaimDeposit::set(10);
+            assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), 42, 100));
+
+            assert_noop!(
+                RewardPallet::claim_reward(RuntimeOrigin::signed(42), 50, None),
+                Error::<Test>::CannotReserveDeposit
+            );
+        },
+    );
+}
+
+#[test]
+fn claim_deposit_is_slashed_into_the_pool_when_ineligible() {
+    ExtBuilder::default().pool(1_000).claimers(vec![ALICE]).build().execute_with(|| {
+        ClaimDeposit::set(10);
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), BOB, 100));
+
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(BOB), 50, None));
+
+        System::assert_last_event(
+            Event::ClaimDepositSlashed {
+                who: BOB,
+                amount: 10,
+                error: Error::<Test>::NotEligible.into(),
+            }
+            .into(),
+        );
+        assert_eq!(Balances::reserved_balance(BOB), 0);
+        assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 10);
+        assert_eq!(RewardPallet::allocations(&BOB), 100);
+        assert_eq!(RewardPallet::reward_pool(), 910);
+        assert!(RewardPallet::accounting_consistent());
+
+        // Banned accounts forfeit the deposit too
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+        assert_ok!(RewardPallet::ban_claimer(RuntimeOrigin::root(), ALICE, false));
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 50, None));
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - 10);
+        assert_eq!(RewardPallet::reward_pool(), 820);
+    });
+}
+
+#[test]
+fn claim_deposit_is_returned_when_the_claim_fails_otherwise() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        ClaimDeposit::set(10);
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+
+        assert_noop!(
+            RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 101, None),
+            Error::<Test>::ExceedsAllocation
+        );
+        assert_noop!(
+            RewardPallet::claim_reward(RuntimeOrigin::signed(BOB), 50, None),
+            Error::<Test>::NothingToClaim
+        );
+        assert_eq!(Balances::reserved_balance(ALICE), 0);
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE);
+    });
+}
+
+// ---------------------------------------------
+//  Setter Events
+// ---------------------------------------------
+
+#[test]
+fn set_payout_destination_reports_the_previous_destination() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(RewardPallet::set_payout_destination(RuntimeOrigin::signed(ALICE), Some(BOB)));
+        System::assert_last_event(
+            Event::PayoutDestinationUpdated {
+                who: ALICE,
+                old: None,
+                new: Some(BOB),
+                changed_by: Some(ALICE),
+            }
+            .into(),
+        );
+
+        let old = RewardPallet::payout_destination(ALICE);
+        assert_ok!(RewardPallet::set_payout_destination(RuntimeOrigin::signed(ALICE), None));
+        System::assert_last_event(
+            Event::PayoutDestinationUpdated { who: ALICE, old, new: None, changed_by: Some(ALICE) }
+                .into(),
+        );
+        assert_eq!(old, Some(BOB));
+        assert_eq!(RewardPallet::payout_destination(ALICE), None);
+    });
+}
+
+#[test]
+fn set_claim_delegate_reports_the_previous_delegate() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(RewardPallet::set_claim_delegate(RuntimeOrigin::signed(ALICE), Some(BOB)));
+
+        let old = RewardPallet::claim_delegate(ALICE);
+        assert_ok!(RewardPallet::set_claim_delegate(RuntimeOrigin::signed(ALICE), Some(CHARLIE)));
+        System::assert_last_event(
+            Event::ClaimDelegateUpdated {
+                principal: ALICE,
+                old,
+                new: Some(CHARLIE),
+                changed_by: Some(ALICE),
+            }
+            .into(),
+        );
+        assert_eq!(old, Some(BOB));
+    });
+}
+
+#[test]
+fn set_author_payout_account_reports_the_previous_account() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(RewardPallet::set_author_payout_account(
+            RuntimeOrigin::signed(ALICE),
+            Some(BOB)
+        ));
+
+        let old = RewardPallet::author_payout_destination(ALICE);
+        assert_ok!(RewardPallet::set_author_payout_account(RuntimeOrigin::signed(ALICE), None));
+        System::assert_last_event(
+            Event::AuthorPayoutAccountUpdated {
+                author: ALICE,
+                old,
+                new: None,
+                changed_by: Some(ALICE),
+            }
+            .into(),
+        );
+        assert_eq!(old, Some(BOB));
+    });
+}
+
+#[test]
+fn admin_changes_report_the_previous_admin() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(RewardPallet::set_admin(RuntimeOrigin::root(), ALICE));
+        System::assert_last_event(
+            Event::PendingAdminUpdated { old: None, new: ALICE, changed_by: None }.into(),
+        );
+        assert_ok!(RewardPallet::accept_admin(RuntimeOrigin::signed(ALICE)));
+        System::assert_last_event(
+            Event::AdminUpdated { old: None, new: ALICE, changed_by: Some(ALICE) }.into(),
+        );
+
+        // A replaced proposal is reported as the old pending admin
+        assert_ok!(RewardPallet::set_admin(RuntimeOrigin::signed(ALICE), BOB));
+        let old = RewardPallet::pending_admin();
+        assert_ok!(RewardPallet::set_admin(RuntimeOrigin::signed(ALICE), CHARLIE));
+        System::assert_last_event(
+            Event::PendingAdminUpdated { old, new: CHARLIE, changed_by: Some(ALICE) }.into(),
+        );
+        assert_eq!(old, Some(BOB));
+
+        let old = RewardPallet::admin();
+        assert_noop!(
+            RewardPallet::accept_admin(RuntimeOrigin::signed(BOB)),
+            Error::<Test>::NotPendingAdmin
+        );
+        assert_ok!(RewardPallet::accept_admin(RuntimeOrigin::signed(CHARLIE)));
+        System::assert_last_event(
+            Event::AdminUpdated { old, new: CHARLIE, changed_by: Some(CHARLIE) }.into(),
+        );
+        assert_eq!(old, Some(ALICE));
+    });
+}
+
+#[test]
+fn parameter_setters_report_the_previous_value() {
+    new_test_ext().execute_with(|| {
+        let root = RuntimeOrigin::root;
+
+        assert_ok!(RewardPallet::set_min_pool_reserve(root(), 5));
+        let old = RewardPallet::min_pool_reserve();
+        assert_ok!(RewardPallet::set_min_pool_reserve(root(), 7));
+        System::assert_last_event(
+            Event::MinPoolReserveUpdated { old, new: 7, changed_by: None }.into(),
+        );
+        assert_eq!(old, 5);
+
+        assert_ok!(RewardPallet::set_eligibility_min_balance(root(), 5));
+        let old = RewardPallet::eligibility_min_balance();
+        assert_ok!(RewardPallet::set_eligibility_min_balance(root(), 7));
+        System::assert_last_event(
+            Event::EligibilityMinBalanceUpdated { old, new: 7, changed_by: None }.into(),
+        );
+        assert_eq!(old, 5);
+
+        assert_ok!(RewardPallet::set_era_budget(root(), 5));
+        let old = RewardPallet::era_budget();
+        assert_ok!(RewardPallet::set_era_budget(root(), 7));
+        System::assert_last_event(Event::EraBudgetUpdated { old, new: 7, changed_by: None }.into());
+        assert_eq!(old, 5);
+
+        assert_ok!(RewardPallet::set_reward_per_block(root(), Some(5)));
+        let old = RewardPallet::reward_per_block_override();
+        assert_ok!(RewardPallet::set_reward_per_block(root(), None));
+        System::assert_last_event(
+            Event::RewardPerBlockUpdated { old, new: None, changed_by: None }.into(),
+        );
+        assert_eq!(old, Some(5));
+
+        assert_ok!(RewardPallet::set_min_consensus_fee(root(), 5));
+        let old = RewardPallet::min_consensus_fee();
+        assert_ok!(RewardPallet::set_min_consensus_fee(root(), 7));
+        System::assert_last_event(
+            Event::MinConsensusFeeUpdated { old, new: 7, changed_by: None }.into(),
+        );
+        assert_eq!(old, 5);
+
+        let split = FeeSplit {
+            dev: Permill::from_percent(50),
+            validator: Permill::from_percent(30),
+            burn: Permill::from_percent(20),
+        };
+        let old = RewardPallet::params().fee_split;
+        assert_ok!(RewardPallet::set_fee_split(root(), split));
+        System::assert_last_event(
+            Event::FeeSplitUpdated { old, new: split, changed_by: None }.into(),
+        );
+        assert_eq!(old, FeeSplit::default());
+
+        assert_ok!(RewardPallet::set_claim_cooldown(root(), 5));
+        let old = RewardPallet::params().claim_cooldown;
+        assert_ok!(RewardPallet::set_claim_cooldown(root(), 7));
+        System::assert_last_event(
+            Event::ClaimCooldownUpdated { old, new: 7, changed_by: None }.into(),
+        );
+        assert_eq!(old, 5);
+
+        assert_ok!(RewardPallet::set_min_claim_amount(root(), 5));
+        let old = RewardPallet::params().min_claim_amount;
+        assert_ok!(RewardPallet::set_min_claim_amount(root(), 7));
+        System::assert_last_event(
+            Event::MinClaimAmountUpdated { old, new: 7, changed_by: None }.into(),
+        );
+        assert_eq!(old, 5);
+
+        assert_ok!(RewardPallet::set_dust_threshold(root(), 5));
+        let old = RewardPallet::params().dust_threshold;
+        assert_ok!(RewardPallet::set_dust_threshold(root(), 7));
+        System::assert_last_event(
+            Event::DustThresholdUpdated { old, new: 7, changed_by: None }.into(),
+        );
+        assert_eq!(old, 5);
+
+        assert_ok!(RewardPallet::set_top_up_allowance(root(), BOB, 5));
+        let old = RewardPallet::top_up_allowance(BOB).unwrap_or_default();
+        assert_ok!(RewardPallet::set_top_up_allowance(root(), BOB, 0));
+        System::assert_last_event(
+            Event::TopUpAllowanceUpdated { who: BOB, old, new: 0, changed_by: None }.into(),
+        );
+        assert_eq!(old, 5);
+    });
+}
+
+#[test]
+fn setters_attribute_the_change_to_a_signed_admin() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(RewardPallet::set_admin(RuntimeOrigin::root(), ALICE));
+        assert_ok!(RewardPallet::accept_admin(RuntimeOrigin::signed(ALICE)));
+
+        assert_ok!(RewardPallet::set_dust_threshold(RuntimeOrigin::signed(ALICE), 5));
+        System::assert_last_event(
+            Event::DustThresholdUpdated { old: 0, new: 5, changed_by: Some(ALICE) }.into(),
+        );
+    });
+}
+
+// ---------------------------------------------
+//  Funding
+// ---------------------------------------------
+
+#[test]
+fn top_up_moves_funds_into_the_pot_without_minting() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        let issuance = Balances::total_issuance();
+        let pot = RewardPallet::pot();
+
+        assert_ok!(RewardPallet::top_up_pool(RuntimeOrigin::signed(FUNDER), 100));
+
+        assert_eq!(Balances::total_issuance(), issuance);
+        assert_eq!(RewardPallet::pot(), pot + 100);
+        assert_eq!(Balances::free_balance(FUNDER), INITIAL_BALANCE - 100);
+        assert_eq!(RewardPallet::reward_pool(), 1_100);
+        assert!(RewardPallet::accounting_consistent());
+        System::assert_last_event(Event::RewardPoolIncreased(Some(FUNDER), 100, 1_100).into());
+    });
+}
+
+#[test]
+fn root_top_up_is_paid_by_the_treasury() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_ok!(RewardPallet::top_up_pool(RuntimeOrigin::root(), 100));
+
+        assert_eq!(Balances::free_balance(TREASURY), INITIAL_BALANCE - 100);
+        assert_eq!(RewardPallet::reward_pool(), 1_100);
+        System::assert_last_event(Event::RewardPoolIncreased(None, 100, 1_100).into());
+
+        // The treasury is never reaped
+        assert_noop!(
+            RewardPallet::top_up_pool(RuntimeOrigin::root(), INITIAL_BALANCE - 100),
+            pallet_balances::Error::<Test>::KeepAlive
+        );
+        assert_ok!(RewardPallet::top_up_pool(
+            RuntimeOrigin::root(),
+            INITIAL_BALANCE - 100 - ExistentialDeposit::get()
+        ));
+        assert_eq!(Balances::free_balance(TREASURY), ExistentialDeposit::get());
+    });
+}
+
+#[test]
+fn admin_top_up_is_attributed_to_the_admin() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_ok!(RewardPallet::set_admin(RuntimeOrigin::root(), ALICE));
+        assert_ok!(RewardPallet::accept_admin(RuntimeOrigin::signed(ALICE)));
+
+        assert_ok!(RewardPallet::top_up_pool(RuntimeOrigin::signed(ALICE), 100));
+
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - 100);
+        assert_eq!(Balances::free_balance(TREASURY), INITIAL_BALANCE);
+        System::assert_last_event(Event::RewardPoolIncreased(Some(ALICE), 100, 1_100).into());
+    });
+}
+
+#[test]
+fn top_up_requires_a_funder_that_can_pay() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_noop!(
+            RewardPallet::top_up_pool(RuntimeOrigin::signed(ALICE), 100),
+            Error::<Test>::BadOriginForTopUp
+        );
+        assert_noop!(
+            RewardPallet::top_up_pool(RuntimeOrigin::signed(FUNDER), INITIAL_BALANCE + 1),
+            Error::<Test>::InsufficientFunderBalance
+        );
+    });
+}
+
+#[test]
+fn donations_add_to_the_genesis_pool_up_to_the_cap() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        MaxPoolSize::set(1_050);
+
+        assert_ok!(RewardPallet::donate_to_pool(RuntimeOrigin::signed(BOB), 50));
+        assert_eq!(RewardPallet::reward_pool(), 1_050);
+        assert_eq!(RewardPallet::pot(), 1_050 + ExistentialDeposit::get());
+        assert!(RewardPallet::accounting_consistent());
+
+        assert_noop!(
+            RewardPallet::donate_to_pool(RuntimeOrigin::signed(BOB), 1),
+            Error::<Test>::PoolCapExceeded
+        );
+    });
+}
+
+#[test]
+fn donations_below_the_minimum_are_rejected() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        MinDonation::set(5);
+
+        assert_noop!(
+            RewardPallet::donate_to_pool(RuntimeOrigin::signed(BOB), 0),
+            Error::<Test>::InvalidDonationAmount
+        );
+        assert_noop!(
+            RewardPallet::donate_to_pool(RuntimeOrigin::signed(BOB), 4),
+            Error::<Test>::DonationBelowMinimum
+        );
+        assert_noop!(
+            RewardPallet::donate_to_pool(RuntimeOrigin::signed(BOB), INITIAL_BALANCE + 1),
+            pallet_balances::Error::<Test>::InsufficientBalance
+        );
+    });
+}
+
+/// Withdraw `amount` from `who` as a transaction fee would.
+fn fee(who: AccountId, amount: Balance) -> NegativeImbalanceOf<Test> {
+    Balances::withdraw(&who, amount, WithdrawReasons::FEE, ExistenceRequirement::KeepAlive)
+        .unwrap()
+}
+
+#[test]
+fn captured_fees_land_in_the_pool() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        let issuance = Balances::total_issuance();
+
+        RewardPallet::on_unbalanced(fee(ALICE, 100));
+
+        assert_eq!(RewardPallet::reward_pool(), 1_100);
+        assert_eq!(RewardPallet::pot(), 1_100 + ExistentialDeposit::get());
+        assert_eq!(Balances::total_issuance(), issuance);
+        assert!(RewardPallet::accounting_consistent());
+        System::assert_last_event(
+            Event::FeesCaptured { dev: 100, validator: 0, burned: 0, treasury: 0 }.into(),
+        );
+    });
+}
+
+#[test]
+fn transaction_fees_are_captured_through_transaction_payment() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        use pallet_transaction_payment::ChargeTransactionPayment;
+
+        let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+        let info = call.get_dispatch_info();
+        let len = 10;
+        let issuance = Balances::total_issuance();
+
+        // A flat weight fee of one for the base extrinsic and one for the call, the
+        // length and a tip of 5
+        let pre = ChargeTransactionPayment::<Test>::from(5)
+            .pre_dispatch(&ALICE, &call, &info, len)
+            .unwrap();
+        let post_info = call.dispatch(RuntimeOrigin::signed(ALICE)).unwrap();
+        assert_ok!(ChargeTransactionPayment::<Test>::post_dispatch(
+            Some(pre),
+            &info,
+            &post_info,
+            len,
+            &Ok(())
+        ));
+
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - 17);
+        assert_eq!(RewardPallet::reward_pool(), 1_017);
+        assert_eq!(Balances::total_issuance(), issuance);
+        System::assert_has_event(
+            Event::FeesCaptured { dev: 17, validator: 0, burned: 0, treasury: 0 }.into(),
+        );
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+#[test]
+fn zero_fees_leave_storage_untouched() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        let root = sp_io::storage::root(sp_runtime::StateVersion::V1);
+
+        RewardPallet::on_unbalanced(NegativeImbalanceOf::<Test>::zero());
+
+        assert_eq!(sp_io::storage::root(sp_runtime::StateVersion::V1), root);
+        assert!(reward_events().is_empty());
+    });
+}
+
+#[test]
+fn captured_fees_are_split_and_the_treasury_takes_the_rounding() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        BlockAuthor::set(Some(CHARLIE));
+        let third = Permill::from_percent(33);
+        let split = FeeSplit { dev: third, validator: third, burn: third };
+        assert_ok!(RewardPallet::set_fee_split(RuntimeOrigin::root(), split));
+        let issuance = Balances::total_issuance();
+
+        RewardPallet::on_unbalanced(fee(ALICE, 101));
+
+        System::assert_last_event(
+            Event::FeesCaptured { dev: 33, validator: 33, burned: 33, treasury: 2 }.into(),
+        );
+        assert_eq!(RewardPallet::reward_pool(), 1_033);
+        assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 33);
+        assert_eq!(Balances::free_balance(TREASURY), INITIAL_BALANCE + 2);
+        assert_eq!(Balances::total_issuance(), issuance - 33);
+        assert!(RewardPallet::accounting_consistent());
+
+        // Parts summing to over 100% are rejected
+        let split = FeeSplit { burn: Permill::from_percent(40), ..split };
+        assert_noop!(
+            RewardPallet::set_fee_split(RuntimeOrigin::root(), split),
+            Error::<Test>::InvalidParams
+        );
+    });
+}
+
+#[test]
+fn an_all_burn_fee_split_destroys_the_fees() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        BlockAuthor::set(Some(CHARLIE));
+        let split =
+            FeeSplit { dev: Permill::zero(), validator: Permill::zero(), burn: Permill::one() };
+        assert_ok!(RewardPallet::set_fee_split(RuntimeOrigin::root(), split));
+        let issuance = Balances::total_issuance();
+
+        RewardPallet::on_unbalanced(fee(ALICE, 100));
+
+        System::assert_last_event(
+            Event::FeesCaptured { dev: 0, validator: 0, burned: 100, treasury: 0 }.into(),
+        );
+        assert_eq!(RewardPallet::reward_pool(), 1_000);
+        assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE);
+        assert_eq!(Balances::free_balance(TREASURY), INITIAL_BALANCE);
+        assert_eq!(Balances::total_issuance(), issuance - 100);
+    });
+}
+
+#[test]
+fn force_set_reward_pool_is_bounded_by_the_pot() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_noop!(
+            RewardPallet::force_set_reward_pool(RuntimeOrigin::signed(FUNDER), 500),
+            DispatchError::BadOrigin
+        );
+
+        assert_ok!(RewardPallet::force_set_reward_pool(RuntimeOrigin::root(), 500));
+        System::assert_last_event(Event::RewardPoolForceSet { old: 1_000, new: 500 }.into());
+        assert_eq!(RewardPallet::reward_pool(), 500);
+        assert!(RewardPallet::accounting_consistent());
+
+        // The pot's surplus, here the existential deposit, can be claimed back too
+        let pot = RewardPallet::pot();
+        assert_ok!(RewardPallet::force_set_reward_pool(RuntimeOrigin::root(), pot));
+        assert!(RewardPallet::accounting_consistent());
+        assert_noop!(
+            RewardPallet::force_set_reward_pool(RuntimeOrigin::root(), pot + 1),
+            Error::<Test>::InsufficientPotBalance
+        );
+    });
+}
+
+#[test]
+fn withdraw_from_pool_returns_funds_without_distributing_them() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_noop!(
+            RewardPallet::withdraw_from_pool(RuntimeOrigin::signed(ALICE), 100, BOB),
+            DispatchError::BadOrigin
+        );
+
+        assert_ok!(RewardPallet::withdraw_from_pool(RuntimeOrigin::signed(FUNDER), 100, BOB));
+        System::assert_last_event(
+            Event::RewardPoolDecreased { amount: 100, beneficiary: BOB, remaining: 900 }.into(),
+        );
+        assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 100);
+        assert_eq!(RewardPallet::reward_pool(), 900);
+        assert_eq!(RewardPallet::total_distributed(), 0);
+        assert!(RewardPallet::accounting_consistent());
+
+        assert_noop!(
+            RewardPallet::withdraw_from_pool(RuntimeOrigin::root(), 901, BOB),
+            Error::<Test>::InsufficientRewardPool
+        );
+    });
+}
+
+#[test]
+fn pool_accounting_identity_holds_across_flows() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_eq!(RewardPallet::total_topped_up(), 1_000);
+
+        assert_ok!(RewardPallet::top_up_pool(RuntimeOrigin::signed(FUNDER), 200));
+        assert_ok!(RewardPallet::donate_to_pool(RuntimeOrigin::signed(BOB), 50));
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 300));
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 100, None));
+        assert_ok!(RewardPallet::withdraw_from_pool(RuntimeOrigin::root(), 50, TREASURY));
+        assert_ok!(RewardPallet::burn_pool(RuntimeOrigin::root(), 50));
+
+        assert_eq!(RewardPallet::total_topped_up(), 1_150);
+        assert_eq!(RewardPallet::reward_pool(), 850);
+        assert_eq!(RewardPallet::total_allocated(), 200);
+        assert_eq!(RewardPallet::total_distributed(), 100);
+        assert!(RewardPallet::accounting_consistent());
+
+        // A corrupted total breaks the identity
+        TotalToppedUp::<Test>::put(1_151);
+        assert!(!RewardPallet::accounting_consistent());
+        TotalToppedUp::<Test>::put(1_150);
+        RewardPool::<Test>::put(849);
+        assert!(!RewardPallet::accounting_consistent());
+    });
+}
+
+#[test]
+fn pool_cap_accepts_exactly_the_cap() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        MaxPoolSize::set(1_100);
+
+        assert_ok!(RewardPallet::top_up_pool(RuntimeOrigin::signed(FUNDER), 100));
+        assert_eq!(RewardPallet::reward_pool(), 1_100);
+        assert_eq!(RewardPallet::remaining_capacity(), Some(0));
+
+        assert_noop!(
+            RewardPallet::top_up_pool(RuntimeOrigin::signed(FUNDER), 1),
+            Error::<Test>::PoolCapExceeded
+        );
+    });
+}
+
+#[test]
+fn zero_pool_cap_is_unlimited() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_eq!(RewardPallet::remaining_capacity(), None);
+        assert_ok!(RewardPallet::top_up_pool(RuntimeOrigin::signed(FUNDER), INITIAL_BALANCE));
+        assert_eq!(RewardPallet::reward_pool(), 1_000 + INITIAL_BALANCE);
+    });
+}
+
+#[test]
+fn captured_fees_above_the_cap_go_to_the_overflow_handler() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        MaxPoolSize::set(1_060);
+        let issuance = Balances::total_issuance();
+
+        RewardPallet::on_unbalanced(fee(ALICE, 100));
+
+        assert_eq!(RewardPallet::reward_pool(), 1_060);
+        assert!(reward_events().contains(&Event::PoolOverflow(40)));
+        // The mock's `OverflowHandler` drops, and so burns, the excess
+        assert_eq!(Balances::total_issuance(), issuance - 40);
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+#[test]
+fn burn_pool_destroys_the_funds() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        let issuance = Balances::total_issuance();
+        assert_noop!(
+            RewardPallet::burn_pool(RuntimeOrigin::signed(ALICE), 100),
+            DispatchError::BadOrigin
+        );
+
+        assert_ok!(RewardPallet::burn_pool(RuntimeOrigin::signed(FUNDER), 100));
+        System::assert_last_event(Event::PoolBurned(100, 900).into());
+        assert_eq!(Balances::total_issuance(), issuance - 100);
+        assert_eq!(RewardPallet::pot(), 900 + ExistentialDeposit::get());
+
+        assert_noop!(
+            RewardPallet::burn_pool(RuntimeOrigin::root(), 901),
+            Error::<Test>::InsufficientRewardPool
+        );
+        assert_ok!(RewardPallet::burn_pool(RuntimeOrigin::root(), 900));
+        assert_eq!(RewardPallet::reward_pool(), 0);
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+#[test]
+fn fund_pool_absorbs_a_slash() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        let issuance = Balances::total_issuance();
+        assert_ok!(Balances::reserve(&ALICE, 100));
+        let (slashed, _) = Balances::slash_reserved(&ALICE, 100);
+
+        assert_eq!(RewardPallet::fund_pool(slashed), 100);
+
+        System::assert_last_event(Event::RewardPoolIncreased(None, 100, 1_100).into());
+        assert_eq!(Balances::total_balance(&ALICE), INITIAL_BALANCE - 100);
+        assert_eq!(RewardPallet::pot(), 1_100 + ExistentialDeposit::get());
+        assert_eq!(Balances::total_issuance(), issuance);
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+#[test]
+fn fund_pool_saturates_at_the_cap_and_never_fails() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        MaxPoolSize::set(1_000);
+        assert_ok!(Balances::reserve(&ALICE, 100));
+        let (slashed, _) = Balances::slash_reserved(&ALICE, 100);
+
+        assert_eq!(RewardPallet::fund_pool(slashed), 0);
+        assert_eq!(RewardPallet::reward_pool(), 1_000);
+        assert!(reward_events().contains(&Event::PoolOverflow(100)));
+    });
+}
+
+// ---------------------------------------------
+//  Claims
+// ---------------------------------------------
+
+#[test]
+fn claims_to_fresh_accounts_must_reach_the_existential_deposit() {
+    ExistentialDeposit::set(10);
+    ExtBuilder::default().pool(1_000).claimers(vec![ALICE, 50, 51]).build().execute_with(|| {
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), 50, 100));
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), 51, 100));
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+
+        // A brand-new account would be dusted
+        assert_noop!(
+            RewardPallet::claim_reward(RuntimeOrigin::signed(50), 9, None),
+            Error::<Test>::BelowExistentialDeposit
+        );
+
+        // Exactly the existential deposit creates it
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(51), 10, None));
+        assert_eq!(Balances::free_balance(51), 10);
+
+        // Existing accounts can receive any amount
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 1, None));
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 1);
+        assert_eq!(RewardPallet::total_distributed(), 11);
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+// ---------------------------------------------
+//  Reconciliation
+// ---------------------------------------------
+
+#[test]
+fn pot_account_is_derived_from_the_pallet_id() {
+    new_test_ext().execute_with(|| {
+        let pot: AccountId = RewardPalletId::get().into_account_truncating();
+        assert_eq!(RewardPallet::account_id(), pot);
+        assert_ne!(RewardPallet::sub_pool_account(&PoolId::<Test>::default()), pot);
+    });
+}
+
+#[test]
+fn pool_status_reports_surplus_and_deficit() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        let pot = RewardPallet::account_id();
+        assert_ok!(Balances::transfer(RuntimeOrigin::signed(ALICE), pot, 99));
+        let status = RewardPallet::pool_status();
+        assert_eq!(status.recorded_pool, 1_000);
+        assert_eq!(status.pot_free_balance, 1_100);
+        assert_eq!(status.discrepancy, Discrepancy::Surplus(100));
+
+        // Allocations are backed by the pot too
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+        assert_eq!(RewardPallet::pool_status().discrepancy, Discrepancy::Surplus(100));
+
+        RewardPool::<Test>::put(1_000);
+        assert_eq!(RewardPallet::pool_status().discrepancy, Discrepancy::None);
+        RewardPool::<Test>::put(1_010);
+        assert_eq!(RewardPallet::pool_status().discrepancy, Discrepancy::Deficit(10));
+    });
+}
+
+#[test]
+fn sync_pool_absorbs_direct_transfers_up_to_the_cap() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        MaxPoolSize::set(1_050);
+        let pot = RewardPallet::account_id();
+        assert_ok!(Balances::transfer(RuntimeOrigin::signed(ALICE), pot, 100));
+
+        assert_ok!(RewardPallet::sync_pool(RuntimeOrigin::signed(BOB)));
+        System::assert_last_event(Event::RewardPoolIncreased(None, 50, 1_050).into());
+        assert_eq!(RewardPallet::reward_pool(), 1_050);
+        assert!(RewardPallet::accounting_consistent());
+
+        // Full, so a second sync is a no-op
+        let events = System::events().len();
+        assert_ok!(RewardPallet::sync_pool(RuntimeOrigin::signed(BOB)));
+        assert_eq!(System::events().len(), events);
+        assert_eq!(RewardPallet::reward_pool(), 1_050);
+    });
+}
+
+// ---------------------------------------------
+//  Inflation and Drips
+// ---------------------------------------------
+
+#[test]
+fn pool_is_inflated_once_per_era() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        PoolInflationPerEra::set(100);
+        let issuance = Balances::total_issuance();
+
+        run_to_block(9);
+        assert_eq!(RewardPallet::reward_pool(), 1_000);
+
+        run_to_block(10);
+        assert_eq!(RewardPallet::current_era(), 1);
+        assert_eq!(RewardPallet::reward_pool(), 1_100);
+        assert_eq!(Balances::total_issuance(), issuance + 100);
+        assert!(reward_events().contains(&Event::PoolInflated(1, 100)));
+        assert!(RewardPallet::accounting_consistent());
+
+        run_to_block(19);
+        assert_eq!(RewardPallet::reward_pool(), 1_100);
+        run_to_block(20);
+        assert_eq!(RewardPallet::reward_pool(), 1_200);
+    });
+}
+
+#[test]
+fn inflation_never_mints_past_the_cap() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        PoolInflationPerEra::set(100);
+        MaxPoolSize::set(1_030);
+
+        run_to_block(10);
+        assert_eq!(RewardPallet::reward_pool(), 1_030);
+        assert!(reward_events().contains(&Event::PoolInflated(1, 30)));
+
+        run_to_block(20);
+        assert_eq!(RewardPallet::reward_pool(), 1_030);
+        assert!(!reward_events().contains(&Event::PoolInflated(2, 0)));
+    });
+}
+
+#[test]
+fn drips_release_into_the_pool_block_by_block() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_ok!(RewardPallet::schedule_top_up(RuntimeOrigin::signed(FUNDER), 100, 40));
+        System::assert_last_event(Event::DripScheduled { id: 0, total: 100, per_block: 40 }.into());
+        assert_eq!(Balances::free_balance(RewardPallet::drip_account()), 100);
+        assert_eq!(RewardPallet::reward_pool(), 1_000);
+
+        run_to_block(2);
+        assert_eq!(RewardPallet::reward_pool(), 1_040);
+        run_to_block(3);
+        assert_eq!(RewardPallet::reward_pool(), 1_080);
+        run_to_block(4);
+        assert_eq!(RewardPallet::reward_pool(), 1_100);
+        assert!(reward_events().contains(&Event::DripReleased { id: 0, amount: 20, remaining: 0 }));
+        assert!(RewardPallet::pending_drips().is_empty());
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+#[test]
+fn cancelled_drips_refund_the_remainder() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_noop!(
+            RewardPallet::schedule_top_up(RuntimeOrigin::signed(FUNDER), 100, 0),
+            Error::<Test>::InvalidDripSchedule
+        );
+        assert_ok!(RewardPallet::schedule_top_up(RuntimeOrigin::signed(FUNDER), 100, 40));
+        run_to_block(2);
+
+        assert_ok!(RewardPallet::cancel_top_up(RuntimeOrigin::root(), 0, FUNDER));
+        System::assert_last_event(
+            Event::DripCancelled { id: 0, refunded: 60, refund_to: FUNDER }.into(),
+        );
+        assert_eq!(Balances::free_balance(FUNDER), INITIAL_BALANCE - 40);
+        assert_noop!(
+            RewardPallet::cancel_top_up(RuntimeOrigin::root(), 0, FUNDER),
+            Error::<Test>::UnknownDrip
+        );
+
+        run_to_block(3);
+        assert_eq!(RewardPallet::reward_pool(), 1_040);
+    });
+}
+
+#[test]
+fn manual_claims_cannot_breach_the_reserve() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_ok!(RewardPallet::set_min_pool_reserve(RuntimeOrigin::root(), 900));
+
+        assert_noop!(
+            RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 101),
+            Error::<Test>::WouldBreachReserve
+        );
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+
+        AllowUnallocatedClaims::set(true);
+        assert_noop!(
+            RewardPallet::claim_reward(RuntimeOrigin::signed(BOB), 1, None),
+            Error::<Test>::WouldBreachReserve
+        );
+    });
+}
+
+#[test]
+fn block_rewards_may_dip_into_the_reserve() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_ok!(RewardPallet::set_min_pool_reserve(RuntimeOrigin::root(), 1_000));
+        RewardPerBlock::set(50);
+        BlockAuthor::set(Some(CHARLIE));
+
+        run_to_block(2);
+        assert_eq!(RewardPallet::reward_pool(), 950);
+        assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 50);
+    });
+}
+
+// ---------------------------------------------
+//  Genesis
+// ---------------------------------------------
+
+#[test]
+fn genesis_endows_the_pot_so_the_first_block_can_pay() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert!(RewardPallet::initialized());
+        assert_eq!(RewardPallet::total_topped_up(), 1_000);
+        assert_eq!(RewardPallet::total_distributed(), 0);
+
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 1_000));
+        assert_ok!(RewardPallet::claim_all(RuntimeOrigin::signed(ALICE)));
+
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 1_000);
+        assert_eq!(RewardPallet::reward_pool(), 0);
+        assert_eq!(RewardPallet::pot(), ExistentialDeposit::get());
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+// ---------------------------------------------
+//  Statistics
+// ---------------------------------------------
+
+#[test]
+fn stats_count_claims_and_block_rewards() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_eq!(RewardPallet::stats().last_distribution_block, None);
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), BOB, 100));
+
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 30, None));
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 30, None));
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(BOB), 40, None));
+
+        RewardPerBlock::set(10);
+        BlockAuthor::set(Some(CHARLIE));
+        run_to_block(3);
+
+        assert_eq!(
+            RewardPallet::stats(),
+            RewardStats {
+                current_pool: 780,
+                total_distributed: 120,
+                total_topped_up: 1_000,
+                unique_claimants: 2,
+                last_distribution_block: Some(2),
+            }
+        );
+    });
+}
+
+// ---------------------------------------------
+//  Low Pool Warning
+// ---------------------------------------------
+
+#[test]
+fn low_pool_warning_latches_until_recovered() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        LowPoolThreshold::set(950);
+        RewardPerBlock::set(30);
+        BlockAuthor::set(Some(CHARLIE));
+        let low_events = || {
+            reward_events()
+                .into_iter()
+                .filter(|e| matches!(e, Event::RewardPoolLow { .. }))
+                .count()
+        };
+
+        run_to_block(2);
+        assert_eq!(low_events(), 0);
+        assert!(!RewardPallet::pool_low());
+
+        // Crossing down
+        run_to_block(3);
+        assert!(reward_events().contains(&Event::RewardPoolLow { remaining: 940 }));
+        assert!(RewardPallet::pool_low());
+
+        // Staying below
+        run_to_block(5);
+        assert_eq!(RewardPallet::reward_pool(), 880);
+        assert_eq!(low_events(), 1);
+
+        // Recovering
+        assert_ok!(RewardPallet::top_up_pool(RuntimeOrigin::signed(FUNDER), 100));
+        assert!(reward_events().contains(&Event::RewardPoolRecovered { pool: 980 }));
+        assert!(!RewardPallet::pool_low());
+    });
+}
+
+// ---------------------------------------------
+//  Allocations
+// ---------------------------------------------
+
+#[test]
+fn claims_are_paid_from_the_allocation() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_noop!(
+            RewardPallet::allocate_reward(RuntimeOrigin::signed(ALICE), ALICE, 100),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+        System::assert_last_event(Event::RewardAllocated(ALICE, 100).into());
+        assert_eq!(RewardPallet::reward_pool(), 900);
+        assert_eq!(RewardPallet::total_allocated(), 100);
+
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 60, None));
+        System::assert_last_event(
+            Event::RewardClaimed {
+                claim_id: 0,
+                who: ALICE,
+                amount: 60,
+                lifetime_claimed: 60,
+                remaining_allocation: 40,
+                memo: None,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 60);
+        assert_eq!(RewardPallet::allocations(&ALICE), 40);
+        assert_eq!(RewardPallet::total_allocated(), 40);
+        assert_eq!(RewardPallet::reward_pool(), 900);
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+#[test]
+fn unallocated_claims_are_rejected_unless_allowed() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_noop!(
+            RewardPallet::claim_reward(RuntimeOrigin::signed(BOB), 10, None),
+            Error::<Test>::NothingToClaim
+        );
+
+        AllowUnallocatedClaims::set(true);
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(BOB), 10, None));
+        assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 10);
+        assert_eq!(RewardPallet::reward_pool(), 990);
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+#[test]
+fn allocate_many_writes_a_full_batch() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        let mut batch: Vec<_> = (100..163).map(|who| (who, 10)).collect();
+        // Repeated accounts accumulate
+        batch.push((100, 5));
+        let batch = BoundedVec::try_from(batch).unwrap();
+
+        assert_ok!(RewardPallet::allocate_many(RuntimeOrigin::root(), batch));
+
+        System::assert_last_event(Event::RewardsAllocated { count: 64, total: 635 }.into());
+        assert_eq!(RewardPallet::allocations(&100), 15);
+        assert_eq!(RewardPallet::allocations(&162), 10);
+        assert_eq!(RewardPallet::reward_pool(), 365);
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+#[test]
+fn allocate_many_is_all_or_nothing() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        let batch = BoundedVec::try_from(vec![(ALICE, 600), (BOB, 401)]).unwrap();
+        assert_noop!(
+            RewardPallet::allocate_many(RuntimeOrigin::root(), batch),
+            Error::<Test>::InsufficientRewardPool
+        );
+
+        let batch = BoundedVec::try_from(vec![(ALICE, Balance::MAX), (BOB, 1)]).unwrap();
+        assert_noop!(
+            RewardPallet::allocate_many(RuntimeOrigin::root(), batch),
+            ArithmeticError::Overflow
+        );
+        assert_eq!(RewardPallet::allocations(&ALICE), 0);
+    });
+}
+
+#[test]
+fn revoked_allocations_return_to_the_pool() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 20, None));
+
+        assert_noop!(
+            RewardPallet::revoke_allocation(RuntimeOrigin::root(), ALICE, Some(81)),
+            Error::<Test>::ExceedsAllocation
+        );
+        assert_ok!(RewardPallet::revoke_allocation(RuntimeOrigin::root(), ALICE, Some(30)));
+        System::assert_last_event(
+            Event::AllocationRevoked { who: ALICE, amount: 30, remaining: 50 }.into(),
+        );
+        assert_eq!(RewardPallet::reward_pool(), 930);
+
+        // The remainder can still be claimed, and the claimed part is untouched
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 50, None));
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 70);
+        assert_eq!(RewardPallet::allocations(&ALICE), 0);
+
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), BOB, 100));
+        assert_ok!(RewardPallet::revoke_allocation(RuntimeOrigin::root(), BOB, None));
+        assert_eq!(RewardPallet::allocations(&BOB), 0);
+        assert_eq!(RewardPallet::reward_pool(), 930);
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+#[test]
+fn claim_all_pays_what_partial_claims_left() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 30, None));
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 25, None));
+
+        assert_ok!(RewardPallet::claim_all(RuntimeOrigin::signed(ALICE)));
+        assert!(matches!(
+            reward_events().last(),
+            Some(Event::RewardClaimed { amount: 45, remaining_allocation: 0, .. })
+        ));
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 100);
+        assert_eq!(RewardPallet::allocations(&ALICE), 0);
+
+        assert_noop!(
+            RewardPallet::claim_all(RuntimeOrigin::signed(ALICE)),
+            Error::<Test>::NothingToClaim
+        );
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+#[test]
+fn payout_to_pays_the_beneficiary_not_the_caller() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_noop!(
+            RewardPallet::payout_to(RuntimeOrigin::signed(BOB), ALICE),
+            Error::<Test>::NothingToClaim
+        );
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+
+        assert_ok!(RewardPallet::payout_to(RuntimeOrigin::signed(BOB), ALICE));
+        assert!(matches!(
+            reward_events().last(),
+            Some(Event::RewardClaimed { who: ALICE, amount: 100, .. })
+        ));
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 100);
+        assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE);
+
+        // With a tip, the caller only gets the tip
+        PayoutCallerReward::set(5);
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+        assert_ok!(RewardPallet::payout_to(RuntimeOrigin::signed(BOB), ALICE));
+        System::assert_last_event(
+            Event::PayoutTipPaid { caller: BOB, beneficiary: ALICE, amount: 5 }.into(),
+        );
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 195);
+        assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 5);
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+#[test]
+fn rewards_follow_the_payout_destination() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        const COLD: AccountId = 77;
+        assert_ok!(RewardPallet::set_payout_destination(RuntimeOrigin::signed(ALICE), Some(COLD)));
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 40, None));
+        assert!(matches!(
+            reward_events().last(),
+            Some(Event::RewardClaimed { who: ALICE, amount: 40, .. })
+        ));
+        assert_ok!(RewardPallet::claim_all(RuntimeOrigin::signed(ALICE)));
+        assert_eq!(Balances::free_balance(COLD), 100);
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE);
+
+        // Block-author rewards follow it too
+        RewardPerBlock::set(10);
+        BlockAuthor::set(Some(ALICE));
+        run_to_block(2);
+        assert_eq!(Balances::free_balance(COLD), 110);
+
+        // Clearing removes the entry
+        assert_ok!(RewardPallet::set_payout_destination(RuntimeOrigin::signed(ALICE), None));
+        assert_eq!(RewardPallet::payout_destination(ALICE), None);
+        run_to_block(3);
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 10);
+    });
+}
+
+#[test]
+fn claim_cooldown_ends_exactly_at_the_boundary() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        ClaimCooldown::set(3);
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 10, None));
+        assert_eq!(RewardPallet::last_claim_block(ALICE), Some(1));
+
+        run_to_block(3);
+        assert_noop!(
+            RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 10, None),
+            Error::<Test>::ClaimTooSoon
+        );
+        run_to_block(4);
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 10, None));
+
+        // An upgrade changing the constant applies to the next claim
+        ClaimCooldown::set(5);
+        run_to_block(8);
+        assert_noop!(
+            RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 10, None),
+            Error::<Test>::ClaimTooSoon
+        );
+        run_to_block(9);
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 10, None));
+
+        // Zero disables the check
+        ClaimCooldown::set(0);
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 10, None));
+    });
+}
+
+#[test]
+fn cooldown_changes_apply_to_accounts_already_cooling_down() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        let claim = |who| RewardPallet::claim_reward(RuntimeOrigin::signed(who), 10, None);
+        assert_ok!(RewardPallet::set_claim_cooldown(RuntimeOrigin::root(), 5));
+        for who in [ALICE, BOB] {
+            assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), who, 100));
+            assert_ok!(claim(who));
+        }
+
+        run_to_block(3);
+        assert_noop!(claim(ALICE), Error::<Test>::ClaimTooSoon);
+        assert_ok!(RewardPallet::set_claim_cooldown(RuntimeOrigin::root(), 2));
+        assert_ok!(claim(ALICE));
+
+        // Raising it holds back the other account too
+        assert_ok!(RewardPallet::set_claim_cooldown(RuntimeOrigin::root(), 10));
+        run_to_block(10);
+        assert_noop!(claim(BOB), Error::<Test>::ClaimTooSoon);
+        run_to_block(11);
+        assert_ok!(claim(BOB));
+    });
+}
+
+#[test]
+fn min_claim_changes_apply_to_the_next_claim() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 5, None));
+
+        assert_ok!(RewardPallet::set_min_claim_amount(RuntimeOrigin::root(), 10));
+        assert_noop!(
+            RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 5, None),
+            Error::<Test>::ClaimBelowMinimum
+        );
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 10, None));
+
+        // The runtime constant is only the default
+        MinClaimAmount::set(50);
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 10, None));
+    });
+}
+
+#[test]
+fn claim_cap_resets_at_the_period_boundary() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        ClaimCapPerPeriod::set(50);
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 500));
+
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 30, None));
+        assert_noop!(
+            RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 21, None),
+            Error::<Test>::ClaimCapExceeded
+        );
+        run_to_block(9);
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 20, None));
+        assert_eq!(RewardPallet::claimed_this_period(&ALICE, 9), 50);
+
+        // Periods are ten blocks long
+        run_to_block(10);
+        assert_eq!(RewardPallet::claimed_this_period(&ALICE, 10), 0);
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 50, None));
+
+        // A cap of zero is unlimited
+        ClaimCapPerPeriod::set(0);
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 400, None));
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 500);
+    });
+}
+
+#[test]
+fn dust_claims_are_rejected_unless_they_empty_the_allocation() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        MinClaimAmount::set(10);
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 25));
+
+        assert_noop!(
+            RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 5, None),
+            Error::<Test>::ClaimBelowMinimum
+        );
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 20, None));
+
+        // The remainder is below the minimum but empties the allocation
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 5, None));
+        assert_eq!(RewardPallet::allocations(&ALICE), 0);
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 25);
+    });
+}
+
+#[test]
+fn claims_per_block_are_limited() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        MaxClaimsPerBlock::set(2);
+        for who in [ALICE, BOB, CHARLIE] {
+            assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), who, 100));
+        }
+
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 10, None));
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(BOB), 10, None));
+        assert_noop!(
+            RewardPallet::claim_reward(RuntimeOrigin::signed(CHARLIE), 10, None),
+            Error::<Test>::TooManyClaimsThisBlock
+        );
+
+        run_to_block(2);
+        assert_eq!(RewardPallet::claims_this_block(), 0);
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(CHARLIE), 10, None));
+    });
+}
+
+#[test]
+fn claim_history_evicts_the_oldest_record() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+        for amount in 1..=5 {
+            run_to_block(amount);
+            assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), amount, None));
+        }
+
+        let history: Vec<_> = (2..=5).map(|n| ClaimRecord { amount: n, block: n }).collect();
+        assert_eq!(RewardPallet::claim_history(ALICE).into_inner(), history);
+
+        assert_noop!(
+            RewardPallet::prune_claim_history(RuntimeOrigin::signed(ALICE), ALICE),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(RewardPallet::prune_claim_history(RuntimeOrigin::root(), ALICE));
+        System::assert_last_event(Event::ClaimHistoryPruned(ALICE).into());
+        assert!(RewardPallet::claim_history(ALICE).is_empty());
+    });
+}
+
+#[test]
+fn lifetime_claimed_accumulates_claims_and_block_rewards() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 30, None));
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 20, None));
+        assert!(matches!(
+            reward_events().last(),
+            Some(Event::RewardClaimed { lifetime_claimed: 50, .. })
+        ));
+
+        RewardPerBlock::set(10);
+        BlockAuthor::set(Some(ALICE));
+        run_to_block(3);
+        assert_eq!(RewardPallet::total_claimed_by(ALICE), 70);
+        assert_eq!(RewardPallet::total_claimed_by(BOB), 0);
+    });
+}
+
+#[test]
+fn partial_claims_report_the_remainder_until_exhausted() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+
+        for (amount, remaining) in [(40, 60), (35, 25), (25, 0)] {
+            let too_much = amount + remaining + 1;
+            assert_noop!(
+                RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), too_much, None),
+                Error::<Test>::ExceedsAllocation
+            );
+            assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), amount, None));
+            assert!(matches!(
+                reward_events().last(),
+                Some(Event::RewardClaimed { amount: a, remaining_allocation: r, .. })
+                    if *a == amount && *r == remaining
+            ));
+        }
+
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 100);
+        assert_eq!(RewardPallet::total_allocated(), 0);
+        assert_noop!(
+            RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 1, None),
+            Error::<Test>::NothingToClaim
+        );
+    });
+}
+
+#[test]
+fn claimed_value_per_block_is_limited_but_block_rewards_are_exempt() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        MaxClaimedPerBlock::set(100);
+        RewardPerBlock::set(150);
+        BlockAuthor::set(Some(CHARLIE));
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), BOB, 100));
+
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 60, None));
+        assert_noop!(
+            RewardPallet::claim_reward(RuntimeOrigin::signed(BOB), 50, None),
+            Error::<Test>::BlockClaimBudgetExceeded
+        );
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(BOB), 40, None));
+
+        run_to_block(2);
+        assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 150);
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(BOB), 50, None));
+    });
+}
+
+// ---------------------------------------------
+//  Proof and Voucher Claims
+// ---------------------------------------------
+
+fn leaf(who: AccountId, amount: Balance) -> H256 {
+    H256(blake2_256(&(who, amount).encode()))
+}
+
+#[test]
+fn merkle_claims_pay_each_leaf_once() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        let (alice, bob) = (leaf(ALICE, 100), leaf(BOB, 200));
+        let root = RewardPallet::proof_root(alice, &[bob]);
+        let proof = |sibling: H256| BoundedVec::try_from(vec![sibling]).unwrap();
+
+        assert_noop!(
+            RewardPallet::set_claim_root(RuntimeOrigin::root(), root, 1_001),
+            Error::<Test>::InsufficientRewardPool
+        );
+        assert_ok!(RewardPallet::set_claim_root(RuntimeOrigin::root(), root, 300));
+        // The root's total is set aside from the pool up front
+        assert_eq!(RewardPallet::reward_pool(), 700);
+        assert_eq!(RewardPallet::total_allocated(), 300);
+
+        assert_ok!(RewardPallet::claim_with_proof(RuntimeOrigin::signed(ALICE), 100, proof(bob)));
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 100);
+        assert_eq!(RewardPallet::reward_pool(), 700);
+        assert_eq!(RewardPallet::claim_root(), Some((root, 200)));
+
+        // Replays are rejected
+        assert_noop!(
+            RewardPallet::claim_with_proof(RuntimeOrigin::signed(ALICE), 100, proof(bob)),
+            Error::<Test>::AlreadyClaimed
+        );
+
+        // So are amounts that aren't in the tree
+        assert_noop!(
+            RewardPallet::claim_with_proof(RuntimeOrigin::signed(BOB), 300, proof(alice)),
+            Error::<Test>::InvalidProof
+        );
+        assert_ok!(RewardPallet::claim_with_proof(RuntimeOrigin::signed(BOB), 200, proof(alice)));
+        assert_eq!(RewardPallet::total_allocated(), 0);
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+#[test]
+fn claim_root_respects_the_pool_reserve() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        let root = RewardPallet::proof_root(leaf(ALICE, 100), &[leaf(BOB, 200)]);
+        assert_ok!(RewardPallet::set_min_pool_reserve(RuntimeOrigin::root(), 800));
+
+        assert_noop!(
+            RewardPallet::set_claim_root(RuntimeOrigin::root(), root, 300),
+            Error::<Test>::WouldBreachReserve
+        );
+        assert_ok!(RewardPallet::set_claim_root(RuntimeOrigin::root(), root, 200));
+        assert_eq!(RewardPallet::reward_pool(), 800);
+    });
+}
+
+#[test]
+fn claims_cannot_exceed_the_claim_root_total() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        let (alice, bob) = (leaf(ALICE, 100), leaf(BOB, 200));
+        let root = RewardPallet::proof_root(alice, &[bob]);
+        let proof = |sibling: H256| BoundedVec::try_from(vec![sibling]).unwrap();
+
+        // The root under-reports what its leaves add up to
+        assert_ok!(RewardPallet::set_claim_root(RuntimeOrigin::root(), root, 250));
+        assert_ok!(RewardPallet::claim_with_proof(RuntimeOrigin::signed(BOB), 200, proof(alice)));
+        assert_noop!(
+            RewardPallet::claim_with_proof(RuntimeOrigin::signed(ALICE), 100, proof(bob)),
+            Error::<Test>::ClaimRootExhausted
+        );
+        assert_eq!(RewardPallet::reward_pool(), 750);
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+#[test]
+fn replacing_the_claim_root_returns_the_unclaimed_total() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        let (alice, bob) = (leaf(ALICE, 100), leaf(BOB, 200));
+        let root = RewardPallet::proof_root(alice, &[bob]);
+        let proof = BoundedVec::try_from(vec![bob]).unwrap();
+
+        assert_ok!(RewardPallet::set_claim_root(RuntimeOrigin::root(), root, 300));
+        assert_ok!(RewardPallet::claim_with_proof(RuntimeOrigin::signed(ALICE), 100, proof));
+
+        // BOB's 200 goes back to the pool before the new total is set aside
+        let next = H256::repeat_byte(7);
+        assert_ok!(RewardPallet::set_claim_root(RuntimeOrigin::root(), next, 50));
+        assert_eq!(RewardPallet::reward_pool(), 850);
+        assert_eq!(RewardPallet::total_allocated(), 50);
+        assert_eq!(RewardPallet::claim_root(), Some((next, 50)));
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+const VOUCHER_KEY_TYPE: KeyTypeId = KeyTypeId(*b"rwrd");
+
+fn sign_voucher(
+    signer: &sr25519::Public,
+    who: AccountId,
+    amount: Balance,
+    nonce: u64,
+) -> sr25519::Signature {
+    let payload = (&b"rwrd/voucher"[..], System::block_hash(0), who, amount, nonce).encode();
+    sp_io::crypto::sr25519_sign(VOUCHER_KEY_TYPE, signer, &payload).unwrap()
+}
+
+#[test]
+fn vouchers_must_be_signed_for_the_claimant_and_used_once() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        let signer = sp_io::crypto::sr25519_generate(VOUCHER_KEY_TYPE, None);
+        let impostor = sp_io::crypto::sr25519_generate(VOUCHER_KEY_TYPE, None);
+        let voucher = sign_voucher(&signer, ALICE, 100, 7);
+
+        assert_noop!(
+            RewardPallet::claim_with_voucher(RuntimeOrigin::signed(ALICE), 100, 7, voucher.clone()),
+            Error::<Test>::NoClaimSigner
+        );
+        assert_ok!(RewardPallet::set_claim_signer(RuntimeOrigin::root(), Some(signer)));
+
+        // Signed by the wrong key
+        let forged = sign_voucher(&impostor, ALICE, 100, 7);
+        assert_noop!(
+            RewardPallet::claim_with_voucher(RuntimeOrigin::signed(ALICE), 100, 7, forged),
+            Error::<Test>::InvalidVoucherSignature
+        );
+
+        // Redeemed by someone else
+        assert_noop!(
+            RewardPallet::claim_with_voucher(RuntimeOrigin::signed(BOB), 100, 7, voucher.clone()),
+            Error::<Test>::InvalidVoucherSignature
+        );
+
+        assert_ok!(RewardPallet::claim_with_voucher(
+            RuntimeOrigin::signed(ALICE),
+            100,
+            7,
+            voucher.clone()
+        ));
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 100);
+
+        // Replayed
+        assert_noop!(
+            RewardPallet::claim_with_voucher(RuntimeOrigin::signed(ALICE), 100, 7, voucher),
+            Error::<Test>::NonceAlreadyUsed
+        );
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+#[test]
+fn vouchers_must_carry_the_domain_tag() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        let signer = sp_io::crypto::sr25519_generate(VOUCHER_KEY_TYPE, None);
+        assert_ok!(RewardPallet::set_claim_signer(RuntimeOrigin::root(), Some(signer)));
+
+        // A bare `(claimant, amount, nonce)` signature is not a voucher
+        let untagged = (ALICE, 100u64, 7u64).encode();
+        let signature = sp_io::crypto::sr25519_sign(VOUCHER_KEY_TYPE, &signer, &untagged).unwrap();
+        assert_noop!(
+            RewardPallet::claim_with_voucher(RuntimeOrigin::signed(ALICE), 100, 7, signature),
+            Error::<Test>::InvalidVoucherSignature
+        );
+
+        assert_eq!(
+            RewardPallet::voucher_payload(&ALICE, 100, 7),
+            (VOUCHER_CONTEXT, System::block_hash(0), ALICE, 100u64, 7u64).encode()
+        );
+    });
+}
+
+#[test]
+fn vouchers_respect_the_pool_reserve() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        let signer = sp_io::crypto::sr25519_generate(VOUCHER_KEY_TYPE, None);
+        assert_ok!(RewardPallet::set_claim_signer(RuntimeOrigin::root(), Some(signer)));
+        assert_ok!(RewardPallet::set_min_pool_reserve(RuntimeOrigin::root(), 950));
+
+        let voucher = sign_voucher(&signer, ALICE, 100, 7);
+        assert_noop!(
+            RewardPallet::claim_with_voucher(RuntimeOrigin::signed(ALICE), 100, 7, voucher),
+            Error::<Test>::WouldBreachReserve
+        );
+        let voucher = sign_voucher(&signer, ALICE, 50, 8);
+        assert_ok!(RewardPallet::claim_with_voucher(RuntimeOrigin::signed(ALICE), 50, 8, voucher));
+        assert_eq!(RewardPallet::reward_pool(), 950);
+    });
+}
+
+#[test]
+fn claim_memos_are_emitted_and_bounded() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+        let memo = Memo::<Test>::try_from(b"milestone 2".to_vec()).unwrap();
+
+        assert_ok!(RewardPallet::claim_reward_with_memo(
+            RuntimeOrigin::signed(ALICE),
+            10,
+            memo.clone()
+        ));
+        assert!(matches!(
+            reward_events().last(),
+            Some(Event::RewardClaimed { memo: Some(m), .. }) if *m == memo
+        ));
+
+        // Oversized memos can't be built, nor decoded from an extrinsic
+        let oversized = vec![0u8; 33];
+        assert!(Memo::<Test>::try_from(oversized.clone()).is_err());
+        assert!(Memo::<Test>::decode(&mut &oversized.encode()[..]).is_err());
+    });
+}
+
+#[test]
+fn claim_for_pays_opted_in_contract_accounts() {
+    const CONTRACT: AccountId = 500;
+    ExtBuilder::default().pool(1_000).claimers(vec![CONTRACT]).build().execute_with(|| {
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), CONTRACT, 100));
+
+        assert_noop!(
+            RewardPallet::claim_for(RuntimeOrigin::signed(BOB), CONTRACT),
+            Error::<Test>::ThirdPartyClaimsNotAllowed
+        );
+
+        // The contract opts in by dispatching the call itself
+        assert_ok!(RewardPallet::set_third_party_claims(RuntimeOrigin::signed(CONTRACT), true));
+        assert_ok!(RewardPallet::claim_for(RuntimeOrigin::signed(BOB), CONTRACT));
+        System::assert_last_event(
+            Event::ClaimedFor { caller: BOB, beneficiary: CONTRACT, amount: 100 }.into(),
+        );
+        assert_eq!(Balances::free_balance(CONTRACT), 100);
+        assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE);
+
+        // Opting out again stops it
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), CONTRACT, 50));
+        assert_ok!(RewardPallet::set_third_party_claims(RuntimeOrigin::signed(CONTRACT), false));
+        assert_noop!(
+            RewardPallet::claim_for(RuntimeOrigin::signed(BOB), CONTRACT),
+            Error::<Test>::ThirdPartyClaimsNotAllowed
+        );
+    });
+}
+
+// ---------------------------------------------
+//  Bans and Eligibility
+// ---------------------------------------------
+
+#[test]
+fn banning_can_keep_or_slash_the_allocation() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), BOB, 100));
+
+        // Kept allocations become claimable again on unban
+        assert_ok!(RewardPallet::ban_claimer(RuntimeOrigin::root(), ALICE, false));
+        System::assert_last_event(Event::ClaimerBanned { who: ALICE, slashed: 0 }.into());
+        assert_noop!(
+            RewardPallet::claim_all(RuntimeOrigin::signed(ALICE)),
+            Error::<Test>::AccountBanned
+        );
+        assert_eq!(RewardPallet::allocations(&ALICE), 100);
+        assert_ok!(RewardPallet::unban_claimer(RuntimeOrigin::root(), ALICE));
+        System::assert_last_event(Event::ClaimerUnbanned(ALICE).into());
+        assert_ok!(RewardPallet::claim_all(RuntimeOrigin::signed(ALICE)));
+
+        // Slashed allocations return to the pool
+        assert_ok!(RewardPallet::ban_claimer(RuntimeOrigin::root(), BOB, true));
+        System::assert_last_event(Event::ClaimerBanned { who: BOB, slashed: 100 }.into());
+        assert_eq!(RewardPallet::allocations(&BOB), 0);
+        assert_eq!(RewardPallet::reward_pool(), 900);
+        assert_ok!(RewardPallet::unban_claimer(RuntimeOrigin::root(), BOB));
+        assert_noop!(
+            RewardPallet::claim_all(RuntimeOrigin::signed(BOB)),
+            Error::<Test>::NothingToClaim
+        );
+        assert_noop!(
+            RewardPallet::unban_claimer(RuntimeOrigin::root(), BOB),
+            Error::<Test>::NotBanned
+        );
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+#[test]
+fn claims_require_the_minimum_balance_including_reserves() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), BOB, 100));
+        assert_ok!(RewardPallet::set_eligibility_min_balance(
+            RuntimeOrigin::root(),
+            INITIAL_BALANCE + 1
+        ));
+
+        assert_noop!(
+            RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 10, None),
+            Error::<Test>::InsufficientStake
+        );
+
+        // Exactly at the threshold, counting reserved funds
+        assert_ok!(RewardPallet::set_eligibility_min_balance(
+            RuntimeOrigin::root(),
+            INITIAL_BALANCE
+        ));
+        assert_ok!(Balances::reserve(&BOB, INITIAL_BALANCE / 2));
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(BOB), 10, None));
+
+        // Zero disables the check
+        assert_ok!(Balances::transfer(RuntimeOrigin::signed(ALICE), TREASURY, 1));
+        assert_noop!(
+            RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 10, None),
+            Error::<Test>::InsufficientStake
+        );
+        assert_ok!(RewardPallet::set_eligibility_min_balance(RuntimeOrigin::root(), 0));
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 10, None));
+    });
+}
+
+// ---------------------------------------------
+//  Direct Payouts and Expiry
+// ---------------------------------------------
+
+#[test]
+fn force_payout_many_pays_a_full_batch() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        let batch: Vec<_> = (100..108).map(|who| (who, 10 + who as Balance - 100)).collect();
+        let batch = BoundedVec::try_from(batch).unwrap();
+
+        assert_noop!(
+            RewardPallet::force_payout_many(RuntimeOrigin::signed(ALICE), batch.clone()),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(RewardPallet::force_payout_many(RuntimeOrigin::root(), batch));
+
+        let claimed = reward_events()
+            .into_iter()
+            .filter(|e| matches!(e, Event::RewardClaimed { .. }))
+            .count();
+        assert_eq!(claimed, 8);
+        System::assert_last_event(Event::PayoutsCompleted { count: 8, total: 108 }.into());
+        assert_eq!(Balances::free_balance(107), 17);
+        assert_eq!(RewardPallet::reward_pool(), 892);
+        assert_eq!(RewardPallet::total_distributed(), 108);
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+#[test]
+fn force_payout_many_is_all_or_nothing() {
+    ExistentialDeposit::set(10);
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        // The second payout would dust a fresh account
+        let batch = BoundedVec::try_from(vec![(ALICE, 50), (100, 5), (BOB, 50)]).unwrap();
+        assert_noop!(
+            RewardPallet::force_payout_many(RuntimeOrigin::root(), batch),
+            Error::<Test>::BelowExistentialDeposit
+        );
+
+        DirectPayoutLimit::set(40);
+        let batch = BoundedVec::try_from(vec![(ALICE, 40), (BOB, 41)]).unwrap();
+        assert_noop!(
+            RewardPallet::force_payout_many(RuntimeOrigin::root(), batch),
+            Error::<Test>::ExceedsDirectPayoutLimit
+        );
+    });
+}
+
+#[test]
+fn large_payouts_are_proposed_and_approved() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        DirectPayoutLimit::set(100);
+        assert_ok!(RewardPallet::add_operator(RuntimeOrigin::root(), ALICE));
+
+        // The limit itself can still be paid directly
+        let direct = |amount| BoundedVec::try_from(vec![(CHARLIE, amount)]).unwrap();
+        assert_ok!(RewardPallet::force_payout_many(RuntimeOrigin::signed(ALICE), direct(100)));
+        assert_noop!(
+            RewardPallet::force_payout_many(RuntimeOrigin::signed(ALICE), direct(101)),
+            Error::<Test>::ExceedsDirectPayoutLimit
+        );
+
+        assert_noop!(
+            RewardPallet::propose_payout(RuntimeOrigin::signed(BOB), BOB, 500),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(RewardPallet::propose_payout(RuntimeOrigin::signed(ALICE), BOB, 500));
+        System::assert_last_event(
+            Event::PayoutProposed { id: 0, proposer: ALICE, beneficiary: BOB, amount: 500 }.into(),
+        );
+
+        // Operators propose, but only the params origin approves
+        assert_noop!(
+            RewardPallet::approve_payout(RuntimeOrigin::signed(ALICE), 0),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(RewardPallet::approve_payout(RuntimeOrigin::root(), 0));
+        System::assert_last_event(
+            Event::PayoutApproved { id: 0, beneficiary: BOB, amount: 500 }.into(),
+        );
+        assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 500);
+        assert_eq!(RewardPallet::reward_pool(), 400);
+        assert_noop!(
+            RewardPallet::approve_payout(RuntimeOrigin::root(), 0),
+            Error::<Test>::UnknownPayoutProposal
+        );
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+#[test]
+fn payout_proposals_expire_or_are_rejected() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_ok!(RewardPallet::add_operator(RuntimeOrigin::root(), ALICE));
+        for _ in 0..4 {
+            assert_ok!(RewardPallet::propose_payout(RuntimeOrigin::signed(ALICE), BOB, 500));
+        }
+        assert_noop!(
+            RewardPallet::propose_payout(RuntimeOrigin::signed(ALICE), BOB, 500),
+            Error::<Test>::TooManyPayoutProposals
+        );
+
+        assert_ok!(RewardPallet::reject_payout(RuntimeOrigin::root(), 1));
+        System::assert_last_event(Event::PayoutRejected { id: 1 }.into());
+        assert_eq!(RewardPallet::payout_proposals(1), None);
+
+        // Proposed at block 1, so the last block to approve is 10
+        run_to_block(10);
+        assert_noop!(
+            RewardPallet::remove_expired_payout(RuntimeOrigin::signed(CHARLIE), 0),
+            Error::<Test>::PayoutProposalNotExpired
+        );
+        assert_ok!(RewardPallet::approve_payout(RuntimeOrigin::root(), 2));
+
+        run_to_block(11);
+        assert_noop!(
+            RewardPallet::approve_payout(RuntimeOrigin::root(), 0),
+            Error::<Test>::PayoutProposalExpired
+        );
+        assert_ok!(RewardPallet::remove_expired_payout(RuntimeOrigin::signed(CHARLIE), 0));
+        System::assert_last_event(Event::PayoutProposalExpired { id: 0 }.into());
+        assert_eq!(RewardPallet::payout_proposals(0), None);
+        assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 500);
+        assert_eq!(RewardPallet::reward_pool(), 500);
+    });
+}
+
+#[test]
+fn allocations_expire_and_are_swept_back_to_the_pool() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        AllocationLifetime::set(5);
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+        let sweep = || BoundedVec::try_from(vec![ALICE, BOB]).unwrap();
+
+        // One block before expiry
+        run_to_block(5);
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 40, None));
+        assert_ok!(RewardPallet::sweep_expired(RuntimeOrigin::signed(BOB), sweep()));
+        assert_eq!(RewardPallet::allocations(&ALICE), 60);
+
+        // At expiry
+        run_to_block(6);
+        assert_noop!(
+            RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 10, None),
+            Error::<Test>::AllocationExpired
+        );
+
+        assert_ok!(RewardPallet::sweep_expired(RuntimeOrigin::signed(BOB), sweep()));
+        System::assert_last_event(Event::AllocationExpiredSwept { who: ALICE, amount: 60 }.into());
+        assert_eq!(RewardPallet::allocations(&ALICE), 0);
+        assert_eq!(RewardPallet::reward_pool(), 960);
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+#[test]
+fn on_idle_pays_out_small_allocations_across_blocks() {
+    OpenClaimsWhenEmpty::set(true);
+    ExtBuilder::default().pool(1_000).claimers(vec![]).build().execute_with(|| {
+        AutoPayoutThreshold::set(10);
+        let small: Vec<AccountId> = (100..150).collect();
+        for who in &small {
+            assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), *who, 5));
+        }
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+
+        // Room for ten payouts per block
+        let db = <Test as frame_system::Config>::DbWeight::get();
+        let limit = db.reads(1).saturating_add(db.reads_writes(13, 11).saturating_mul(10));
+        let unpaid = || small.iter().filter(|who| RewardPallet::allocations(who) > 0).count();
+
+        RewardPallet::on_idle(1, limit);
+        assert!(unpaid() >= 40);
+        assert!(AutoPayoutCursor::<Test>::get().is_some());
+
+        // Allocations removed mid-sweep are skipped
+        let revoked = *small.iter().find(|who| RewardPallet::allocations(who) > 0).unwrap();
+        assert_ok!(RewardPallet::revoke_allocation(RuntimeOrigin::root(), revoked, None));
+
+        for n in 2..=6 {
+            run_to_block(n);
+            RewardPallet::on_idle(n, limit);
+        }
+        assert_eq!(unpaid(), 0);
+        let paid = small.iter().filter(|who| Balances::free_balance(**who) == 5).count();
+        assert_eq!(paid, 49);
+        assert_eq!(Balances::free_balance(revoked), 0);
+        assert_eq!(RewardPallet::allocations(&ALICE), 100);
+        assert_eq!(RewardPallet::total_distributed(), 49 * 5);
+        assert!(AutoPayoutCursor::<Test>::get().is_none());
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+#[test]
+fn on_idle_skips_allocations_that_could_not_be_claimed() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        AutoPayoutThreshold::set(10);
+        const OUTSIDER: AccountId = 100;
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), OUTSIDER, 5));
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), BOB, 5));
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), CHARLIE, 5));
+        assert_ok!(RewardPallet::ban_claimer(RuntimeOrigin::root(), CHARLIE, false));
+
+        RewardPallet::on_idle(1, Weight::MAX);
+
+        // Neither the non-whitelisted nor the banned account is paid
+        assert_eq!(RewardPallet::allocations(&OUTSIDER), 5);
+        assert_eq!(Balances::free_balance(OUTSIDER), 0);
+        assert_eq!(RewardPallet::allocations(&CHARLIE), 5);
+        assert_eq!(RewardPallet::allocations(&BOB), 0);
+        assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 5);
+        assert_eq!(RewardPallet::claims_this_block(), 1);
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+// ---------------------------------------------
+//  Claim Keys and Fees
+// ---------------------------------------------
+
+#[test]
+fn claim_keys_reject_retries_within_the_retention_window() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+        let key: ClaimKey = [7; 32];
+
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 10, Some(key)));
+        run_to_block(10);
+        assert_noop!(
+            RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 10, Some(key)),
+            Error::<Test>::DuplicateClaim
+        );
+        // Keys are scoped per account
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), BOB, 100));
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(BOB), 10, Some(key)));
+
+        // Pruning keeps active keys
+        let keys = || BoundedVec::try_from(vec![key]).unwrap();
+        assert_ok!(RewardPallet::prune_claim_keys(RuntimeOrigin::signed(CHARLIE), ALICE, keys()));
+        assert!(ProcessedClaimKeys::<Test>::contains_key(ALICE, key));
+
+        // The retention window is ten blocks
+        run_to_block(11);
+        assert_ok!(RewardPallet::prune_claim_keys(RuntimeOrigin::signed(CHARLIE), ALICE, keys()));
+        assert!(!ProcessedClaimKeys::<Test>::contains_key(ALICE, key));
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 10, Some(key)));
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 20);
+    });
+}
+
+#[test]
+fn successful_eligible_claims_are_fee_free() {
+    ExtBuilder::default().pool(1_000).claimers(vec![ALICE]).build().execute_with(|| {
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+
+        let post = RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 10, None).unwrap();
+        assert_eq!(post.pays_fee, Pays::No);
+
+        let error =
+            RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 1_000, None).unwrap_err();
+        assert_eq!(error.post_info.pays_fee, Pays::Yes);
+
+        // A claim that only forfeits the deposit still pays
+        ClaimDeposit::set(10);
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), BOB, 100));
+        let post = RewardPallet::claim_reward(RuntimeOrigin::signed(BOB), 10, None).unwrap();
+        assert_eq!(post.pays_fee, Pays::Yes);
+
+        // So does an unallocated claim by a non-member
+        ClaimDeposit::set(0);
+        OpenClaimsWhenEmpty::set(true);
+        AllowUnallocatedClaims::set(true);
+        assert_ok!(RewardPallet::remove_claimer(RuntimeOrigin::root(), ALICE));
+        let post = RewardPallet::claim_reward(RuntimeOrigin::signed(CHARLIE), 10, None).unwrap();
+        assert_eq!(post.pays_fee, Pays::Yes);
+    });
+}
+
+// ---------------------------------------------
+//  Delegated, Queued and Vested Claims
+// ---------------------------------------------
+
+#[test]
+fn delegate_and_principal_share_one_allocation() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+        assert_noop!(
+            RewardPallet::claim_as_delegate(RuntimeOrigin::signed(BOB), ALICE, 60),
+            Error::<Test>::NotDelegate
+        );
+        assert_ok!(RewardPallet::set_claim_delegate(RuntimeOrigin::signed(ALICE), Some(BOB)));
+
+        assert_ok!(RewardPallet::claim_as_delegate(RuntimeOrigin::signed(BOB), ALICE, 60));
+        System::assert_last_event(
+            Event::DelegatedClaim { delegate: BOB, principal: ALICE, amount: 60 }.into(),
+        );
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 60);
+        assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE);
+
+        // The principal can't claim more than is left
+        assert_noop!(
+            RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 41, None),
+            Error::<Test>::ExceedsAllocation
+        );
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 30, None));
+
+        // Revoking stops the delegate immediately
+        assert_ok!(RewardPallet::set_claim_delegate(RuntimeOrigin::signed(ALICE), None));
+        assert_noop!(
+            RewardPallet::claim_as_delegate(RuntimeOrigin::signed(BOB), ALICE, 10),
+            Error::<Test>::NotDelegate
+        );
+        assert_eq!(RewardPallet::allocations(&ALICE), 10);
+    });
+}
+
+#[test]
+fn queued_claims_are_processed_in_order_over_several_blocks() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), BOB, 100));
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), CHARLIE, 10));
+
+        let requests = [
+            (ALICE, 10),
+            (BOB, 10),
+            (CHARLIE, 20),
+            (ALICE, 11),
+            (BOB, 12),
+            (ALICE, 13),
+            (BOB, 14),
+            (ALICE, 15),
+        ];
+        for (who, amount) in requests {
+            assert_ok!(RewardPallet::request_claim(RuntimeOrigin::signed(who), amount));
+        }
+        assert_noop!(
+            RewardPallet::request_claim(RuntimeOrigin::signed(ALICE), 1),
+            Error::<Test>::QueueFull
+        );
+
+        // Only the requester can cancel
+        assert_noop!(
+            RewardPallet::cancel_claim_request(RuntimeOrigin::signed(BOB), 0),
+            Error::<Test>::UnknownClaimRequest
+        );
+        assert_ok!(RewardPallet::cancel_claim_request(RuntimeOrigin::signed(BOB), 1));
+
+        // Two requests per block
+        run_to_block(2);
+        assert!(reward_events().contains(&Event::ClaimRequestRejected {
+            id: 2,
+            who: CHARLIE,
+            error: Error::<Test>::ExceedsAllocation.into(),
+        }));
+        assert_eq!(RewardPallet::pending_claims().len(), 5);
+        run_to_block(4);
+        assert_eq!(RewardPallet::pending_claims().len(), 1);
+        run_to_block(5);
+        assert!(RewardPallet::pending_claims().is_empty());
+
+        let paid: Vec<_> = reward_events()
+            .into_iter()
+            .filter_map(|e| match e {
+                Event::RewardClaimed { amount, .. } => Some(amount),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(paid, vec![10, 11, 12, 13, 14, 15]);
+        assert_eq!(RewardPallet::allocations(&CHARLIE), 10);
+    });
+}
+
+#[test]
+fn vested_claims_unlock_linearly() {
+    ExtBuilder::default().pool(1_000).claimers(vec![50]).build().execute_with(|| {
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), 50, 120));
+        assert_noop!(
+            RewardPallet::claim_reward_vested(RuntimeOrigin::signed(50), 100, 0),
+            Error::<Test>::ZeroVestingRate
+        );
+        assert_ok!(RewardPallet::claim_reward_vested(RuntimeOrigin::signed(50), 100, 10));
+        assert_eq!(Balances::free_balance(50), 100);
+
+        // Before vesting, nothing moves
+        assert_noop!(
+            Balances::transfer(RuntimeOrigin::signed(50), BOB, 1),
+            pallet_balances::Error::<Test>::LiquidityRestrictions
+        );
+
+        // Halfway, half of it does
+        run_to_block(6);
+        assert_ok!(RewardPallet::vest_rewards(RuntimeOrigin::signed(50)));
+        System::assert_last_event(Event::VestingUpdated { who: 50, locked: 50 }.into());
+        assert_ok!(Balances::transfer(RuntimeOrigin::signed(50), BOB, 50));
+        assert_noop!(
+            Balances::transfer(RuntimeOrigin::signed(50), BOB, 1),
+            pallet_balances::Error::<Test>::LiquidityRestrictions
+        );
+
+        // A further vested claim merges into the schedule
+        assert_ok!(RewardPallet::claim_reward_vested(RuntimeOrigin::signed(50), 20, 5));
+        assert_eq!(
+            RewardPallet::vesting(50),
+            Some(VestingInfo { locked: 70, per_block: 15, starting_block: 6 })
+        );
+
+        // Fully vested, the lock is gone
+        run_to_block(11);
+        assert_ok!(RewardPallet::vest_rewards(RuntimeOrigin::signed(50)));
+        assert_eq!(RewardPallet::vesting(50), None);
+        assert_ok!(Balances::transfer(RuntimeOrigin::signed(50), BOB, 70));
+        assert_noop!(
+            RewardPallet::vest_rewards(RuntimeOrigin::signed(50)),
+            Error::<Test>::NotVesting
+        );
+    });
+}
+
+// ---------------------------------------------
+//  Era Points
+// ---------------------------------------------
+
+#[test]
+fn era_shares_never_exceed_the_budget() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_ok!(RewardPallet::set_era_budget(RuntimeOrigin::root(), 100));
+        for who in [ALICE, BOB, CHARLIE] {
+            RewardPallet::note_points(&who, 1);
+        }
+        assert_noop!(
+            RewardPallet::claim_era_share(RuntimeOrigin::signed(ALICE), 0),
+            Error::<Test>::EraNotSettled
+        );
+
+        run_to_block(10);
+        System::assert_has_event(
+            Event::EraSettled { era: 0, budget: 100, total_points: 3, dust: 0 }.into(),
+        );
+        assert_eq!(RewardPallet::reward_pool(), 900);
+
+        // Points start over in the new era
+        RewardPallet::note_points(&ALICE, 5);
+        assert_eq!(RewardPallet::era_points(1, ALICE), 5);
+
+        for who in [ALICE, BOB, CHARLIE] {
+            assert_ok!(RewardPallet::claim_era_share(RuntimeOrigin::signed(who), 0));
+            System::assert_has_event(
+                Event::EraShareClaimed { era: 0, who, points: 1, amount: 33 }.into(),
+            );
+        }
+        assert_noop!(
+            RewardPallet::claim_era_share(RuntimeOrigin::signed(ALICE), 0),
+            Error::<Test>::NoEraPoints
+        );
+
+        // The shares round down, and the remainder is carried over
+        assert_eq!(RewardPallet::total_distributed(), 99);
+        assert_eq!(RewardPallet::carried_dust(), 1);
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+#[test]
+fn era_dust_is_carried_until_distributed() {
+    ExtBuilder::default().pool(10_000).build().execute_with(|| {
+        assert_ok!(RewardPallet::set_era_budget(RuntimeOrigin::root(), 100));
+
+        for round in 0..6u32 {
+            let carried = RewardPallet::carried_dust();
+            let noted = [(ALICE, round + 1), (BOB, 2 * round + 1), (CHARLIE, 7)];
+            for (who, points) in noted {
+                RewardPallet::note_points(&who, points);
+            }
+
+            run_to_block(10 * (round as BlockNumber + 1));
+            System::assert_has_event(
+                Event::EraSettled {
+                    era: round,
+                    budget: 100 + carried,
+                    total_points: (3 * round + 9).into(),
+                    dust: carried,
+                }
+                .into(),
+            );
+            for who in [ALICE, BOB, CHARLIE] {
+                assert_ok!(RewardPallet::claim_era_share(RuntimeOrigin::signed(who), round));
+            }
+
+            // Distributed plus carried dust always adds up to the budgets so far
+            let budgets = 100 * (round as Balance + 1);
+            assert_eq!(RewardPallet::total_distributed() + RewardPallet::carried_dust(), budgets);
+            assert!(RewardPallet::accounting_consistent());
+        }
+    });
+}
+
+#[test]
+fn expired_era_remainders_return_to_the_pool() {
+    EraClaimLifetime::set(2);
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_ok!(RewardPallet::set_era_budget(RuntimeOrigin::root(), 100));
+        for who in [ALICE, BOB, CHARLIE] {
+            RewardPallet::note_points(&who, 1);
+        }
+        run_to_block(10);
+        assert_ok!(RewardPallet::claim_era_share(RuntimeOrigin::signed(ALICE), 0));
+
+        // Era 0 stays claimable through era 2
+        run_to_block(20);
+        assert_noop!(
+            RewardPallet::sweep_expired_era(RuntimeOrigin::signed(ALICE), 0),
+            Error::<Test>::EraNotExpired
+        );
+
+        run_to_block(30);
+        assert_noop!(
+            RewardPallet::claim_era_share(RuntimeOrigin::signed(BOB), 0),
+            Error::<Test>::EraExpired
+        );
+        assert_ok!(RewardPallet::sweep_expired_era(RuntimeOrigin::signed(ALICE), 0));
+        System::assert_last_event(Event::EraSwept { era: 0, amount: 67 }.into());
+        assert_eq!(RewardPallet::reward_pool(), 967);
+        assert_eq!(RewardPallet::total_allocated(), 0);
+        assert_eq!(RewardPallet::era_reward(0), None);
+        assert_eq!(RewardPallet::era_points(0, BOB), 0);
+        assert!(RewardPallet::accounting_consistent());
+
+        // Nothing is left to sweep
+        assert_noop!(
+            RewardPallet::sweep_expired_era(RuntimeOrigin::signed(ALICE), 0),
+            Error::<Test>::EraNotSettled
+        );
+    });
+}
+
+#[test]
+fn era_points_are_swept_in_bounded_batches() {
+    EraClaimLifetime::set(1);
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_ok!(RewardPallet::set_era_budget(RuntimeOrigin::root(), 100));
+        let earners: Vec<AccountId> = (100..110).collect();
+        for who in &earners {
+            RewardPallet::note_points(who, 1);
+        }
+        run_to_block(20);
+
+        // `MaxSweepPerCall` is 8, so the last two earners take a second call
+        assert_ok!(RewardPallet::sweep_expired_era(RuntimeOrigin::signed(ALICE), 0));
+        System::assert_last_event(Event::EraSwept { era: 0, amount: 100 }.into());
+        assert_eq!(EraPoints::<Test>::iter_prefix(0).count(), 2);
+        assert_ok!(RewardPallet::sweep_expired_era(RuntimeOrigin::signed(ALICE), 0));
+        System::assert_last_event(Event::EraSwept { era: 0, amount: 0 }.into());
+        assert_eq!(EraPoints::<Test>::iter_prefix(0).count(), 0);
+        assert_eq!(RewardPallet::reward_pool(), 1_000);
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+// ---------------------------------------------
+//  Receipts
+// ---------------------------------------------
+
+#[test]
+fn receipts_are_numbered_across_claim_paths_and_prunable() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), BOB, 100));
+
+        assert_ok!(RewardPallet::claim_reward(RuntimeOrigin::signed(ALICE), 10, None));
+        run_to_block(2);
+        assert_ok!(RewardPallet::payout_to(RuntimeOrigin::signed(ALICE), BOB));
+        let batch = BoundedVec::try_from(vec![(CHARLIE, 5), (ALICE, 6)]).unwrap();
+        assert_ok!(RewardPallet::force_payout_many(RuntimeOrigin::root(), batch));
+
+        let receipts: Vec<_> = (0..4).map(|id| Receipts::<Test>::get(id).unwrap()).collect();
+        assert_eq!(
+            receipts,
+            vec![
+                Receipt { who: ALICE, amount: 10, block: 1 },
+                Receipt { who: BOB, amount: 100, block: 2 },
+                Receipt { who: CHARLIE, amount: 5, block: 2 },
+                Receipt { who: ALICE, amount: 6, block: 2 },
+            ]
+        );
+        assert_eq!(RewardPallet::next_claim_id(), 4);
+
+        assert_noop!(
+            RewardPallet::prune_receipts(RuntimeOrigin::signed(ALICE), 1),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(RewardPallet::prune_receipts(RuntimeOrigin::root(), 1));
+        System::assert_last_event(Event::ReceiptsPruned { first_remaining: 2 }.into());
+        assert!(Receipts::<Test>::get(1).is_none());
+        assert!(Receipts::<Test>::get(2).is_some());
+
+        // Ids past the last claim are ignored
+        assert_ok!(RewardPallet::prune_receipts(RuntimeOrigin::root(), 100));
+        assert_eq!(RewardPallet::first_receipt_id(), 4);
+        assert_eq!(Receipts::<Test>::iter().count(), 0);
+    });
+}
+
+// ---------------------------------------------
+//  Transaction Validation
+// ---------------------------------------------
+
+fn check_claim(who: AccountId, call: crate::Call<Test>) -> bool {
+    let call = RuntimeCall::RewardPallet(call);
+    let info = DispatchInfo::default();
+    let validity = CheckRewardPool::<Test>::new().validate(&who, &call, &info, 0);
+    let pre = CheckRewardPool::<Test>::new().pre_dispatch(&who, &call, &info, 0);
+    assert_eq!(validity.is_ok(), pre.is_ok());
+    match validity {
+        Ok(_) => true,
+        Err(error) => {
+            assert_eq!(error, InvalidTransaction::Custom(NOTHING_TO_CLAIM).into());
+            false
+        },
+    }
+}
+
+#[test]
+fn check_reward_pool_rejects_claims_with_nothing_to_draw_from() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        let claim_reward = || crate::Call::claim_reward { amount: 10, claim_key: None };
+
+        assert!(!check_claim(ALICE, crate::Call::claim_all {}));
+        assert!(!check_claim(ALICE, claim_reward()));
+        assert!(check_claim(ALICE, crate::Call::donate_to_pool { amount: 10 }));
+
+        // An allocation can pay either call
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+        assert!(check_claim(ALICE, crate::Call::claim_all {}));
+        assert!(check_claim(ALICE, claim_reward()));
+
+        // Unallocated claims are valid only while the pool can pay them
+        AllowUnallocatedClaims::set(true);
+        assert!(check_claim(BOB, claim_reward()));
+        assert!(!check_claim(BOB, crate::Call::claim_all {}));
+        assert_ok!(RewardPallet::force_set_reward_pool(RuntimeOrigin::root(), 0));
+        assert!(!check_claim(BOB, claim_reward()));
+    });
+}
+
+// ---------------------------------------------
+//  Runtime API
+// ---------------------------------------------
+
+/// Stands in for a metering pallet that credits rewards through `RewardAccrual`.
+struct UsageMeter<R>(sp_std::marker::PhantomData<R>);
+
+impl<R: RewardAccrual<AccountId, Balance>> UsageMeter<R> {
+    fn record_usage(who: AccountId, units: Balance) -> DispatchResult {
+        R::accrue(&who, units * 2)
+    }
+}
+
+#[test]
+fn other_pallets_accrue_rewards_for_users_to_claim() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        type Meter = UsageMeter<RewardPallet>;
+
+        assert_ok!(Meter::record_usage(ALICE, 20));
+        assert_ok!(Meter::record_usage(ALICE, 5));
+        assert_eq!(<RewardPallet as RewardAccrual<_, _>>::pending(&ALICE), 50);
+        assert_eq!(RewardPallet::reward_pool(), 950);
+
+        assert_ok!(RewardPallet::claim_all(RuntimeOrigin::signed(ALICE)));
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 50);
+        assert_eq!(<RewardPallet as RewardAccrual<_, _>>::pending(&ALICE), 0);
+
+        // Accrual fails when the pool is short
+        assert_noop!(Meter::record_usage(BOB, 476), Error::<Test>::InsufficientRewardPool);
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+#[test]
+fn fee_attributed_rewards_require_the_min_consensus_fee() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        // Without a floor even fee-free transactions earn rewards
+        assert_eq!(RewardPallet::min_consensus_fee(), 0);
+        assert_ok!(<RewardPallet as RewardAccrual<_, _>>::accrue_for_fee(&ALICE, 10, 0));
+
+        assert_ok!(RewardPallet::set_min_consensus_fee(RuntimeOrigin::root(), 10));
+        for (who, fee, attributed) in [(ALICE, 10, true), (BOB, 9, false), (CHARLIE, 50, true)] {
+            let result = <RewardPallet as RewardAccrual<_, _>>::accrue_for_fee(&who, 30, fee);
+            if attributed {
+                assert_ok!(result);
+            } else {
+                assert_eq!(result, Err(Error::<Test>::BelowMinConsensusFee.into()));
+            }
+        }
+
+        assert_eq!(RewardPallet::allocations(&ALICE), 40);
+        assert_eq!(RewardPallet::allocations(&BOB), 0);
+        assert_eq!(RewardPallet::allocations(&CHARLIE), 30);
+        assert_eq!(RewardPallet::reward_pool(), 930);
+    });
+}
+
+// ---------------------------------------------
+//  Block Rewards
+// ---------------------------------------------
+
+#[test]
+fn block_reward_is_paid_to_the_author_found_in_the_digest() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        RewardPerBlock::set(10);
+        BlockAuthor::set(Some(CHARLIE));
+
+        run_to_block(2);
+        System::assert_has_event(
+            Event::BlockRewardDistributed(CHARLIE, CHARLIE, 10, 0, Permill::zero()).into(),
+        );
+        assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 10);
+        assert_eq!(RewardPallet::reward_pool(), 990);
+
+        // Without an author the reward stays in the pool
+        BlockAuthor::set(None);
+        run_to_block(3);
+        System::assert_has_event(Event::BlockRewardSkipped { reason: SkipReason::NoAuthor }.into());
+        assert_eq!(RewardPallet::reward_pool(), 990);
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+#[test]
+fn block_reward_is_paid_to_the_author_noted_by_authorship() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        use pallet_authorship::EventHandler;
+        RewardPerBlock::set(10);
+
+        // Runtimes wiring only the `EventHandler` leave `FindAuthor` empty
+        RewardPallet::note_author(BOB);
+        run_to_block(2);
+        assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 10);
+        assert_eq!(RewardPallet::noted_author(), None);
+
+        // A noted author takes precedence over the digest
+        BlockAuthor::set(Some(CHARLIE));
+        RewardPallet::note_author(BOB);
+        run_to_block(3);
+        assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 20);
+        assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE);
+
+        // Noting is per block; the next falls back to the digest
+        run_to_block(4);
+        assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 10);
+    });
+}
+
+#[test]
+fn noted_author_is_paid_in_the_same_block() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        use pallet_authorship::EventHandler;
+        RewardPerBlock::set(10);
+
+        RewardPallet::on_initialize(1);
+        RewardPallet::note_author(BOB);
+        RewardPallet::on_finalize(1);
+        assert_eq!(System::block_number(), 1);
+        assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 10);
+        assert_eq!(RewardPallet::last_distribution_block(), Some(1));
+
+        // A block without a noted author pays nothing
+        System::set_block_number(2);
+        RewardPallet::on_initialize(2);
+        RewardPallet::on_finalize(2);
+        assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 10);
+        assert_eq!(RewardPallet::reward_pool(), 990);
+        System::assert_last_event(
+            Event::BlockRewardSkipped { reason: SkipReason::NoAuthor }.into(),
+        );
+    });
+}
+
+#[test]
+fn block_reward_split_gives_the_rounding_to_the_treasury() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        RewardPerBlock::set(11);
+        BlockRewardSplit::set(Permill::from_percent(80));
+        BlockAuthor::set(Some(CHARLIE));
+
+        run_to_block(2);
+        System::assert_has_event(
+            Event::BlockRewardDistributed(CHARLIE, CHARLIE, 8, 0, Permill::zero()).into(),
+        );
+        System::assert_has_event(Event::TreasuryRewardDistributed(TREASURY, 3).into());
+        assert_eq!(Balances::free_balance(TREASURY), INITIAL_BALANCE + 3);
+        assert_eq!(RewardPallet::reward_pool(), 989);
+
+        // The whole reward goes to the author at 100%
+        BlockRewardSplit::set(Permill::one());
+        run_to_block(3);
+        assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 19);
+        assert_eq!(Balances::free_balance(TREASURY), INITIAL_BALANCE + 3);
+        assert_eq!(RewardPallet::total_distributed(), 22);
+    });
+}
+
+#[test]
+fn partial_block_reward_drains_the_pool() {
+    ExtBuilder::default().pool(25).build().execute_with(|| {
+        RewardPerBlock::set(10);
+        BlockAuthor::set(Some(CHARLIE));
+
+        // By default a short pool skips the reward
+        run_to_block(4);
+        assert_eq!(RewardPallet::reward_pool(), 5);
+        run_to_block(5);
+        System::assert_has_event(
+            Event::BlockRewardSkipped { reason: SkipReason::InsufficientPool }.into(),
+        );
+        assert_eq!(RewardPallet::reward_pool(), 5);
+
+        PayPartialBlockReward::set(true);
+        run_to_block(6);
+        System::assert_has_event(
+            Event::BlockRewardDistributed(CHARLIE, CHARLIE, 5, 0, Permill::zero()).into(),
+        );
+        System::assert_has_event(Event::RewardPoolDepleted.into());
+        assert_eq!(RewardPallet::reward_pool(), 0);
+        assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 25);
+
+        // Nothing is left for the next block
+        System::reset_events();
+        run_to_block(7);
+        assert_eq!(
+            reward_events(),
+            vec![Event::BlockRewardSkipped { reason: SkipReason::InsufficientPool }]
+        );
+        assert_eq!(RewardPallet::pot(), ExistentialDeposit::get());
+    });
+}
+
+#[test]
+fn skipped_block_rewards_report_the_reason() {
+    ExtBuilder::default().pool(5).build().execute_with(|| {
+        let skipped_in_next_block = |reason: SkipReason| {
+            System::reset_events();
+            run_to_block(System::block_number() + 1);
+            assert_eq!(reward_events(), vec![Event::BlockRewardSkipped { reason }]);
+        };
+
+        BlockAuthor::set(Some(CHARLIE));
+        skipped_in_next_block(SkipReason::ZeroReward);
+
+        RewardPerBlock::set(10);
+        skipped_in_next_block(SkipReason::InsufficientPool);
+
+        BlockAuthor::set(None);
+        skipped_in_next_block(SkipReason::NoAuthor);
+
+        assert_eq!(RewardPallet::reward_pool(), 5);
+        assert_eq!(RewardPallet::emission_stats().blocks_skipped, 2);
+    });
+}
+
+#[test]
+fn accrued_block_rewards_are_claimed_per_author() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        AccrueBlockRewards::set(true);
+        RewardPerBlock::set(10);
+
+        BlockAuthor::set(Some(CHARLIE));
+        run_to_block(5);
+        BlockAuthor::set(Some(BOB));
+        run_to_block(8);
+
+        // The pool is debited at accrual time, but nothing is transferred yet
+        assert_eq!(RewardPallet::reward_pool(), 930);
+        assert_eq!(RewardPallet::author_accrued(CHARLIE), 40);
+        assert_eq!(RewardPallet::author_accrued(BOB), 30);
+        assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE);
+        assert!(RewardPallet::accounting_consistent());
+
+        assert_ok!(RewardPallet::claim_block_rewards(RuntimeOrigin::signed(CHARLIE)));
+        System::assert_last_event(Event::BlockRewardsClaimed(CHARLIE, 40).into());
+        assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 40);
+        assert_eq!(RewardPallet::author_accrued(BOB), 30);
+        assert_noop!(
+            RewardPallet::claim_block_rewards(RuntimeOrigin::signed(CHARLIE)),
+            Error::<Test>::NothingToClaim
+        );
+
+        assert_ok!(RewardPallet::claim_block_rewards(RuntimeOrigin::signed(BOB)));
+        assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 30);
+        assert_eq!(RewardPallet::total_distributed(), 70);
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+#[test]
+fn block_reward_halves_every_interval() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        RewardPerBlock::set(40);
+        HalvingInterval::set(4);
+        BlockAuthor::set(Some(CHARLIE));
+
+        run_to_block(12);
+        System::assert_has_event(
+            Event::BlockRewardDistributed(CHARLIE, CHARLIE, 20, 1, Permill::zero()).into(),
+        );
+        System::assert_has_event(
+            Event::BlockRewardDistributed(CHARLIE, CHARLIE, 10, 2, Permill::zero()).into(),
+        );
+        assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 3 * 40 + 4 * 20 + 4 * 10);
+
+        // Shifting past the balance width pays nothing rather than overflowing
+        HalvingInterval::set(1);
+        System::set_block_number(200);
+        assert_eq!(RewardPallet::block_reward(), (0, 200));
+    });
+}
+
+#[test]
+fn proportional_emission_decays_geometrically() {
+    ExtBuilder::default().pool(100_000).build().execute_with(|| {
+        Emission::set(EmissionMode::Proportional);
+        PoolEmissionRate::set(Perbill::from_percent(1));
+        BlockAuthor::set(Some(CHARLIE));
+
+        run_to_block(301);
+        let expected = (0..300).fold(100_000, |pool: Balance, _| pool - pool / 100);
+        assert_eq!(RewardPallet::reward_pool(), expected);
+        assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 100_000 - expected);
+
+        // A tiny pool rounds down to nothing and stops emission
+        assert_ok!(RewardPallet::force_set_reward_pool(RuntimeOrigin::root(), 99));
+        System::reset_events();
+        run_to_block(302);
+        assert_eq!(
+            reward_events(),
+            vec![Event::BlockRewardSkipped { reason: SkipReason::RoundedToZero }]
+        );
+        assert_eq!(RewardPallet::reward_pool(), 99);
+    });
+}
+
+#[test]
+fn era_author_rewards_follow_blocks_authored() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        PayAuthorsPerEra::set(true);
+        RewardPerBlock::set(10);
+
+        for (author, until) in [(ALICE, 6), (BOB, 9), (CHARLIE, 10)] {
+            BlockAuthor::set(Some(author));
+            run_to_block(until);
+        }
+
+        // 100 split 5:3:1, rounded down, with the remainder left in the pool
+        System::assert_has_event(
+            Event::EraRewardsDistributed { era: 0, total: 99, authors: 3, blocks: 9 }.into(),
+        );
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 55);
+        assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 33);
+        assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 11);
+        assert_eq!(RewardPallet::reward_pool(), 901);
+        assert_eq!(BlocksAuthoredInEra::<Test>::count(), 0);
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+#[test]
+fn excluded_authors_are_withheld_their_rewards() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        RewardPerBlock::set(10);
+        BlockAuthor::set(Some(CHARLIE));
+        run_to_block(2);
+
+        assert_ok!(RewardPallet::exclude_author(RuntimeOrigin::root(), CHARLIE));
+        assert_noop!(
+            RewardPallet::exclude_author(RuntimeOrigin::root(), CHARLIE),
+            Error::<Test>::AlreadyExcluded
+        );
+        run_to_block(4);
+        System::assert_has_event(Event::BlockRewardWithheld { author: CHARLIE }.into());
+        assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 10);
+        assert_eq!(RewardPallet::reward_pool(), 990);
+
+        assert_ok!(RewardPallet::include_author(RuntimeOrigin::root(), CHARLIE));
+        assert_noop!(
+            RewardPallet::include_author(RuntimeOrigin::root(), CHARLIE),
+            Error::<Test>::NotExcluded
+        );
+        run_to_block(5);
+        assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 20);
+        assert_eq!(RewardPallet::reward_pool(), 980);
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+#[test]
+fn author_rewards_are_capped_per_era() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        MaxAuthorRewardPerEra::set(25);
+        RewardPerBlock::set(10);
+        BlockAuthor::set(Some(CHARLIE));
+
+        run_to_block(6);
+        assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 30);
+        let cap_events = reward_events()
+            .into_iter()
+            .filter(|e| matches!(e, Event::AuthorRewardCapReached { .. }))
+            .collect::<Vec<_>>();
+        assert_eq!(cap_events, vec![Event::AuthorRewardCapReached { author: CHARLIE, era: 0 }]);
+
+        // Other authors are unaffected
+        BlockAuthor::set(Some(BOB));
+        run_to_block(8);
+        assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 20);
+
+        // The cap resets with the era
+        BlockAuthor::set(Some(CHARLIE));
+        run_to_block(11);
+        assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 40);
+        assert_eq!(RewardPallet::author_era_rewards(0, CHARLIE), 0);
+        assert_eq!(RewardPallet::reward_pool(), 1_000 - 30 - 20 - 10);
+    });
+}
+
+// ---------------------------------------------
+//  Emission Controls
+// ---------------------------------------------
+
+#[test]
+fn reward_per_block_override_takes_precedence() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        RewardPerBlock::set(10);
+        BlockAuthor::set(Some(CHARLIE));
+
+        assert_ok!(RewardPallet::set_reward_per_block(RuntimeOrigin::root(), Some(25)));
+        run_to_block(2);
+        assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 25);
+
+        // Zero stops emission
+        assert_ok!(RewardPallet::set_reward_per_block(RuntimeOrigin::root(), Some(0)));
+        run_to_block(4);
+        assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 25);
+
+        // Clearing it restores the constant
+        assert_ok!(RewardPallet::set_reward_per_block(RuntimeOrigin::root(), None));
+        assert_eq!(RewardPallet::reward_per_block(), 10);
+        run_to_block(5);
+        assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 35);
+    });
+}
+
+#[test]
+fn paused_block_rewards_pay_nothing() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        RewardPerBlock::set(10);
+        BlockAuthor::set(Some(CHARLIE));
+
+        assert_ok!(RewardPallet::pause_block_rewards(RuntimeOrigin::root()));
+        System::assert_last_event(Event::BlockRewardsPaused.into());
+        run_to_block(4);
+        assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE);
+        assert_eq!(RewardPallet::total_distributed(), 0);
+        assert_eq!(RewardPallet::reward_pool(), 1_000);
+
+        // Claims are unaffected
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 100));
+        assert_ok!(RewardPallet::claim_all(RuntimeOrigin::signed(ALICE)));
+
+        assert_ok!(RewardPallet::resume_block_rewards(RuntimeOrigin::root()));
+        System::assert_last_event(Event::BlockRewardsResumed.into());
+        run_to_block(5);
+        assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 10);
+        assert_eq!(RewardPallet::total_distributed(), 110);
+    });
+}
+
+#[test]
+fn emission_stats_count_rewarded_and_skipped_blocks() {
+    ExtBuilder::default().pool(25).build().execute_with(|| {
+        RewardPerBlock::set(10);
+        BlockAuthor::set(Some(CHARLIE));
+
+        // Two funded blocks, then two the pool can't cover
+        run_to_block(5);
+        assert_eq!(
+            RewardPallet::emission_stats(),
+            EmissionStats { blocks_rewarded: 2, blocks_skipped: 2, last_rewarded_block: Some(2) }
+        );
+
+        assert_ok!(RewardPallet::top_up_pool(RuntimeOrigin::signed(FUNDER), 20));
+        run_to_block(6);
+
+        // Blocks without a configured reward are not counted
+        assert_ok!(RewardPallet::set_reward_per_block(RuntimeOrigin::root(), Some(0)));
+        run_to_block(7);
+        assert_eq!(
+            RewardPallet::emission_stats(),
+            EmissionStats { blocks_rewarded: 3, blocks_skipped: 2, last_rewarded_block: Some(5) }
+        );
+        assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 30);
+    });
+}
+
+#[test]
+fn smoothing_spreads_a_top_up_over_the_window() {
+    // The author's earnings per block, topping up 40 before block 4
+    let schedule = |window: BlockNumber| {
+        ExtBuilder::default().pool(50).build().execute_with(|| {
+            RewardPerBlock::set(10);
+            SmoothingWindow::set(window);
+            BlockAuthor::set(Some(CHARLIE));
+
+            let mut paid = Vec::new();
+            for n in 2..=6 {
+                if n == 5 {
+                    assert_ok!(RewardPallet::top_up_pool(RuntimeOrigin::signed(FUNDER), 40));
+                }
+                let before = Balances::free_balance(CHARLIE);
+                run_to_block(n);
+                paid.push(Balances::free_balance(CHARLIE) - before);
+            }
+            assert!(RewardPallet::accounting_consistent());
+            paid
+        })
+    };
+
+    assert_eq!(schedule(0), vec![10, 10, 10, 10, 10]);
+    assert_eq!(schedule(10), vec![5, 4, 4, 7, 7]);
+}
+
+#[test]
+fn smoothed_rewards_report_the_effective_amount() {
+    ExtBuilder::default().pool(50).build().execute_with(|| {
+        RewardPerBlock::set(10);
+        SmoothingWindow::set(10);
+        BlockAuthor::set(Some(CHARLIE));
+
+        assert_eq!(RewardPallet::block_reward(), (5, 0));
+        run_to_block(2);
+        System::assert_has_event(
+            Event::BlockRewardDistributed(CHARLIE, CHARLIE, 5, 0, Permill::zero()).into(),
+        );
+        assert_eq!(RewardPallet::reward_pool(), 45);
+    });
+}
+
+#[test]
+fn authors_can_switch_their_payout_account_between_blocks() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        RewardPerBlock::set(10);
+        BlockAuthor::set(Some(CHARLIE));
+
+        assert_ok!(RewardPallet::set_author_payout_account(
+            RuntimeOrigin::signed(CHARLIE),
+            Some(BOB)
+        ));
+        run_to_block(2);
+        System::assert_has_event(
+            Event::BlockRewardDistributed(CHARLIE, BOB, 10, 0, Permill::zero()).into(),
+        );
+        assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 10);
+
+        assert_ok!(RewardPallet::set_author_payout_account(
+            RuntimeOrigin::signed(CHARLIE),
+            Some(ALICE)
+        ));
+        run_to_block(3);
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 10);
+        assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 10);
+
+        // Clearing the mapping pays the author directly again
+        assert_ok!(RewardPallet::set_author_payout_account(RuntimeOrigin::signed(CHARLIE), None));
+        run_to_block(4);
+        System::assert_has_event(
+            Event::BlockRewardDistributed(CHARLIE, CHARLIE, 10, 0, Permill::zero()).into(),
+        );
+        assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 10);
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 10);
+    });
+}
+
+#[test]
+fn flushed_dust_is_never_lost() {
+    for dest in [None, Some(TREASURY)] {
+        ExtBuilder::default().pool(1_000).build().execute_with(|| {
+            DustDestination::set(dest);
+            assert_ok!(RewardPallet::set_era_budget(RuntimeOrigin::root(), 100));
+            for who in [ALICE, BOB, CHARLIE] {
+                RewardPallet::note_points(&who, 1);
+            }
+            run_to_block(10);
+            for who in [ALICE, BOB, CHARLIE] {
+                assert_ok!(RewardPallet::claim_era_share(RuntimeOrigin::signed(who), 0));
+            }
+            assert_eq!(RewardPallet::carried_dust(), 1);
+
+            // Nobody earns points in era 1, so its end flushes the dust
+            run_to_block(20);
+            System::assert_has_event(Event::DustFlushed(1).into());
+            assert_eq!(RewardPallet::carried_dust(), 0);
+            let forwarded = Balances::free_balance(TREASURY) - INITIAL_BALANCE;
+            assert_eq!(forwarded, if dest.is_some() { 1 } else { 0 });
+            assert_eq!(
+                RewardPallet::reward_pool() +
+                    RewardPallet::total_allocated() +
+                    RewardPallet::total_distributed() +
+                    forwarded,
+                1_000
+            );
+            assert!(RewardPallet::accounting_consistent());
+
+            // With nothing left over the flush is skipped
+            run_to_block(30);
+            let flushes =
+                reward_events().into_iter().filter(|e| matches!(e, Event::DustFlushed(_))).count();
+            assert_eq!(flushes, 1);
+        });
+    }
+}
+
+/// Record a signed extrinsic in the current block, so it no longer counts as empty.
+fn fill_block() {
+    System::note_applied_extrinsic(&Ok(().into()), DispatchInfo::default());
+    System::note_finished_extrinsics();
+    System::set_block_consumed_resources(Weight::from_parts(1, 0), 0);
+}
+
+#[test]
+fn empty_blocks_are_not_rewarded_unless_configured() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        RewardPerBlock::set(10);
+        RewardEmptyBlocks::set(false);
+        BlockAuthor::set(Some(CHARLIE));
+
+        run_to_block(2);
+        System::assert_has_event(
+            Event::BlockRewardSkipped { reason: SkipReason::EmptyBlock }.into(),
+        );
+        assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE);
+
+        fill_block();
+        run_to_block(3);
+        assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 10);
+
+        // Only inherents, which are dispatched in the mandatory class
+        System::set_block_consumed_resources(Weight::zero(), 0);
+        run_to_block(4);
+        assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 10);
+        assert_eq!(RewardPallet::emission_stats().blocks_skipped, 2);
+
+        RewardEmptyBlocks::set(true);
+        run_to_block(5);
+        assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 20);
+        assert_eq!(RewardPallet::reward_pool(), 980);
+    });
+}
+
+#[test]
+fn era_summary_aggregates_the_era_and_resets() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        RewardPerBlock::set(10);
+
+        // Era 0: CHARLIE authors six blocks, BOB two, and one has no author
+        let (c, b) = (Some(CHARLIE), Some(BOB));
+        for (n, author) in (2..=10).zip([c, c, c, c, b, b, None, c, c]) {
+            BlockAuthor::set(author);
+            run_to_block(n);
+        }
+        System::assert_has_event(
+            Event::EraSummary {
+                era: 0,
+                total_paid: 80,
+                blocks_rewarded: 8,
+                blocks_skipped: 1,
+                unique_authors: 2,
+            }
+            .into(),
+        );
+
+        // Era 1 with per-block events off: only the summary is emitted
+        EmitBlockRewardEvents::set(false);
+        BlockAuthor::set(Some(ALICE));
+        System::reset_events();
+        run_to_block(20);
+        assert!(!reward_events().iter().any(|e| matches!(e, Event::BlockRewardDistributed(..))));
+        System::assert_has_event(
+            Event::EraSummary {
+                era: 1,
+                total_paid: 100,
+                blocks_rewarded: 10,
+                blocks_skipped: 0,
+                unique_authors: 1,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 100);
+    });
+}
+
+#[test]
+fn author_bonus_multiplies_the_block_reward() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        RewardPerBlock::set(10);
+        AuthorBonuses::set(vec![
+            (CHARLIE, Permill::from_percent(20)),
+            (BOB, Permill::from_percent(50)),
+        ]);
+
+        for (n, author, paid, bonus) in [
+            (2, CHARLIE, 12, Permill::from_percent(20)),
+            (3, BOB, 15, Permill::from_percent(50)),
+            (4, ALICE, 10, Permill::zero()),
+        ] {
+            BlockAuthor::set(Some(author));
+            run_to_block(n);
+            System::assert_has_event(
+                Event::BlockRewardDistributed(author, author, paid, 0, bonus).into(),
+            );
+            assert_eq!(Balances::free_balance(author), INITIAL_BALANCE + paid);
+        }
+        assert_eq!(RewardPallet::reward_pool(), 963);
+    });
+}
+
+#[test]
+fn author_bonus_is_bounded_by_the_pool() {
+    ExtBuilder::default().pool(11).build().execute_with(|| {
+        RewardPerBlock::set(10);
+        AuthorBonuses::set(vec![(CHARLIE, Permill::from_percent(20))]);
+        BlockAuthor::set(Some(CHARLIE));
+
+        run_to_block(2);
+        System::assert_has_event(
+            Event::BlockRewardDistributed(CHARLIE, CHARLIE, 11, 0, Permill::from_percent(20))
+                .into(),
+        );
+        System::assert_has_event(Event::RewardPoolDepleted.into());
+        assert_eq!(RewardPallet::reward_pool(), 0);
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+// Corrupt totals trip `defensive!`, which only panics in debug builds.
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic(expected = "Defensive failure has been triggered!"))]
+fn block_reward_survives_a_corrupt_total_distributed() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        RewardPerBlock::set(10);
+        BlockAuthor::set(Some(CHARLIE));
+        TotalDistributed::<Test>::put(Balance::MAX - 5);
+
+        run_to_block(2);
+        assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 10);
+        assert_eq!(RewardPallet::reward_pool(), 990);
+        assert_eq!(RewardPallet::total_distributed(), Balance::MAX);
+    });
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic(expected = "Defensive failure has been triggered!"))]
+fn claim_survives_a_corrupt_total_distributed() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 10));
+        TotalDistributed::<Test>::put(Balance::MAX - 5);
+
+        assert_ok!(RewardPallet::claim_all(RuntimeOrigin::signed(ALICE)));
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 10);
+        assert_eq!(RewardPallet::total_distributed(), Balance::MAX);
+    });
+}
+
+// ---------------------------------------------
+//  Configuration
+// ---------------------------------------------
+
+#[test]
+fn mock_config_passes_the_integrity_test() {
+    new_test_ext().execute_with(|| RewardPallet::integrity_test());
+}
+
+#[test]
+#[should_panic(expected = "RewardPerBlock exceeds MaxPoolSize, no pool could ever pay it")]
+fn reward_per_block_above_the_pool_cap_fails_the_integrity_test() {
+    RewardPerBlock::set(100);
+    MaxPoolSize::set(50);
+    new_test_ext().execute_with(|| RewardPallet::integrity_test());
+}
+
+#[test]
+#[should_panic(expected = "MaxClaimsPerBlock must allow claims")]
+fn zero_claims_per_block_fails_the_integrity_test() {
+    MaxClaimsPerBlock::set(0);
+    new_test_ext().execute_with(|| RewardPallet::integrity_test());
+}
+
+#[test]
+#[should_panic(expected = "EraLength must be non-zero when era features are configured")]
+fn era_features_without_eras_fail_the_integrity_test() {
+    PayAuthorsPerEra::set(true);
+    EraLength::set(0);
+    new_test_ext().execute_with(|| RewardPallet::integrity_test());
+}
+
+#[test]
+#[should_panic(expected = "AccrueBlockRewards is set but BlockRewardSplit leaves authors nothing")]
+fn accruing_a_zero_author_share_fails_the_integrity_test() {
+    AccrueBlockRewards::set(true);
+    BlockRewardSplit::set(Permill::zero());
+    new_test_ext().execute_with(|| RewardPallet::integrity_test());
+}
+
+#[test]
+#[should_panic(expected = "AccrueBlockRewards has no effect when PayAuthorsPerEra is set")]
+fn accruing_per_era_rewards_fails_the_integrity_test() {
+    AccrueBlockRewards::set(true);
+    PayAuthorsPerEra::set(true);
+    new_test_ext().execute_with(|| RewardPallet::integrity_test());
+}
+
+#[test]
+#[should_panic(expected = "PoolEmissionRate must be below 100%")]
+fn emitting_the_whole_pool_fails_the_integrity_test() {
+    Emission::set(EmissionMode::Proportional);
+    PoolEmissionRate::set(Perbill::one());
+    new_test_ext().execute_with(|| RewardPallet::integrity_test());
+}
+
+// ---------------------------------------------
+//  Administration
+// ---------------------------------------------
+
+#[test]
+fn admin_handover_takes_effect_only_once_accepted() {
+    new_test_ext().execute_with(|| {
+        // Nobody can accept an unsolicited handover, and only root can start one
+        assert_noop!(
+            RewardPallet::accept_admin(RuntimeOrigin::signed(ALICE)),
+            Error::<Test>::NotPendingAdmin
+        );
+        assert_noop!(
+            RewardPallet::set_admin(RuntimeOrigin::signed(ALICE), ALICE),
+            DispatchError::BadOrigin
+        );
+
+        assert_ok!(RewardPallet::set_admin(RuntimeOrigin::root(), ALICE));
+        assert_eq!(RewardPallet::pending_admin(), Some(ALICE));
+        assert_eq!(RewardPallet::admin(), None);
+        assert_noop!(
+            RewardPallet::set_era_budget(RuntimeOrigin::signed(ALICE), 5),
+            DispatchError::BadOrigin
+        );
+
+        assert_ok!(RewardPallet::accept_admin(RuntimeOrigin::signed(ALICE)));
+        assert_eq!(RewardPallet::pending_admin(), None);
+        assert_ok!(RewardPallet::set_era_budget(RuntimeOrigin::signed(ALICE), 5));
+
+        // The old admin keeps its rights until the new one accepts
+        assert_ok!(RewardPallet::set_admin(RuntimeOrigin::signed(ALICE), BOB));
+        assert_ok!(RewardPallet::set_era_budget(RuntimeOrigin::signed(ALICE), 6));
+        assert_ok!(RewardPallet::accept_admin(RuntimeOrigin::signed(BOB)));
+        assert_eq!(RewardPallet::admin(), Some(BOB));
+        assert_noop!(
+            RewardPallet::set_era_budget(RuntimeOrigin::signed(ALICE), 7),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(RewardPallet::set_era_budget(RuntimeOrigin::signed(BOB), 7));
+        assert_eq!(RewardPallet::era_budget(), 7);
+    });
+}
+
+/// Stands in for a call of another pallet whose `Config` takes the manager origin.
+fn sibling_call<O>(origin: RuntimeOrigin) -> Result<Option<AccountId>, DispatchError>
+where
+    O: EnsureOrigin<RuntimeOrigin, Success = Option<AccountId>>,
+{
+    Ok(O::ensure_origin(origin)?)
+}
+
+#[test]
+fn ensure_reward_manager_accepts_root_and_the_admin() {
+    new_test_ext().execute_with(|| {
+        type Manager = EnsureRewardManager<Test>;
+
+        assert_eq!(Manager::try_origin(RuntimeOrigin::root()).ok(), Some(None));
+        assert!(Manager::try_origin(RuntimeOrigin::signed(ALICE)).is_err());
+        assert!(Manager::try_origin(RuntimeOrigin::none()).is_err());
+
+        assert_ok!(RewardPallet::set_admin(RuntimeOrigin::root(), ALICE));
+        assert!(Manager::try_origin(RuntimeOrigin::signed(ALICE)).is_err());
+        assert_ok!(RewardPallet::accept_admin(RuntimeOrigin::signed(ALICE)));
+        assert_eq!(Manager::try_origin(RuntimeOrigin::signed(ALICE)).ok(), Some(Some(ALICE)));
+        assert!(Manager::try_origin(RuntimeOrigin::signed(BOB)).is_err());
+    });
+}
+
+#[test]
+fn ensure_reward_manager_gates_other_pallets() {
+    new_test_ext().execute_with(|| {
+        type Manager = EnsureRewardManager<Test>;
+
+        assert_ok!(RewardPallet::set_admin(RuntimeOrigin::root(), ALICE));
+        assert_ok!(RewardPallet::accept_admin(RuntimeOrigin::signed(ALICE)));
+
+        assert_eq!(sibling_call::<Manager>(RuntimeOrigin::root()), Ok(None));
+        assert_eq!(sibling_call::<Manager>(RuntimeOrigin::signed(ALICE)), Ok(Some(ALICE)));
+        assert_eq!(
+            sibling_call::<Manager>(RuntimeOrigin::signed(BOB)),
+            Err(DispatchError::BadOrigin)
+        );
+
+        // Handing over moves the authority in both pallets at once
+        assert_ok!(RewardPallet::set_admin(RuntimeOrigin::signed(ALICE), BOB));
+        assert_ok!(RewardPallet::accept_admin(RuntimeOrigin::signed(BOB)));
+        assert_eq!(sibling_call::<Manager>(RuntimeOrigin::signed(BOB)), Ok(Some(BOB)));
+        assert_eq!(
+            sibling_call::<Manager>(RuntimeOrigin::signed(ALICE)),
+            Err(DispatchError::BadOrigin)
+        );
+    });
+}
+
+#[test]
+fn update_params_changes_only_the_given_fields() {
+    new_test_ext().execute_with(|| {
+        let old = RewardPallet::params();
+        let update = PartialParams {
+            claim_cooldown: Some(5),
+            min_claim_amount: Some(3),
+            ..Default::default()
+        };
+        assert_ok!(RewardPallet::update_params(RuntimeOrigin::root(), update));
+
+        let new = RewardParams { claim_cooldown: 5, min_claim_amount: 3, ..old };
+        assert_eq!(RewardPallet::params(), new);
+        System::assert_last_event(Event::ParamsUpdated { old, new, changed_by: None }.into());
+
+        // A later update keeps the earlier one
+        let update = PartialParams { dust_threshold: Some(2), ..Default::default() };
+        assert_ok!(RewardPallet::update_params(RuntimeOrigin::root(), update));
+        assert_eq!(RewardPallet::params(), RewardParams { dust_threshold: 2, ..new });
+    });
+}
+
+#[test]
+fn invalid_updates_leave_the_params_intact() {
+    new_test_ext().execute_with(|| {
+        MaxPoolSize::set(100);
+        let update = PartialParams { claim_cooldown: Some(5), ..Default::default() };
+        assert_ok!(RewardPallet::update_params(RuntimeOrigin::root(), update));
+
+        // The splits sum to over 100%
+        let fee_split = FeeSplit {
+            dev: Permill::from_percent(60),
+            validator: Permill::from_percent(30),
+            burn: Permill::from_percent(20),
+        };
+        let update = PartialParams {
+            fee_split: Some(fee_split),
+            claim_cooldown: Some(7),
+            ..Default::default()
+        };
+        assert_noop!(
+            RewardPallet::update_params(RuntimeOrigin::root(), update),
+            Error::<Test>::InvalidParams
+        );
+
+        // The reserve and era budget together exceed the pool cap
+        let update = PartialParams {
+            min_pool_reserve: Some(60),
+            era_budget: Some(50),
+            ..Default::default()
+        };
+        assert_noop!(
+            RewardPallet::update_params(RuntimeOrigin::root(), update),
+            Error::<Test>::InvalidParams
+        );
+        assert_eq!(RewardPallet::params().claim_cooldown, 5);
+
+        assert_noop!(
+            RewardPallet::update_params(RuntimeOrigin::signed(ALICE), Default::default()),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn funding_and_params_origins_are_separate() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        // The funder moves money but can't touch parameters or the whitelist
+        assert_ok!(RewardPallet::top_up_pool(RuntimeOrigin::signed(FUNDER), 100));
+        assert_ok!(RewardPallet::withdraw_from_pool(RuntimeOrigin::signed(FUNDER), 50, BOB));
+        assert_noop!(
+            RewardPallet::set_era_budget(RuntimeOrigin::signed(FUNDER), 5),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            RewardPallet::update_params(RuntimeOrigin::signed(FUNDER), Default::default()),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            RewardPallet::add_claimer(RuntimeOrigin::signed(FUNDER), FUNDER),
+            DispatchError::BadOrigin
+        );
+
+        // Root satisfies both
+        assert_ok!(RewardPallet::top_up_pool(RuntimeOrigin::root(), 100));
+        assert_ok!(RewardPallet::set_era_budget(RuntimeOrigin::root(), 5));
+        assert_ok!(RewardPallet::add_claimer(RuntimeOrigin::root(), FUNDER));
+        assert_eq!(RewardPallet::reward_pool(), 1_150);
+    });
+}
+
+#[test]
+fn scheduled_param_changes_apply_in_order_at_their_block() {
+    new_test_ext().execute_with(|| {
+        let root = RuntimeOrigin::root;
+        let cooldown =
+            |blocks| PartialParams { claim_cooldown: Some(blocks), ..Default::default() };
+
+        assert_noop!(
+            RewardPallet::schedule_param_change(root(), cooldown(5), 1),
+            Error::<Test>::ParamChangeInPast
+        );
+        assert_ok!(RewardPallet::schedule_param_change(root(), cooldown(5), 3));
+        System::assert_last_event(Event::ParamChangeScheduled { id: 0, effective_at: 3 }.into());
+        let both = PartialParams { min_claim_amount: Some(2), ..cooldown(7) };
+        assert_ok!(RewardPallet::schedule_param_change(root(), both, 3));
+        assert_ok!(RewardPallet::schedule_param_change(root(), cooldown(9), 5));
+        let dust = PartialParams { dust_threshold: Some(1), ..Default::default() };
+        assert_ok!(RewardPallet::schedule_param_change(root(), dust, 4));
+        assert_noop!(
+            RewardPallet::schedule_param_change(root(), dust, 4),
+            Error::<Test>::TooManyParamChanges
+        );
+
+        assert_ok!(RewardPallet::cancel_param_change(root(), 2));
+        System::assert_last_event(Event::ParamChangeCancelled { id: 2 }.into());
+        assert_noop!(
+            RewardPallet::cancel_param_change(root(), 2),
+            Error::<Test>::UnknownParamChange
+        );
+
+        run_to_block(2);
+        assert_eq!(RewardPallet::params().claim_cooldown, 0);
+
+        // Both changes due at block 3 apply, and the later one wins
+        run_to_block(3);
+        assert_eq!(RewardPallet::params().claim_cooldown, 7);
+        assert_eq!(RewardPallet::params().min_claim_amount, 2);
+        System::assert_has_event(
+            Event::ParamChangeApplied { id: 1, params: RewardPallet::params() }.into(),
+        );
+
+        run_to_block(5);
+        assert_eq!(RewardPallet::params().dust_threshold, 1);
+        assert_eq!(RewardPallet::params().claim_cooldown, 7);
+        assert!(RewardPallet::scheduled_param_changes().is_empty());
+    });
+}
+
+#[test]
+fn top_up_allowances_are_per_operator_and_reset_each_era() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_ok!(RewardPallet::set_top_up_allowance(RuntimeOrigin::root(), ALICE, 100));
+        assert_ok!(RewardPallet::set_top_up_allowance(RuntimeOrigin::root(), BOB, 50));
+
+        assert_ok!(RewardPallet::top_up_pool(RuntimeOrigin::signed(ALICE), 60));
+        System::assert_last_event(Event::RewardPoolIncreased(Some(ALICE), 60, 1_060).into());
+        assert_noop!(
+            RewardPallet::top_up_pool(RuntimeOrigin::signed(ALICE), 50),
+            Error::<Test>::TopUpAllowanceExceeded
+        );
+
+        // Spending is tracked per operator
+        assert_ok!(RewardPallet::top_up_pool(RuntimeOrigin::signed(BOB), 50));
+        assert_noop!(
+            RewardPallet::top_up_pool(RuntimeOrigin::signed(BOB), 1),
+            Error::<Test>::TopUpAllowanceExceeded
+        );
+        assert_ok!(RewardPallet::top_up_pool(RuntimeOrigin::signed(ALICE), 40));
+        assert_noop!(
+            RewardPallet::top_up_pool(RuntimeOrigin::signed(CHARLIE), 1),
+            Error::<Test>::BadOriginForTopUp
+        );
+
+        run_to_block(10);
+        System::assert_has_event(Event::TopUpAllowancesReset { era: 1 }.into());
+        assert_ok!(RewardPallet::top_up_pool(RuntimeOrigin::signed(ALICE), 100));
+        assert_ok!(RewardPallet::top_up_pool(RuntimeOrigin::signed(BOB), 50));
+        assert_eq!(RewardPallet::reward_pool(), 1_300);
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+#[test]
+fn operators_allocate_and_pay_out_but_cannot_manage() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_ok!(RewardPallet::set_admin(RuntimeOrigin::root(), ALICE));
+        assert_ok!(RewardPallet::accept_admin(RuntimeOrigin::signed(ALICE)));
+        assert_noop!(
+            RewardPallet::add_operator(RuntimeOrigin::signed(BOB), BOB),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(RewardPallet::add_operator(RuntimeOrigin::signed(ALICE), BOB));
+        System::assert_last_event(Event::OperatorAdded { who: BOB }.into());
+        assert!(RewardPallet::is_operator(&BOB));
+        assert_noop!(
+            RewardPallet::add_operator(RuntimeOrigin::signed(ALICE), BOB),
+            Error::<Test>::AlreadyOperator
+        );
+
+        let bob = || RuntimeOrigin::signed(BOB);
+        assert_ok!(RewardPallet::allocate_reward(bob(), CHARLIE, 100));
+        let batch = BoundedVec::try_from(vec![(CHARLIE, 10)]).unwrap();
+        assert_ok!(RewardPallet::force_payout_many(bob(), batch));
+        assert_noop!(RewardPallet::top_up_pool(bob(), 100), Error::<Test>::BadOriginForTopUp);
+        assert_noop!(RewardPallet::set_era_budget(bob(), 5), DispatchError::BadOrigin);
+        assert_noop!(RewardPallet::add_operator(bob(), CHARLIE), DispatchError::BadOrigin);
+
+        assert_ok!(RewardPallet::remove_operator(RuntimeOrigin::root(), BOB));
+        System::assert_last_event(Event::OperatorRemoved { who: BOB }.into());
+        assert_noop!(RewardPallet::allocate_reward(bob(), CHARLIE, 100), DispatchError::BadOrigin);
+        assert_noop!(
+            RewardPallet::remove_operator(RuntimeOrigin::root(), BOB),
+            Error::<Test>::NotOperator
+        );
+    });
+}
+
+#[test]
+fn operator_set_is_bounded() {
+    new_test_ext().execute_with(|| {
+        for who in 100..104 {
+            assert_ok!(RewardPallet::add_operator(RuntimeOrigin::root(), who));
+        }
+        assert_noop!(
+            RewardPallet::add_operator(RuntimeOrigin::root(), 104),
+            Error::<Test>::TooManyOperators
+        );
+    });
+}
+
+#[test]
+fn initialize_runs_once_and_unlocks_claims() {
+    ExtBuilder::default().claimers(vec![ALICE]).build().execute_with(|| {
+        RequireInitialization::set(true);
+        assert_ok!(RewardPallet::top_up_pool(RuntimeOrigin::root(), 100));
+        assert_ok!(RewardPallet::allocate_reward(RuntimeOrigin::root(), ALICE, 50));
+        assert_noop!(
+            RewardPallet::claim_all(RuntimeOrigin::signed(ALICE)),
+            Error::<Test>::NotInitialized
+        );
+
+        let params = RewardParams { claim_cooldown: 3, ..RewardPallet::params() };
+        assert_noop!(
+            RewardPallet::initialize(RuntimeOrigin::signed(FUNDER), FUNDER, 500, params),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(RewardPallet::initialize(RuntimeOrigin::root(), FUNDER, 500, params));
+        System::assert_last_event(
+            Event::Initialized { source: FUNDER, initial_pool: 500, params }.into(),
+        );
+        assert_eq!(Balances::free_balance(FUNDER), INITIAL_BALANCE - 500);
+        assert_eq!(RewardPallet::reward_pool(), 550);
+        assert_eq!(RewardPallet::params(), params);
+        assert!(RewardPallet::accounting_consistent());
+
+        assert_ok!(RewardPallet::claim_all(RuntimeOrigin::signed(ALICE)));
+        assert_noop!(
+            RewardPallet::initialize(RuntimeOrigin::root(), FUNDER, 500, params),
+            Error::<Test>::AlreadyInitialized
+        );
+    });
+}
+
+#[test]
+fn chains_set_up_at_genesis_cannot_initialize() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_noop!(
+            RewardPallet::initialize(RuntimeOrigin::root(), FUNDER, 500, RewardPallet::params()),
+            Error::<Test>::AlreadyInitialized
+        );
+    });
+}
+
+#[test]
+fn frozen_params_can_never_change_again() {
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        let root = RuntimeOrigin::root;
+        assert_noop!(
+            RewardPallet::freeze_params(RuntimeOrigin::signed(FUNDER)),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(RewardPallet::freeze_params(root()));
+        System::assert_last_event(Event::ParamsFrozen.into());
+        assert_noop!(RewardPallet::freeze_params(root()), Error::<Test>::ParamsFrozen);
+
+        // Not even root can change the economics
+        assert_noop!(
+            RewardPallet::set_reward_per_block(root(), Some(5)),
+            Error::<Test>::ParamsFrozen
+        );
+        assert_noop!(RewardPallet::set_dust_threshold(root(), 5), Error::<Test>::ParamsFrozen);
+        assert_noop!(
+            RewardPallet::set_fee_split(root(), FeeSplit::default()),
+            Error::<Test>::ParamsFrozen
+        );
+        assert_noop!(
+            RewardPallet::update_params(root(), Default::default()),
+            Error::<Test>::ParamsFrozen
+        );
+        assert_noop!(
+            RewardPallet::schedule_param_change(root(), Default::default(), 5),
+            Error::<Test>::ParamsFrozen
+        );
+        assert_noop!(RewardPallet::set_admin(root(), ALICE), Error::<Test>::ParamsFrozen);
+
+        // Funding and claiming carry on
+        assert_ok!(RewardPallet::top_up_pool(RuntimeOrigin::signed(FUNDER), 100));
+        assert_ok!(RewardPallet::allocate_reward(root(), ALICE, 100));
+        assert_ok!(RewardPallet::claim_all(RuntimeOrigin::signed(ALICE)));
+        assert_eq!(RewardPallet::reward_pool(), 1_000);
+        assert!(RewardPallet::params_frozen());
+    });
+}
+
+// ---------------------------------------------
+//  Genesis
+// ---------------------------------------------
+
+#[test]
+fn genesis_accepts_a_consistent_config() {
+    MaxPoolSize::set(1_000);
+    RewardPerBlock::set(10);
+    PayAuthorsPerEra::set(true);
+    ExtBuilder::default().pool(1_000).build().execute_with(|| {
+        assert_eq!(RewardPallet::reward_pool(), 1_000);
+        assert!(RewardPallet::initialized());
+        assert!(RewardPallet::accounting_consistent());
+    });
+}
+
+#[test]
+#[should_panic(expected = "initial_reward_pool exceeds MaxPoolSize")]
+fn genesis_rejects_a_pool_above_the_cap() {
+    MaxPoolSize::set(500);
+    ExtBuilder::default().pool(1_000).build();
+}
+
+#[test]
+#[should_panic(expected = "RewardPerBlock exceeds MaxPoolSize, no pool could ever pay it")]
+fn genesis_rejects_a_reward_above_the_cap() {
+    MaxPoolSize::set(500);
+    RewardPerBlock::set(501);
+    ExtBuilder::default().pool(100).build();
+}
+
+#[test]
+#[should_panic(expected = "EraLength must be non-zero when era features are configured")]
+fn genesis_rejects_era_features_without_eras() {
+    MaxAuthorRewardPerEra::set(100);
+    EraLength::set(0);
+    ExtBuilder::default().pool(1_000).build();
+}
+
+#[test]
+#[should_panic(expected = "MaxClaimsPerBlock must allow claims")]
+fn genesis_rejects_a_config_without_claims() {
+    MaxClaimsPerBlock::set(0);
+    ExtBuilder::default().pool(1_000).build();
+}
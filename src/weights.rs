@@ -0,0 +1,947 @@
+//! Weights for the reward pallet.
+//!
+//! PLACEHOLDER WEIGHTS. These values were written by hand from the storage
+//! accesses of each call and were never measured; do not use them on a
+//! production chain. Replace this file with benchmark output generated on
+//! reference hardware with the `runtime-benchmarks` feature:
+//!
+//! ```text
+//! ./target/release/node benchmark pallet \
+//!     --chain=dev --steps=50 --repeat=20 \
+//!     --pallet=pallet_archway_reward --extrinsic='*' \
+//!     --output=src/weights.rs
+//! ```
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for the reward pallet.
+pub trait WeightInfo {
+    fn top_up_pool() -> Weight;
+    fn force_top_up_pool() -> Weight;
+    fn donate_to_pool() -> Weight;
+    fn claim_reward() -> Weight;
+    fn claim_reward_with_memo() -> Weight;
+    fn claim_all() -> Weight;
+    fn payout_to() -> Weight;
+    fn allocate_reward() -> Weight;
+    /// `n` is the number of allocations.
+    fn allocate_many(n: u32) -> Weight;
+    fn revoke_allocation() -> Weight;
+    fn force_set_reward_pool() -> Weight;
+    fn withdraw_from_pool() -> Weight;
+    fn burn_pool() -> Weight;
+    fn sync_pool() -> Weight;
+    fn create_pool() -> Weight;
+    fn top_up_sub_pool() -> Weight;
+    fn claim_from_pool() -> Weight;
+    fn allocate_from_pool() -> Weight;
+    fn schedule_top_up() -> Weight;
+    fn cancel_top_up() -> Weight;
+    fn set_min_pool_reserve() -> Weight;
+    fn set_payout_destination() -> Weight;
+    fn prune_claim_history() -> Weight;
+    fn set_claim_root() -> Weight;
+    /// `p` is the number of hashes in the proof.
+    fn claim_with_proof(p: u32) -> Weight;
+    fn set_claim_signer() -> Weight;
+    fn claim_with_voucher() -> Weight;
+    fn add_claimer() -> Weight;
+    fn remove_claimer() -> Weight;
+    /// `n` is the number of accounts.
+    fn add_claimers(n: u32) -> Weight;
+    /// `n` is the number of accounts.
+    fn remove_claimers(n: u32) -> Weight;
+    fn ban_claimer() -> Weight;
+    fn unban_claimer() -> Weight;
+    fn set_eligibility_min_balance() -> Weight;
+    /// `n` is the number of payouts.
+    fn force_payout_many(n: u32) -> Weight;
+    /// `n` is the number of accounts.
+    fn sweep_expired(n: u32) -> Weight;
+    /// `n` is the number of keys.
+    fn prune_claim_keys(n: u32) -> Weight;
+    fn set_claim_delegate() -> Weight;
+    fn claim_as_delegate() -> Weight;
+    fn request_claim() -> Weight;
+    fn cancel_claim_request() -> Weight;
+    fn claim_reward_vested() -> Weight;
+    fn vest_rewards() -> Weight;
+    fn set_era_budget() -> Weight;
+    fn claim_era_share() -> Weight;
+    /// `n` is the number of point entries removed.
+    fn sweep_expired_era(n: u32) -> Weight;
+    /// `n` is the number of receipts removed.
+    fn prune_receipts(n: u32) -> Weight;
+    fn set_third_party_claims() -> Weight;
+    fn claim_for() -> Weight;
+    fn claim_block_rewards() -> Weight;
+    fn exclude_author() -> Weight;
+    fn include_author() -> Weight;
+    fn set_reward_per_block() -> Weight;
+    fn pause_block_rewards() -> Weight;
+    fn resume_block_rewards() -> Weight;
+    fn set_author_payout_account() -> Weight;
+    fn set_admin() -> Weight;
+    fn accept_admin() -> Weight;
+    fn update_params() -> Weight;
+    fn pause() -> Weight;
+    fn unpause() -> Weight;
+    fn schedule_param_change() -> Weight;
+    fn cancel_param_change() -> Weight;
+    fn set_top_up_allowance() -> Weight;
+    fn set_min_consensus_fee() -> Weight;
+    fn set_fee_split() -> Weight;
+    fn propose_payout() -> Weight;
+    fn approve_payout() -> Weight;
+    fn reject_payout() -> Weight;
+    fn remove_expired_payout() -> Weight;
+    fn add_operator() -> Weight;
+    fn remove_operator() -> Weight;
+    fn initialize() -> Weight;
+    fn freeze_params() -> Weight;
+    fn set_claim_cooldown() -> Weight;
+    fn set_min_claim_amount() -> Weight;
+    fn set_dust_threshold() -> Weight;
+    /// `r` is 1 if the block reward is paid, 0 if block rewards are paused.
+    fn on_initialize_reward(r: u32) -> Weight;
+}
+
+/// Placeholder weights for the reward pallet, see the module documentation.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    fn top_up_pool() -> Weight {
+        Weight::from_parts(45_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(6))
+            .saturating_add(T::DbWeight::get().writes(5))
+    }
+    fn force_top_up_pool() -> Weight {
+        Weight::from_parts(35_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(4))
+    }
+    fn donate_to_pool() -> Weight {
+        Weight::from_parts(42_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(5))
+    }
+    fn claim_reward() -> Weight {
+        Weight::from_parts(125_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(24))
+            .saturating_add(T::DbWeight::get().writes(17))
+    }
+    fn claim_reward_with_memo() -> Weight {
+        Weight::from_parts(118_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(23))
+            .saturating_add(T::DbWeight::get().writes(16))
+    }
+    fn claim_all() -> Weight {
+        Weight::from_parts(102_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(22))
+            .saturating_add(T::DbWeight::get().writes(14))
+    }
+    fn payout_to() -> Weight {
+        Weight::from_parts(110_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(24))
+            .saturating_add(T::DbWeight::get().writes(16))
+    }
+    fn allocate_reward() -> Weight {
+        Weight::from_parts(30_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(6))
+            .saturating_add(T::DbWeight::get().writes(3))
+    }
+    fn allocate_many(n: u32) -> Weight {
+        Weight::from_parts(24_000_000, 0)
+            .saturating_add(Weight::from_parts(16_000_000, 0).saturating_mul(n.into()))
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().reads((1u64).saturating_mul(n.into())))
+            .saturating_add(T::DbWeight::get().writes(1))
+            .saturating_add(T::DbWeight::get().writes((2u64).saturating_mul(n.into())))
+    }
+    fn revoke_allocation() -> Weight {
+        Weight::from_parts(29_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(6))
+            .saturating_add(T::DbWeight::get().writes(4))
+    }
+    fn force_set_reward_pool() -> Weight {
+        Weight::from_parts(24_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(3))
+    }
+    fn withdraw_from_pool() -> Weight {
+        Weight::from_parts(40_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(4))
+    }
+    fn burn_pool() -> Weight {
+        Weight::from_parts(38_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(4))
+    }
+    fn sync_pool() -> Weight {
+        Weight::from_parts(26_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(3))
+    }
+    fn create_pool() -> Weight {
+        Weight::from_parts(21_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn top_up_sub_pool() -> Weight {
+        Weight::from_parts(39_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(3))
+    }
+    fn claim_from_pool() -> Weight {
+        Weight::from_parts(58_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(15))
+            .saturating_add(T::DbWeight::get().writes(9))
+    }
+    fn allocate_from_pool() -> Weight {
+        Weight::from_parts(27_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn schedule_top_up() -> Weight {
+        Weight::from_parts(44_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(4))
+    }
+    fn cancel_top_up() -> Weight {
+        Weight::from_parts(42_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(3))
+    }
+    fn set_min_pool_reserve() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn set_payout_destination() -> Weight {
+        Weight::from_parts(14_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn prune_claim_history() -> Weight {
+        Weight::from_parts(19_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn set_claim_root() -> Weight {
+        Weight::from_parts(18_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn claim_with_proof(p: u32) -> Weight {
+        Weight::from_parts(118_000_000, 0)
+            .saturating_add(Weight::from_parts(1_000_000, 0).saturating_mul(p.into()))
+            .saturating_add(T::DbWeight::get().reads(23))
+            .saturating_add(T::DbWeight::get().writes(16))
+    }
+    fn set_claim_signer() -> Weight {
+        Weight::from_parts(17_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn claim_with_voucher() -> Weight {
+        Weight::from_parts(160_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(23))
+            .saturating_add(T::DbWeight::get().writes(16))
+    }
+    fn add_claimer() -> Weight {
+        Weight::from_parts(22_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn remove_claimer() -> Weight {
+        Weight::from_parts(21_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn add_claimers(n: u32) -> Weight {
+        Weight::from_parts(18_000_000, 0)
+            .saturating_add(Weight::from_parts(7_000_000, 0).saturating_mul(n.into()))
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().reads((1u64).saturating_mul(n.into())))
+            .saturating_add(T::DbWeight::get().writes(1))
+            .saturating_add(T::DbWeight::get().writes((1u64).saturating_mul(n.into())))
+    }
+    fn remove_claimers(n: u32) -> Weight {
+        Weight::from_parts(18_000_000, 0)
+            .saturating_add(Weight::from_parts(7_000_000, 0).saturating_mul(n.into()))
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().reads((1u64).saturating_mul(n.into())))
+            .saturating_add(T::DbWeight::get().writes(1))
+            .saturating_add(T::DbWeight::get().writes((1u64).saturating_mul(n.into())))
+    }
+    fn ban_claimer() -> Weight {
+        Weight::from_parts(31_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(6))
+            .saturating_add(T::DbWeight::get().writes(5))
+    }
+    fn unban_claimer() -> Weight {
+        Weight::from_parts(19_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn set_eligibility_min_balance() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn force_payout_many(n: u32) -> Weight {
+        Weight::from_parts(28_000_000, 0)
+            .saturating_add(Weight::from_parts(62_000_000, 0).saturating_mul(n.into()))
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().reads((8u64).saturating_mul(n.into())))
+            .saturating_add(T::DbWeight::get().writes(3))
+            .saturating_add(T::DbWeight::get().writes((7u64).saturating_mul(n.into())))
+    }
+    fn sweep_expired(n: u32) -> Weight {
+        Weight::from_parts(12_000_000, 0)
+            .saturating_add(Weight::from_parts(24_000_000, 0).saturating_mul(n.into()))
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().reads((4u64).saturating_mul(n.into())))
+            .saturating_add(T::DbWeight::get().writes((4u64).saturating_mul(n.into())))
+    }
+    fn prune_claim_keys(n: u32) -> Weight {
+        Weight::from_parts(11_000_000, 0)
+            .saturating_add(Weight::from_parts(6_000_000, 0).saturating_mul(n.into()))
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().reads((1u64).saturating_mul(n.into())))
+            .saturating_add(T::DbWeight::get().writes((1u64).saturating_mul(n.into())))
+    }
+    fn set_claim_delegate() -> Weight {
+        Weight::from_parts(14_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn claim_as_delegate() -> Weight {
+        Weight::from_parts(104_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(23))
+            .saturating_add(T::DbWeight::get().writes(14))
+    }
+    fn request_claim() -> Weight {
+        Weight::from_parts(22_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn cancel_claim_request() -> Weight {
+        Weight::from_parts(21_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn claim_reward_vested() -> Weight {
+        Weight::from_parts(128_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(25))
+            .saturating_add(T::DbWeight::get().writes(18))
+    }
+    fn vest_rewards() -> Weight {
+        Weight::from_parts(31_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn set_era_budget() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn claim_era_share() -> Weight {
+        Weight::from_parts(112_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(24))
+            .saturating_add(T::DbWeight::get().writes(16))
+    }
+    fn sweep_expired_era(n: u32) -> Weight {
+        Weight::from_parts(24_000_000, 0)
+            .saturating_add(Weight::from_parts(4_000_000, 0).saturating_mul(n.into()))
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(4))
+            .saturating_add(T::DbWeight::get().writes((1u64).saturating_mul(n.into())))
+    }
+    fn prune_receipts(n: u32) -> Weight {
+        Weight::from_parts(16_000_000, 0)
+            .saturating_add(Weight::from_parts(5_000_000, 0).saturating_mul(n.into()))
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(1))
+            .saturating_add(T::DbWeight::get().writes((1u64).saturating_mul(n.into())))
+    }
+    fn set_third_party_claims() -> Weight {
+        Weight::from_parts(14_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn claim_for() -> Weight {
+        Weight::from_parts(106_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(24))
+            .saturating_add(T::DbWeight::get().writes(15))
+    }
+    fn claim_block_rewards() -> Weight {
+        Weight::from_parts(58_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(10))
+            .saturating_add(T::DbWeight::get().writes(8))
+    }
+    fn exclude_author() -> Weight {
+        Weight::from_parts(22_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn include_author() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn set_reward_per_block() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn pause_block_rewards() -> Weight {
+        Weight::from_parts(15_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn resume_block_rewards() -> Weight {
+        Weight::from_parts(15_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn set_author_payout_account() -> Weight {
+        Weight::from_parts(14_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn set_admin() -> Weight {
+        Weight::from_parts(17_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn accept_admin() -> Weight {
+        Weight::from_parts(19_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn update_params() -> Weight {
+        Weight::from_parts(23_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn pause() -> Weight {
+        Weight::from_parts(12_000_000, 0)
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn unpause() -> Weight {
+        Weight::from_parts(12_000_000, 0)
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn schedule_param_change() -> Weight {
+        Weight::from_parts(26_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn cancel_param_change() -> Weight {
+        Weight::from_parts(22_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn set_top_up_allowance() -> Weight {
+        Weight::from_parts(24_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn set_min_consensus_fee() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn set_fee_split() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn propose_payout() -> Weight {
+        Weight::from_parts(27_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(3))
+    }
+    fn approve_payout() -> Weight {
+        Weight::from_parts(70_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(10))
+            .saturating_add(T::DbWeight::get().writes(8))
+    }
+    fn reject_payout() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn remove_expired_payout() -> Weight {
+        Weight::from_parts(19_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn add_operator() -> Weight {
+        Weight::from_parts(22_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn remove_operator() -> Weight {
+        Weight::from_parts(21_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn initialize() -> Weight {
+        Weight::from_parts(48_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(7))
+            .saturating_add(T::DbWeight::get().writes(7))
+    }
+    fn freeze_params() -> Weight {
+        Weight::from_parts(15_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn set_claim_cooldown() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn set_min_claim_amount() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn set_dust_threshold() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn on_initialize_reward(r: u32) -> Weight {
+        Weight::from_parts(8_000_000, 0)
+            .saturating_add(Weight::from_parts(62_000_000, 0).saturating_mul(r.into()))
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().reads((11u64).saturating_mul(r.into())))
+            .saturating_add(T::DbWeight::get().writes(1))
+            .saturating_add(T::DbWeight::get().writes((9u64).saturating_mul(r.into())))
+    }
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+    fn top_up_pool() -> Weight {
+        Weight::from_parts(45_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(6))
+            .saturating_add(RocksDbWeight::get().writes(5))
+    }
+    fn force_top_up_pool() -> Weight {
+        Weight::from_parts(35_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(5))
+            .saturating_add(RocksDbWeight::get().writes(4))
+    }
+    fn donate_to_pool() -> Weight {
+        Weight::from_parts(42_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(5))
+            .saturating_add(RocksDbWeight::get().writes(5))
+    }
+    fn claim_reward() -> Weight {
+        Weight::from_parts(125_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(24))
+            .saturating_add(RocksDbWeight::get().writes(17))
+    }
+    fn claim_reward_with_memo() -> Weight {
+        Weight::from_parts(118_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(23))
+            .saturating_add(RocksDbWeight::get().writes(16))
+    }
+    fn claim_all() -> Weight {
+        Weight::from_parts(102_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(22))
+            .saturating_add(RocksDbWeight::get().writes(14))
+    }
+    fn payout_to() -> Weight {
+        Weight::from_parts(110_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(24))
+            .saturating_add(RocksDbWeight::get().writes(16))
+    }
+    fn allocate_reward() -> Weight {
+        Weight::from_parts(30_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(6))
+            .saturating_add(RocksDbWeight::get().writes(3))
+    }
+    fn allocate_many(n: u32) -> Weight {
+        Weight::from_parts(24_000_000, 0)
+            .saturating_add(Weight::from_parts(16_000_000, 0).saturating_mul(n.into()))
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().reads((1u64).saturating_mul(n.into())))
+            .saturating_add(RocksDbWeight::get().writes(1))
+            .saturating_add(RocksDbWeight::get().writes((2u64).saturating_mul(n.into())))
+    }
+    fn revoke_allocation() -> Weight {
+        Weight::from_parts(29_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(6))
+            .saturating_add(RocksDbWeight::get().writes(4))
+    }
+    fn force_set_reward_pool() -> Weight {
+        Weight::from_parts(24_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(5))
+            .saturating_add(RocksDbWeight::get().writes(3))
+    }
+    fn withdraw_from_pool() -> Weight {
+        Weight::from_parts(40_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(5))
+            .saturating_add(RocksDbWeight::get().writes(4))
+    }
+    fn burn_pool() -> Weight {
+        Weight::from_parts(38_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(5))
+            .saturating_add(RocksDbWeight::get().writes(4))
+    }
+    fn sync_pool() -> Weight {
+        Weight::from_parts(26_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(5))
+            .saturating_add(RocksDbWeight::get().writes(3))
+    }
+    fn create_pool() -> Weight {
+        Weight::from_parts(21_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+    fn top_up_sub_pool() -> Weight {
+        Weight::from_parts(39_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(3))
+    }
+    fn claim_from_pool() -> Weight {
+        Weight::from_parts(58_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(15))
+            .saturating_add(RocksDbWeight::get().writes(9))
+    }
+    fn allocate_from_pool() -> Weight {
+        Weight::from_parts(27_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(5))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+    fn schedule_top_up() -> Weight {
+        Weight::from_parts(44_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(5))
+            .saturating_add(RocksDbWeight::get().writes(4))
+    }
+    fn cancel_top_up() -> Weight {
+        Weight::from_parts(42_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(3))
+    }
+    fn set_min_pool_reserve() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn set_payout_destination() -> Weight {
+        Weight::from_parts(14_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn prune_claim_history() -> Weight {
+        Weight::from_parts(19_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn set_claim_root() -> Weight {
+        Weight::from_parts(18_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn claim_with_proof(p: u32) -> Weight {
+        Weight::from_parts(118_000_000, 0)
+            .saturating_add(Weight::from_parts(1_000_000, 0).saturating_mul(p.into()))
+            .saturating_add(RocksDbWeight::get().reads(23))
+            .saturating_add(RocksDbWeight::get().writes(16))
+    }
+    fn set_claim_signer() -> Weight {
+        Weight::from_parts(17_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn claim_with_voucher() -> Weight {
+        Weight::from_parts(160_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(23))
+            .saturating_add(RocksDbWeight::get().writes(16))
+    }
+    fn add_claimer() -> Weight {
+        Weight::from_parts(22_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+    fn remove_claimer() -> Weight {
+        Weight::from_parts(21_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+    fn add_claimers(n: u32) -> Weight {
+        Weight::from_parts(18_000_000, 0)
+            .saturating_add(Weight::from_parts(7_000_000, 0).saturating_mul(n.into()))
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().reads((1u64).saturating_mul(n.into())))
+            .saturating_add(RocksDbWeight::get().writes(1))
+            .saturating_add(RocksDbWeight::get().writes((1u64).saturating_mul(n.into())))
+    }
+    fn remove_claimers(n: u32) -> Weight {
+        Weight::from_parts(18_000_000, 0)
+            .saturating_add(Weight::from_parts(7_000_000, 0).saturating_mul(n.into()))
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().reads((1u64).saturating_mul(n.into())))
+            .saturating_add(RocksDbWeight::get().writes(1))
+            .saturating_add(RocksDbWeight::get().writes((1u64).saturating_mul(n.into())))
+    }
+    fn ban_claimer() -> Weight {
+        Weight::from_parts(31_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(6))
+            .saturating_add(RocksDbWeight::get().writes(5))
+    }
+    fn unban_claimer() -> Weight {
+        Weight::from_parts(19_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn set_eligibility_min_balance() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn force_payout_many(n: u32) -> Weight {
+        Weight::from_parts(28_000_000, 0)
+            .saturating_add(Weight::from_parts(62_000_000, 0).saturating_mul(n.into()))
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().reads((8u64).saturating_mul(n.into())))
+            .saturating_add(RocksDbWeight::get().writes(3))
+            .saturating_add(RocksDbWeight::get().writes((7u64).saturating_mul(n.into())))
+    }
+    fn sweep_expired(n: u32) -> Weight {
+        Weight::from_parts(12_000_000, 0)
+            .saturating_add(Weight::from_parts(24_000_000, 0).saturating_mul(n.into()))
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().reads((4u64).saturating_mul(n.into())))
+            .saturating_add(RocksDbWeight::get().writes((4u64).saturating_mul(n.into())))
+    }
+    fn prune_claim_keys(n: u32) -> Weight {
+        Weight::from_parts(11_000_000, 0)
+            .saturating_add(Weight::from_parts(6_000_000, 0).saturating_mul(n.into()))
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().reads((1u64).saturating_mul(n.into())))
+            .saturating_add(RocksDbWeight::get().writes((1u64).saturating_mul(n.into())))
+    }
+    fn set_claim_delegate() -> Weight {
+        Weight::from_parts(14_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn claim_as_delegate() -> Weight {
+        Weight::from_parts(104_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(23))
+            .saturating_add(RocksDbWeight::get().writes(14))
+    }
+    fn request_claim() -> Weight {
+        Weight::from_parts(22_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+    fn cancel_claim_request() -> Weight {
+        Weight::from_parts(21_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn claim_reward_vested() -> Weight {
+        Weight::from_parts(128_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(25))
+            .saturating_add(RocksDbWeight::get().writes(18))
+    }
+    fn vest_rewards() -> Weight {
+        Weight::from_parts(31_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+    fn set_era_budget() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn claim_era_share() -> Weight {
+        Weight::from_parts(112_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(24))
+            .saturating_add(RocksDbWeight::get().writes(16))
+    }
+    fn sweep_expired_era(n: u32) -> Weight {
+        Weight::from_parts(24_000_000, 0)
+            .saturating_add(Weight::from_parts(4_000_000, 0).saturating_mul(n.into()))
+            .saturating_add(RocksDbWeight::get().reads(5))
+            .saturating_add(RocksDbWeight::get().writes(4))
+            .saturating_add(RocksDbWeight::get().writes((1u64).saturating_mul(n.into())))
+    }
+    fn prune_receipts(n: u32) -> Weight {
+        Weight::from_parts(16_000_000, 0)
+            .saturating_add(Weight::from_parts(5_000_000, 0).saturating_mul(n.into()))
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(1))
+            .saturating_add(RocksDbWeight::get().writes((1u64).saturating_mul(n.into())))
+    }
+    fn set_third_party_claims() -> Weight {
+        Weight::from_parts(14_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn claim_for() -> Weight {
+        Weight::from_parts(106_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(24))
+            .saturating_add(RocksDbWeight::get().writes(15))
+    }
+    fn claim_block_rewards() -> Weight {
+        Weight::from_parts(58_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(10))
+            .saturating_add(RocksDbWeight::get().writes(8))
+    }
+    fn exclude_author() -> Weight {
+        Weight::from_parts(22_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+    fn include_author() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+    fn set_reward_per_block() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn pause_block_rewards() -> Weight {
+        Weight::from_parts(15_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn resume_block_rewards() -> Weight {
+        Weight::from_parts(15_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn set_author_payout_account() -> Weight {
+        Weight::from_parts(14_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn set_admin() -> Weight {
+        Weight::from_parts(17_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn accept_admin() -> Weight {
+        Weight::from_parts(19_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+    fn update_params() -> Weight {
+        Weight::from_parts(23_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn pause() -> Weight {
+        Weight::from_parts(12_000_000, 0)
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn unpause() -> Weight {
+        Weight::from_parts(12_000_000, 0)
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn schedule_param_change() -> Weight {
+        Weight::from_parts(26_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(5))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+    fn cancel_param_change() -> Weight {
+        Weight::from_parts(22_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn set_top_up_allowance() -> Weight {
+        Weight::from_parts(24_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(5))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+    fn set_min_consensus_fee() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn set_fee_split() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn propose_payout() -> Weight {
+        Weight::from_parts(27_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(5))
+            .saturating_add(RocksDbWeight::get().writes(3))
+    }
+    fn approve_payout() -> Weight {
+        Weight::from_parts(70_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(10))
+            .saturating_add(RocksDbWeight::get().writes(8))
+    }
+    fn reject_payout() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+    fn remove_expired_payout() -> Weight {
+        Weight::from_parts(19_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+    fn add_operator() -> Weight {
+        Weight::from_parts(22_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+    fn remove_operator() -> Weight {
+        Weight::from_parts(21_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+    fn initialize() -> Weight {
+        Weight::from_parts(48_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(7))
+            .saturating_add(RocksDbWeight::get().writes(7))
+    }
+    fn freeze_params() -> Weight {
+        Weight::from_parts(15_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn set_claim_cooldown() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn set_min_claim_amount() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn set_dust_threshold() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn on_initialize_reward(r: u32) -> Weight {
+        Weight::from_parts(8_000_000, 0)
+            .saturating_add(Weight::from_parts(62_000_000, 0).saturating_mul(r.into()))
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().reads((11u64).saturating_mul(r.into())))
+            .saturating_add(RocksDbWeight::get().writes(1))
+            .saturating_add(RocksDbWeight::get().writes((9u64).saturating_mul(r.into())))
+    }
+}